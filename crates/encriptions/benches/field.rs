@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use encriptions::field::{FiniteFieldElement, Secp256k1Prime};
+
+type Fe = FiniteFieldElement<Secp256k1Prime>;
+
+// Neg and From<i64> on negative values reduce a signed integer modulo p; Sub is a baseline.
+fn bench_signed_reduction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signed_reduction");
+    let a = Fe::from(-170);
+    let b = Fe::from(42);
+
+    group.bench_function("neg", |bench| bench.iter(|| -black_box(a.clone())));
+    group.bench_function("sub", |bench| bench.iter(|| black_box(&b) - black_box(&a)));
+    group.bench_function("from_negative_i64", |bench| {
+        bench.iter(|| Fe::from(black_box(-170)))
+    });
+    group.finish();
+}
+
+// A chain of multiplications, as in scalar multiplication, in BigUint and Montgomery form.
+fn bench_mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mul");
+    let a = Fe::from(-170);
+    let b = Fe::from(42);
+
+    group.bench_function("biguint", |bench| {
+        bench.iter(|| (0..64).fold(black_box(a.clone()), |acc, _| &acc * &b))
+    });
+
+    #[cfg(feature = "montgomery")]
+    {
+        use encriptions::montgomery::MontgomeryFieldElement;
+
+        let a = MontgomeryFieldElement::from(a.clone());
+        let b = MontgomeryFieldElement::from(b.clone());
+        group.bench_function("montgomery", |bench| {
+            bench.iter(|| (0..64).fold(black_box(a), |acc, _| acc * b))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_signed_reduction, bench_mul);
+criterion_main!(benches);
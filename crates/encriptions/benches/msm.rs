@@ -0,0 +1,76 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use encriptions::ecdsa::{verify_batch, RecoverableSignature};
+use encriptions::fe256::Fe256;
+use encriptions::field::ScalarFieldElement;
+use encriptions::keys::{PrivateKey, PublicKey};
+use encriptions::msm::{pippenger, strauss};
+use encriptions::point::{GeneralPoint, S256Point};
+use num::{BigInt, BigUint};
+
+fn generator() -> S256Point {
+    let coordinate = |hex: &str| {
+        Fe256::from_biguint(&BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()).unwrap()
+    };
+    S256Point::new(GeneralPoint::finite(
+        coordinate("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        coordinate("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+    ))
+    .unwrap()
+}
+
+fn terms(n: usize) -> Vec<(BigInt, S256Point)> {
+    let g = generator();
+    (1..=n as u64)
+        .map(|i| {
+            let scalar = BigInt::from(0x9e37_79b9_7f4a_7c15u64).pow(4) * i;
+            (scalar, BigInt::from(i + 1) * g)
+        })
+        .collect()
+}
+
+fn bench_msm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msm");
+    group.sample_size(10);
+
+    for n in [8, 32, 128, 256] {
+        let input = terms(n);
+        group.bench_with_input(BenchmarkId::new("strauss", n), &input, |b, input| {
+            b.iter(|| strauss(input))
+        });
+        group.bench_with_input(BenchmarkId::new("pippenger", n), &input, |b, input| {
+            b.iter(|| pippenger(input))
+        });
+    }
+    group.finish();
+}
+
+fn bench_verify_batch(c: &mut Criterion) {
+    let items: Vec<(BigUint, RecoverableSignature, PublicKey)> = (1..=16u64)
+        .map(|i| {
+            let key = PrivateKey::new(ScalarFieldElement::from(i as i64 * 7919)).unwrap();
+            let z = BigUint::from(i).pow(40u32);
+            let (signature, recovery_id) = key.sign_recoverable(&z);
+            let sig = RecoverableSignature {
+                signature,
+                recovery_id,
+                compressed: true,
+            };
+            (z, sig, *key.public_key())
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("ecdsa_verify_16");
+    group.sample_size(10);
+    group.bench_function("one_by_one", |b| {
+        b.iter(|| {
+            items
+                .iter()
+                .all(|(z, sig, key)| key.verify(z, &sig.signature))
+        })
+    });
+    group.bench_function("batch", |b| b.iter(|| verify_batch(&items)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_msm, bench_verify_batch);
+criterion_main!(benches);
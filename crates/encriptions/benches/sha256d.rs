@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use encriptions::block::{check_pow, merkle_root};
+use encriptions::hashes::Txid;
+use encriptions::sha256d::hash256;
+
+// 64 bytes is a merkle node, 80 a block header. Any speedup is sha2's runtime detection, so
+// compare a default run against one with sha2's portable code forced:
+//
+//     cargo bench --bench sha256d -- --save-baseline accelerated
+//     cargo bench --bench sha256d --features portable-sha2 -- --baseline accelerated
+fn bench_double_sha256(c: &mut Criterion) {
+    let mut group = c.benchmark_group("double_sha256");
+    for len in [64, 80, 1024] {
+        let data = vec![0x5a; len];
+        group.bench_with_input(BenchmarkId::from_parameter(len), &data, |b, data| {
+            b.iter(|| hash256(black_box(data)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_block_checks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("block_checks");
+    let txids: Vec<Txid> = (0..2048u32).map(|i| Txid::hash(&i.to_le_bytes())).collect();
+    let header = [0x5a; 80];
+
+    group.bench_function("merkle_root_2048", |b| {
+        b.iter(|| merkle_root(black_box(&txids)))
+    });
+    group.bench_function("check_pow", |b| b.iter(|| check_pow(black_box(&header))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_double_sha256, bench_block_checks);
+criterion_main!(benches);
@@ -0,0 +1,315 @@
+use crate::base58::{decode_base58check, encode_base58check, Base58Error};
+use crate::bech32::{self, decode_segwit_address, encode_segwit_address, Bech32Error};
+use crate::hashes::Hash160;
+use crate::network::Network;
+use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Address {
+    P2pkh {
+        network: Network,
+        hash: Hash160,
+    },
+    P2sh {
+        network: Network,
+        hash: Hash160,
+    },
+    P2wpkh {
+        network: Network,
+        hash: Hash160,
+    },
+    P2wsh {
+        network: Network,
+        hash: [u8; 32],
+    },
+    // The tweaked x-only output key.
+    P2tr {
+        network: Network,
+        output_key: [u8; 32],
+    },
+}
+
+impl Address {
+    pub fn network(&self) -> Network {
+        match *self {
+            Address::P2pkh { network, .. }
+            | Address::P2sh { network, .. }
+            | Address::P2wpkh { network, .. }
+            | Address::P2wsh { network, .. }
+            | Address::P2tr { network, .. } => network,
+        }
+    }
+
+    // The output script that pays to this address.
+    pub fn script_pubkey(&self) -> Vec<u8> {
+        let mut script = Vec::with_capacity(34);
+        match self {
+            // OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG
+            Address::P2pkh { hash, .. } => {
+                script.extend([0x76, 0xa9, 0x14]);
+                script.extend(hash.as_byte_array());
+                script.extend([0x88, 0xac]);
+            }
+            // OP_HASH160 <hash> OP_EQUAL
+            Address::P2sh { hash, .. } => {
+                script.extend([0xa9, 0x14]);
+                script.extend(hash.as_byte_array());
+                script.push(0x87);
+            }
+            // OP_n <program>
+            _ => {
+                let (version, program) = self.witness_program().unwrap();
+                script.push(if version == 0 { 0x00 } else { 0x50 + version });
+                script.push(program.len() as u8);
+                script.extend(program);
+            }
+        }
+        script
+    }
+
+    fn witness_program(&self) -> Option<(u8, &[u8])> {
+        match self {
+            Address::P2wpkh { hash, .. } => Some((0, hash.as_byte_array())),
+            Address::P2wsh { hash, .. } => Some((0, hash)),
+            Address::P2tr { output_key, .. } => Some((1, output_key)),
+            Address::P2pkh { .. } | Address::P2sh { .. } => None,
+        }
+    }
+
+    fn from_base58check(s: &str) -> Result<Self, AddressError> {
+        let (prefix, payload) = decode_base58check(s).map_err(AddressError::Base58)?;
+        // Signet and regtest share testnet's prefixes, which decode as testnet.
+        let (network, script_hash) = Network::ALL
+            .iter()
+            .find_map(|&network| {
+                if prefix == network.p2pkh_prefix() {
+                    Some((network, false))
+                } else if prefix == network.p2sh_prefix() {
+                    Some((network, true))
+                } else {
+                    None
+                }
+            })
+            .ok_or(AddressError::UnknownPrefix(prefix))?;
+        let hash = Hash160::from_slice(&payload)
+            .map_err(|_| AddressError::InvalidLength(payload.len()))?;
+        Ok(if script_hash {
+            Address::P2sh { network, hash }
+        } else {
+            Address::P2pkh { network, hash }
+        })
+    }
+
+    fn from_segwit(network: Network, s: &str) -> Result<Self, AddressError> {
+        let (version, program) =
+            decode_segwit_address(network.bech32_hrp(), s).map_err(AddressError::Bech32)?;
+        match (version, program.len()) {
+            (0, 20) => Ok(Address::P2wpkh {
+                network,
+                hash: Hash160::from_slice(&program).unwrap(),
+            }),
+            (0, 32) => Ok(Address::P2wsh {
+                network,
+                hash: program.try_into().unwrap(),
+            }),
+            (1, 32) => Ok(Address::P2tr {
+                network,
+                output_key: program.try_into().unwrap(),
+            }),
+            (version, len) => Err(AddressError::UnsupportedWitnessProgram { version, len }),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let network = self.network();
+        let encoded = match self {
+            Address::P2pkh { hash, .. } => {
+                encode_base58check(network.p2pkh_prefix(), hash.as_byte_array())
+            }
+            Address::P2sh { hash, .. } => {
+                encode_base58check(network.p2sh_prefix(), hash.as_byte_array())
+            }
+            _ => {
+                let (version, program) = self.witness_program().unwrap();
+                encode_segwit_address(network.bech32_hrp(), version, program)
+                    .expect("P2WPKH, P2WSH and P2TR programs have valid lengths")
+            }
+        };
+        f.write_str(&encoded)
+    }
+}
+
+// Segwit addresses are recognised by a known hrp before the last '1'; anything else is tried as
+// Base58Check.
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_ascii_lowercase();
+        if let Some(separator) = lower.rfind('1') {
+            let hrp = &lower[..separator];
+            if let Some(&network) = Network::ALL.iter().find(|n| n.bech32_hrp() == hrp) {
+                return Self::from_segwit(network, s);
+            }
+        }
+        if bech32::decode(s).is_ok() {
+            return Err(AddressError::UnknownHrp);
+        }
+        Self::from_base58check(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    Base58(Base58Error),
+    Bech32(Bech32Error),
+    UnknownPrefix(u8),
+    InvalidLength(usize),
+    UnknownHrp,
+    UnsupportedWitnessProgram { version: u8, len: usize },
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::Base58(e) => write!(f, "invalid base58 address: {}", e),
+            AddressError::Bech32(e) => write!(f, "invalid segwit address: {}", e),
+            AddressError::UnknownPrefix(prefix) => {
+                write!(f, "unknown address prefix 0x{:02x}", prefix)
+            }
+            AddressError::InvalidLength(len) => {
+                write!(f, "address hash must be 20 bytes, got {}", len)
+            }
+            AddressError::UnknownHrp => write!(f, "segwit address for an unknown network"),
+            AddressError::UnsupportedWitnessProgram { version, len } => write!(
+                f,
+                "no address type for a {}-byte version {} witness program",
+                len, version
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn parse_and_display() {
+        for (s, network, script) in [
+            (
+                "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1",
+                Network::Mainnet,
+                "76a91499a4c61750789253f69fd750ac0d02126337330588ac",
+            ),
+            (
+                "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+                Network::Mainnet,
+                "a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87",
+            ),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                Network::Mainnet,
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                Network::Testnet,
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                Network::Mainnet,
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ] {
+            let address: Address = s.parse().unwrap();
+            assert_eq!(address.network(), network);
+            assert_eq!(hex::encode(&address.script_pubkey()), script);
+            assert_eq!(address.to_string(), s);
+        }
+
+        let upper: Address = "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"
+            .parse()
+            .unwrap();
+        assert!(matches!(upper, Address::P2wpkh { .. }));
+        assert_eq!(
+            upper.to_string(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+    }
+
+    #[test]
+    fn other_networks() {
+        let hash: Hash160 = "751e76e8199196d454941c45d1b3a323f1433bd6".parse().unwrap();
+        let regtest = Address::P2wpkh {
+            network: Network::Regtest,
+            hash,
+        };
+        assert_eq!(
+            regtest.to_string(),
+            "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+        );
+        assert_eq!(regtest.to_string().parse(), Ok(regtest));
+
+        // Signet shares testnet's hrp and every network but mainnet shares its Base58 prefixes,
+        // so those parse back as testnet.
+        let signet = Address::P2wpkh {
+            network: Network::Signet,
+            hash,
+        };
+        assert_eq!(
+            signet.to_string().parse::<Address>().unwrap().network(),
+            Network::Testnet
+        );
+        let legacy = Address::P2pkh {
+            network: Network::Regtest,
+            hash,
+        };
+        let testnet = Address::P2pkh {
+            network: Network::Testnet,
+            hash,
+        };
+        assert_eq!(legacy.to_string(), testnet.to_string());
+        assert_eq!(legacy.to_string().parse(), Ok(testnet));
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        // A WIF private key, not an address.
+        assert_eq!(
+            "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ".parse::<Address>(),
+            Err(AddressError::UnknownPrefix(0x80))
+        );
+        assert!(matches!(
+            "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF2".parse::<Address>(),
+            Err(AddressError::Base58(Base58Error::ChecksumMismatch { .. }))
+        ));
+        assert_eq!(
+            encode_base58check(0x00, &[0; 21]).parse::<Address>(),
+            Err(AddressError::InvalidLength(21))
+        );
+        assert_eq!(
+            "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty".parse::<Address>(),
+            Err(AddressError::UnknownHrp)
+        );
+        assert_eq!(
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5".parse::<Address>(),
+            Err(AddressError::Bech32(Bech32Error::InvalidChecksum))
+        );
+        assert_eq!(
+            "BC1SW50QGDZ25J".parse::<Address>(),
+            Err(AddressError::UnsupportedWitnessProgram {
+                version: 16,
+                len: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// Counts allocations per thread so that parallel tests do not see each other's work.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|c| c.set(c.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|c| c.set(c.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.with(|c| c.get())
+}
+
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = allocation_count();
+    let result = f();
+    (result, allocation_count() - before)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{OverField, Secp256k1};
+    use crate::fe256::Fe256;
+    use crate::field::{ConstantTimeEq, Field, FiniteFieldElement, Secp256k1Prime};
+    use crate::point::{GeneralPoint, PointOnCurve, S256Point};
+    use num::BigInt;
+    use num_traits::Pow;
+    use std::fmt::Debug;
+    use std::str::FromStr;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    // Regression thresholds, set slightly above the counts measured when they were added.
+    const BIG_FIELD_ADD_MAX: usize = 6;
+    const BIG_FIELD_MUL_MAX: usize = 6;
+    const BIG_FIELD_DIV_MAX: usize = 32;
+    // The copy of the prime used to size the comparison; the values themselves are not copied.
+    const BIG_FIELD_CT_EQ_MAX: usize = 1;
+    const FE256_OP_MAX: usize = 0;
+    // The BigUint's own digit buffer.
+    const FE256_TO_BIGUINT_MAX: usize = 1;
+    const FE256_SCALAR_MUL_MAX: usize = 4_500;
+    const BIG_FIELD_SCALAR_MUL_MAX: usize = 320_000;
+
+    fn assert_at_most<R>(name: &str, max: usize, f: impl FnOnce() -> R) -> R {
+        let (result, count) = count_allocations(f);
+        assert!(
+            count <= max,
+            "{}: {} allocations, threshold {}",
+            name,
+            count,
+            max
+        );
+        result
+    }
+
+    fn generator<T: Field + FromStr>() -> GeneralPoint<T>
+    where
+        T::Err: Debug,
+    {
+        GeneralPoint::finite(
+            "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+                .parse()
+                .unwrap(),
+            "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+                .parse()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn counts_vec_allocations() {
+        let (_, count) = count_allocations(|| vec![0u8; 16]);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn finite_field_ops() {
+        let a: FiniteFieldElement<Secp256k1Prime> = FiniteFieldElement::from(-170);
+        let b = FiniteFieldElement::from(-42);
+
+        assert_at_most("FiniteFieldElement add", BIG_FIELD_ADD_MAX, || &a + &b);
+        assert_at_most("FiniteFieldElement mul", BIG_FIELD_MUL_MAX, || &a * &b);
+        assert_at_most("FiniteFieldElement div", BIG_FIELD_DIV_MAX, || &a / &b);
+        assert_at_most("FiniteFieldElement ct_eq", BIG_FIELD_CT_EQ_MAX, || {
+            a.ct_eq(&b)
+        });
+    }
+
+    #[test]
+    fn fe256_ops_do_not_allocate() {
+        let a = Fe256::from(-170);
+        let b = Fe256::from(42);
+
+        assert_at_most("Fe256 add", FE256_OP_MAX, || a + b);
+        assert_at_most("Fe256 sub", FE256_OP_MAX, || a - b);
+        assert_at_most("Fe256 mul", FE256_OP_MAX, || a * b);
+    }
+
+    #[test]
+    fn fe256_conversions() {
+        let value = S256Point::generator().x().unwrap().to_biguint();
+        let too_wide = &value << 8;
+
+        let fe = assert_at_most("Fe256 from_biguint", FE256_OP_MAX, || {
+            Fe256::from_biguint(&value)
+        });
+        assert_at_most("Fe256 from_biguint out of range", FE256_OP_MAX, || {
+            Fe256::from_biguint(&too_wide)
+        });
+        assert_at_most("Fe256 to_biguint", FE256_TO_BIGUINT_MAX, || {
+            fe.unwrap().to_biguint()
+        });
+    }
+
+    #[cfg(feature = "montgomery")]
+    #[test]
+    fn montgomery_ops_do_not_allocate() {
+        use crate::montgomery::MontgomeryFieldElement;
+
+        let a = MontgomeryFieldElement::<Secp256k1Prime>::from(-170);
+        let b = MontgomeryFieldElement::from(42);
+
+        assert_at_most("Montgomery add", FE256_OP_MAX, || a + b);
+        assert_at_most("Montgomery sub", FE256_OP_MAX, || a - b);
+        assert_at_most("Montgomery mul", FE256_OP_MAX, || a * b);
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let k = BigInt::from(2).pow(200u32) - 12345;
+
+        let g = S256Point::new(generator()).unwrap();
+        assert_at_most("S256Point mul_wnaf", FE256_SCALAR_MUL_MAX, || {
+            g.mul_wnaf(&k)
+        });
+
+        let g: PointOnCurve<_, OverField<Secp256k1, FiniteFieldElement<Secp256k1Prime>>> =
+            PointOnCurve::new(generator()).unwrap();
+        assert_at_most("BigUint point mul_wnaf", BIG_FIELD_SCALAR_MUL_MAX, || {
+            g.mul_wnaf(&k)
+        });
+    }
+}
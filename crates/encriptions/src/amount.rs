@@ -0,0 +1,144 @@
+use std::fmt;
+use std::str::FromStr;
+
+const SAT_PER_BTC: u64 = 100_000_000;
+
+// A non-negative number of satoshis, at most the 21 million BTC supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Amount(u64);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+    pub const ONE_BTC: Amount = Amount(SAT_PER_BTC);
+    pub const MAX_MONEY: Amount = Amount(21_000_000 * SAT_PER_BTC);
+
+    pub fn from_sat(sat: u64) -> Result<Self, AmountError> {
+        if sat > Self::MAX_MONEY.0 {
+            return Err(AmountError::TooLarge);
+        }
+        Ok(Amount(sat))
+    }
+
+    pub fn to_sat(self) -> u64 {
+        self.0
+    }
+}
+
+// Decimal BTC with trailing zeros dropped, e.g. "0.01" or "20.3", as BIP21 writes amounts.
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (whole, frac) = (self.0 / SAT_PER_BTC, self.0 % SAT_PER_BTC);
+        if frac == 0 {
+            return write!(f, "{}", whole);
+        }
+        let frac = format!("{:08}", frac);
+        write!(f, "{}.{}", whole, frac.trim_end_matches('0'))
+    }
+}
+
+// Parses decimal BTC with at most eight fractional digits; no sign or exponent.
+impl FromStr for Amount {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+        if whole.is_empty() && frac.is_empty() {
+            return Err(AmountError::Empty);
+        }
+        if let Some(c) = whole
+            .chars()
+            .chain(frac.chars())
+            .find(|c| !c.is_ascii_digit())
+        {
+            return Err(AmountError::InvalidCharacter(c));
+        }
+        if frac.len() > 8 {
+            return Err(AmountError::TooPrecise);
+        }
+
+        let mut sat: u64 = 0;
+        for digit in whole.bytes().chain(frac.bytes()) {
+            sat = sat
+                .checked_mul(10)
+                .and_then(|sat| sat.checked_add((digit - b'0') as u64))
+                .ok_or(AmountError::TooLarge)?;
+        }
+        let sat = sat
+            .checked_mul(10u64.pow(8 - frac.len() as u32))
+            .ok_or(AmountError::TooLarge)?;
+        Amount::from_sat(sat)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountError {
+    Empty,
+    InvalidCharacter(char),
+    TooPrecise,
+    TooLarge,
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountError::Empty => write!(f, "empty amount"),
+            AmountError::InvalidCharacter(c) => write!(f, "invalid character {:?} in amount", c),
+            AmountError::TooPrecise => write!(f, "amount has more than eight decimal places"),
+            AmountError::TooLarge => write!(f, "amount exceeds 21 million BTC"),
+        }
+    }
+}
+
+impl std::error::Error for AmountError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        for (s, sat, displayed) in [
+            ("0.01", 1_000_000, "0.01"),
+            ("20.3", 2_030_000_000, "20.3"),
+            ("1", 100_000_000, "1"),
+            ("1.", 100_000_000, "1"),
+            (".5", 50_000_000, "0.5"),
+            ("0.00000001", 1, "0.00000001"),
+            ("21000000", 21_000_000 * SAT_PER_BTC, "21000000"),
+        ] {
+            let amount: Amount = s.parse().unwrap();
+            assert_eq!(amount.to_sat(), sat);
+            assert_eq!(amount.to_string(), displayed);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert_eq!("".parse::<Amount>(), Err(AmountError::Empty));
+        assert_eq!(".".parse::<Amount>(), Err(AmountError::Empty));
+        assert_eq!(
+            "-1".parse::<Amount>(),
+            Err(AmountError::InvalidCharacter('-'))
+        );
+        assert_eq!(
+            "1e3".parse::<Amount>(),
+            Err(AmountError::InvalidCharacter('e'))
+        );
+        assert_eq!(
+            "0.000000001".parse::<Amount>(),
+            Err(AmountError::TooPrecise)
+        );
+        assert_eq!(
+            "21000000.00000001".parse::<Amount>(),
+            Err(AmountError::TooLarge)
+        );
+        assert_eq!(
+            "99999999999999999999".parse::<Amount>(),
+            Err(AmountError::TooLarge)
+        );
+        assert_eq!(
+            Amount::from_sat(Amount::MAX_MONEY.to_sat() + 1),
+            Err(AmountError::TooLarge)
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use crate::hashes::hash256;
+use num::BigUint;
+use std::convert::TryInto;
+use std::fmt;
+
+const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Each leading zero byte becomes a leading '1'; the rest is the big-endian number in base 58.
+pub fn encode_base58(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut digits = BigUint::from_bytes_be(bytes).to_radix_be(58);
+    if digits == [0] {
+        digits.clear();
+    }
+    std::iter::repeat_n(b'1', zeros)
+        .chain(digits.into_iter().map(|d| ALPHABET[d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+// Any string is safe to pass in: the first character outside the alphabet is reported with its
+// byte offset.
+pub fn decode_base58(s: &str) -> Result<Vec<u8>, Base58Error> {
+    let digits = s
+        .char_indices()
+        .map(|(position, character)| {
+            ALPHABET
+                .iter()
+                .position(|&a| a as char == character)
+                .map(|d| d as u8)
+                .ok_or(Base58Error::InvalidCharacter {
+                    character,
+                    position,
+                })
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+    let mut out = vec![0; zeros];
+    if zeros < digits.len() {
+        let value =
+            BigUint::from_radix_be(&digits[zeros..], 58).expect("every digit is below the radix");
+        out.extend(value.to_bytes_be());
+    }
+    Ok(out)
+}
+
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = hash256(data);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// data || first four bytes of hash256(data). Extended keys use this directly with their
+// four-byte version.
+pub(crate) fn encode_check(data: &[u8]) -> String {
+    let mut data = data.to_vec();
+    data.extend(checksum(&data));
+    encode_base58(&data)
+}
+
+pub(crate) fn decode_check(s: &str) -> Result<Vec<u8>, Base58Error> {
+    let mut data = decode_base58(s)?;
+    if data.len() < 4 {
+        return Err(Base58Error::TooShort(data.len()));
+    }
+    let actual: [u8; 4] = data.split_off(data.len() - 4).try_into().unwrap();
+    let expected = checksum(&data);
+    if expected != actual {
+        return Err(Base58Error::ChecksumMismatch { expected, actual });
+    }
+    Ok(data)
+}
+
+// A one-byte version followed by the payload, as used by addresses and WIF.
+pub fn encode_base58check(version: u8, payload: &[u8]) -> String {
+    let mut data = vec![version];
+    data.extend(payload);
+    encode_check(&data)
+}
+
+pub fn decode_base58check(s: &str) -> Result<(u8, Vec<u8>), Base58Error> {
+    let data = decode_check(s)?;
+    match data.split_first() {
+        Some((&version, payload)) => Ok((version, payload.to_vec())),
+        // Four checksum bytes and nothing else.
+        None => Err(Base58Error::TooShort(4)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base58Error {
+    InvalidCharacter { character: char, position: usize },
+    TooShort(usize),
+    // `expected` is recomputed from the data, `actual` is what the string carried.
+    ChecksumMismatch { expected: [u8; 4], actual: [u8; 4] },
+}
+
+impl fmt::Display for Base58Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base58Error::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid base58 character {:?} at position {}",
+                character, position
+            ),
+            Base58Error::TooShort(len) => {
+                write!(f, "{} bytes is too short for a Base58Check string", len)
+            }
+            Base58Error::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {:02x?}, found {:02x?}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Base58Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use proptest::prelude::*;
+
+    #[test]
+    fn round_trips() {
+        for bytes in [&[][..], &[0], &[0, 0, 1], &[0xff; 20]] {
+            assert_eq!(decode_base58(&encode_base58(bytes)).as_deref(), Ok(bytes));
+        }
+        assert_eq!(encode_base58(&[0, 0, 0x39]), "11z");
+        assert_eq!(encode_base58(&[0x3a]), "21");
+
+        let encoded = encode_base58check(0x6f, &[1, 2, 3]);
+        assert_eq!(decode_base58check(&encoded), Ok((0x6f, vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn checksum_errors() {
+        // Chapter 4 exercise 5: the compressed mainnet address of 0x12345deadbeef.
+        let address = "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1";
+        let (version, payload) = decode_base58check(address).unwrap();
+        assert_eq!(version, 0);
+        assert_eq!(
+            crate::hashes::Hash160::from_slice(&payload)
+                .unwrap()
+                .to_string(),
+            "99a4c61750789253f69fd750ac0d021263373305"
+        );
+        assert_eq!(encode_base58check(version, &payload), address);
+
+        let mut data = decode_base58(address).unwrap();
+        let expected = [data[21], data[22], data[23], data[24]];
+        data[24] ^= 1;
+        assert_eq!(
+            decode_base58check(&encode_base58(&data)),
+            Err(Base58Error::ChecksumMismatch {
+                expected,
+                actual: [expected[0], expected[1], expected[2], expected[3] ^ 1]
+            })
+        );
+        // Corrupting the payload leaves the stored checksum as the actual one.
+        let mut data = decode_base58(address).unwrap();
+        data[1] ^= 1;
+        assert!(matches!(
+            decode_base58check(&encode_base58(&data)),
+            Err(Base58Error::ChecksumMismatch { actual, .. }) if actual == expected
+        ));
+
+        assert_eq!(decode_base58check("111"), Err(Base58Error::TooShort(3)));
+        assert_eq!(
+            decode_base58check(&encode_check(&[])),
+            Err(Base58Error::TooShort(4))
+        );
+        assert_eq!(
+            decode_base58check("1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6K01"),
+            Err(Base58Error::InvalidCharacter {
+                character: '0',
+                position: 32
+            })
+        );
+    }
+
+    // Chapter 4 exercise 4.
+    #[test]
+    fn book_exercises() {
+        for (raw, encoded) in [
+            (
+                "7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d",
+                "9MA8fRQrT4u8Zj8ZRd6MAiiyaxb2Y1CMpvVkHQu5hVM6",
+            ),
+            (
+                "eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c",
+                "4fE3H2E6XMp4SsxtwinF7w9a34ooUrwWe4WsW1458Pd",
+            ),
+            (
+                "c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6",
+                "EQJsjkd6JaGwxrjEhfeqPenqHwrBmPQZjJGNSCHBkcF7",
+            ),
+        ] {
+            assert_eq!(encode_base58(&hex::decode(raw).unwrap()), encoded);
+            assert_eq!(decode_base58(encoded), Ok(hex::decode(raw).unwrap()));
+        }
+    }
+
+    #[test]
+    fn reports_invalid_characters() {
+        for (s, character, position) in [("0OIl", '0', 0), ("11O", 'O', 2), ("2l", 'l', 1)] {
+            assert_eq!(
+                decode_base58(s),
+                Err(Base58Error::InvalidCharacter {
+                    character,
+                    position
+                })
+            );
+        }
+        // Positions are byte offsets, so they can slice the input.
+        assert_eq!(
+            decode_base58("z€z"),
+            Err(Base58Error::InvalidCharacter {
+                character: '€',
+                position: 1
+            })
+        );
+        assert_eq!(
+            decode_base58("zz\u{1}"),
+            Err(Base58Error::InvalidCharacter {
+                character: '\u{1}',
+                position: 2
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn decodes_arbitrary_input(s in "\\PC*") {
+            if let Ok(bytes) = decode_base58(&s) {
+                prop_assert_eq!(encode_base58(&bytes), s);
+            }
+        }
+
+        #[test]
+        fn encodes_arbitrary_bytes(bytes: Vec<u8>) {
+            prop_assert_eq!(decode_base58(&encode_base58(&bytes)), Ok(bytes));
+        }
+    }
+}
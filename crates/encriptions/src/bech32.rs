@@ -0,0 +1,649 @@
+use std::fmt;
+
+const CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+// BIP173's limit, within which the checksum is guaranteed to catch up to four errors.
+pub const MAX_LEN: usize = 90;
+const CHECKSUM_LEN: usize = 6;
+
+// BIP173 bech32, or BIP350 bech32m whose checksum constant fixes bech32's weakness to inserted
+// or deleted 'q's before a final 'p'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    // Witness version 0 uses bech32; version 1 (taproot) and up use bech32m.
+    pub fn for_witness_version(version: u8) -> Self {
+        if version == 0 {
+            Variant::Bech32
+        } else {
+            Variant::Bech32m
+        }
+    }
+
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+
+    fn from_residue(residue: u32) -> Option<Self> {
+        [Variant::Bech32, Variant::Bech32m]
+            .iter()
+            .copied()
+            .find(|v| v.constant() == residue)
+    }
+}
+
+fn polymod(values: &[u8]) -> u32 {
+    values.iter().fold(1, |chk, &v| {
+        let top = chk >> 25;
+        let chk = (chk & 0x1ffffff) << 5 ^ v as u32;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, g)| chk ^ g)
+    })
+}
+
+// The high bits of each character, a zero, then the low bits.
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let bytes = hrp.bytes();
+    bytes
+        .clone()
+        .map(|b| b >> 5)
+        .chain(std::iter::once(0))
+        .chain(bytes.map(|b| b & 31))
+        .collect()
+}
+
+// Regroups `from`-bit values into `to`-bit values. Encoding pads the last group with zeros;
+// decoding (pad = false) rejects more than `from` leftover bits or leftover bits that are set.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Bech32Error> {
+    let max = (1u32 << to) - 1;
+    let mut out = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &value in data {
+        if (value as u32) >> from != 0 {
+            return Err(Bech32Error::InvalidData(value));
+        }
+        acc = (acc << from | value as u32) & ((1 << (from + to - 1)) - 1);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push((acc >> bits & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push((acc << (to - bits) & max) as u8);
+        }
+    } else if bits >= from || acc << (to - bits) & max != 0 {
+        return Err(Bech32Error::InvalidPadding);
+    }
+    Ok(out)
+}
+
+// 1 to 83 printable US-ASCII characters.
+fn check_hrp(hrp: &str) -> Result<(), Bech32Error> {
+    if hrp.is_empty() || hrp.len() > 83 || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return Err(Bech32Error::InvalidHrp);
+    }
+    Ok(())
+}
+
+// 5-bit data values under the given checksum; the output is lowercase.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, Bech32Error> {
+    encode_with_limit(hrp, data, variant, MAX_LEN)
+}
+
+// `encode` for any hrp and length, e.g. lightning invoices, which allow well over 90 characters.
+// The checksum's error-detection guarantees weaken past MAX_LEN.
+pub fn encode_with_limit(
+    hrp: &str,
+    data: &[u8],
+    variant: Variant,
+    max_len: usize,
+) -> Result<String, Bech32Error> {
+    check_hrp(hrp)?;
+    if let Some(&value) = data.iter().find(|&&d| d > 31) {
+        return Err(Bech32Error::InvalidData(value));
+    }
+    let len = hrp.len() + 1 + data.len() + CHECKSUM_LEN;
+    if len > max_len {
+        return Err(Bech32Error::TooLong { len, max: max_len });
+    }
+
+    let hrp = hrp.to_ascii_lowercase();
+    let mut values = hrp_expand(&hrp);
+    values.extend(data);
+    values.extend([0; CHECKSUM_LEN]);
+    let checksum = polymod(&values) ^ variant.constant();
+
+    let mut out = format!("{}1", hrp);
+    out.extend(data.iter().map(|&d| CHARSET[d as usize] as char));
+    out.extend(
+        (0..CHECKSUM_LEN).map(|i| CHARSET[(checksum >> (5 * (5 - i)) & 31) as usize] as char),
+    );
+    Ok(out)
+}
+
+// Accepts all-lowercase or all-uppercase strings and returns the lowercase hrp, the 5-bit data
+// with the checksum removed, and which checksum it carried.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>, Variant), Bech32Error> {
+    decode_with_limit(s, MAX_LEN)
+}
+
+pub fn decode_with_limit(
+    s: &str,
+    max_len: usize,
+) -> Result<(String, Vec<u8>, Variant), Bech32Error> {
+    if s.len() > max_len {
+        return Err(Bech32Error::TooLong {
+            len: s.len(),
+            max: max_len,
+        });
+    }
+    if let Some((position, character)) = s
+        .char_indices()
+        .find(|&(_, c)| !(33..=126).contains(&(c as u32)))
+    {
+        return Err(Bech32Error::InvalidCharacter {
+            character,
+            position,
+        });
+    }
+    if s.bytes().any(|b| b.is_ascii_lowercase()) && s.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(Bech32Error::MixedCase);
+    }
+
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or(Bech32Error::MissingSeparator)?;
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    check_hrp(hrp)?;
+    if data.len() < CHECKSUM_LEN {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+    let mut data = data
+        .bytes()
+        .enumerate()
+        .map(|(i, c)| {
+            CHARSET.iter().position(|&d| d == c).map(|d| d as u8).ok_or(
+                Bech32Error::InvalidCharacter {
+                    character: c as char,
+                    position: separator + 1 + i,
+                },
+            )
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend(&data);
+    let variant = Variant::from_residue(polymod(&values)).ok_or(Bech32Error::InvalidChecksum)?;
+    data.truncate(data.len() - CHECKSUM_LEN);
+    Ok((hrp.to_string(), data, variant))
+}
+
+// Witness version 0 programs are 20 (P2WPKH) or 32 (P2WSH) bytes; later versions 2 to 40.
+fn check_program(version: u8, program: &[u8]) -> Result<(), Bech32Error> {
+    if version > 16 {
+        return Err(Bech32Error::InvalidWitnessVersion(version));
+    }
+    let valid = match version {
+        0 => program.len() == 20 || program.len() == 32,
+        _ => (2..=40).contains(&program.len()),
+    };
+    if !valid {
+        return Err(Bech32Error::InvalidProgramLength(program.len()));
+    }
+    Ok(())
+}
+
+pub fn encode_segwit_address(
+    hrp: &str,
+    version: u8,
+    program: &[u8],
+) -> Result<String, Bech32Error> {
+    check_program(version, program)?;
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true)?);
+    encode(hrp, &data, Variant::for_witness_version(version))
+}
+
+// The witness version and program of an address for the given hrp ("bc", "tb").
+pub fn decode_segwit_address(hrp: &str, s: &str) -> Result<(u8, Vec<u8>), Bech32Error> {
+    let (found, data, variant) = decode(s)?;
+    if found != hrp {
+        return Err(Bech32Error::WrongHrp);
+    }
+    let (&version, program) = data
+        .split_first()
+        .ok_or(Bech32Error::InvalidProgramLength(0))?;
+    if version > 16 {
+        return Err(Bech32Error::InvalidWitnessVersion(version));
+    }
+    // A valid checksum of the wrong variant is as bad as a corrupted one.
+    if variant != Variant::for_witness_version(version) {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+    let program = convert_bits(program, 5, 8, false)?;
+    check_program(version, &program)?;
+    Ok((version, program))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bech32Error {
+    TooLong { len: usize, max: usize },
+    InvalidCharacter { character: char, position: usize },
+    MixedCase,
+    MissingSeparator,
+    InvalidHrp,
+    InvalidChecksum,
+    InvalidData(u8),
+    InvalidPadding,
+    WrongHrp,
+    InvalidWitnessVersion(u8),
+    InvalidProgramLength(usize),
+}
+
+impl fmt::Display for Bech32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bech32Error::TooLong { len, max } => {
+                write!(f, "{} characters is over the {} limit", len, max)
+            }
+            Bech32Error::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid bech32 character {:?} at position {}",
+                character, position
+            ),
+            Bech32Error::MixedCase => write!(f, "mixes upper and lower case"),
+            Bech32Error::MissingSeparator => write!(f, "missing the '1' separator"),
+            Bech32Error::InvalidHrp => write!(
+                f,
+                "human-readable part is empty, too long or not printable ASCII"
+            ),
+            Bech32Error::InvalidChecksum => write!(f, "invalid checksum"),
+            Bech32Error::InvalidData(value) => {
+                write!(f, "value {} does not fit the group size", value)
+            }
+            Bech32Error::InvalidPadding => write!(f, "invalid padding in the data"),
+            Bech32Error::WrongHrp => write!(f, "address is for a different network"),
+            Bech32Error::InvalidWitnessVersion(v) => write!(f, "invalid witness version {}", v),
+            Bech32Error::InvalidProgramLength(len) => {
+                write!(f, "invalid witness program length {}", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Bech32Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    // From the BIP173 test vectors.
+    #[test]
+    fn bip173_checksums() {
+        for s in [
+            "A12UEL5L",
+            "a12uel5l",
+            "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            "?1ezyfcl",
+        ] {
+            let (hrp, data, variant) = decode(s).unwrap();
+            assert_eq!(variant, Variant::Bech32);
+            assert_eq!(encode(&hrp, &data, variant).unwrap(), s.to_ascii_lowercase());
+        }
+
+        let invalid = |c: char, position| {
+            Err(Bech32Error::InvalidCharacter {
+                character: c,
+                position,
+            })
+        };
+        assert_eq!(decode("\u{20}1nwldj5"), invalid(' ', 0));
+        assert_eq!(decode("\u{7f}1axkwrx"), invalid('\u{7f}', 0));
+        assert_eq!(decode("\u{80}1eym55h"), invalid('\u{80}', 0));
+        assert_eq!(
+            decode("an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx"),
+            Err(Bech32Error::TooLong { len: 91, max: 90 })
+        );
+        assert_eq!(decode("pzry9x0s0muk"), Err(Bech32Error::MissingSeparator));
+        assert_eq!(decode("1pzry9x0s0muk"), Err(Bech32Error::InvalidHrp));
+        assert_eq!(decode("x1b4n0q5v"), invalid('b', 2));
+        assert_eq!(decode("li1dgmt3"), Err(Bech32Error::InvalidChecksum));
+        assert_eq!(decode("de1lg7wt\u{ff}"), invalid('\u{ff}', 8));
+        // The checksum was computed over the uppercase hrp.
+        assert_eq!(decode("A1G7SGD8"), Err(Bech32Error::InvalidChecksum));
+        assert_eq!(decode("10a06t8"), Err(Bech32Error::InvalidHrp));
+        assert_eq!(decode("1qzzfhee"), Err(Bech32Error::InvalidHrp));
+        assert_eq!(decode("a12UEL5L"), Err(Bech32Error::MixedCase));
+
+        assert_eq!(
+            encode("bc", &[32], Variant::Bech32),
+            Err(Bech32Error::InvalidData(32))
+        );
+        assert_eq!(
+            encode("", &[], Variant::Bech32),
+            Err(Bech32Error::InvalidHrp)
+        );
+        assert_eq!(
+            encode("bc", &[0; 82], Variant::Bech32),
+            Err(Bech32Error::TooLong { len: 91, max: 90 })
+        );
+    }
+
+    // From the BIP350 test vectors.
+    #[test]
+    fn bip350_checksums() {
+        for s in [
+            "A1LQFN3A",
+            "a1lqfn3a",
+            "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            "11llllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllludsr8",
+            "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+            "?1v759aa",
+        ] {
+            let (hrp, data, variant) = decode(s).unwrap();
+            assert_eq!(variant, Variant::Bech32m);
+            assert_eq!(encode(&hrp, &data, variant).unwrap(), s.to_ascii_lowercase());
+        }
+
+        for (s, error) in [
+            (
+                "\u{20}1xj0phk",
+                Bech32Error::InvalidCharacter {
+                    character: ' ',
+                    position: 0,
+                },
+            ),
+            ("qyrz8wqd2c9m", Bech32Error::MissingSeparator),
+            ("1qyrz8wqd2c9m", Bech32Error::InvalidHrp),
+            (
+                "y1b0jsk6g",
+                Bech32Error::InvalidCharacter {
+                    character: 'b',
+                    position: 2,
+                },
+            ),
+            (
+                "lt1igcx5c0",
+                Bech32Error::InvalidCharacter {
+                    character: 'i',
+                    position: 3,
+                },
+            ),
+            ("in1muywd", Bech32Error::InvalidChecksum),
+            (
+                "mm1crxm3i",
+                Bech32Error::InvalidCharacter {
+                    character: 'i',
+                    position: 8,
+                },
+            ),
+            (
+                "au1s5cgom",
+                Bech32Error::InvalidCharacter {
+                    character: 'o',
+                    position: 7,
+                },
+            ),
+            ("M1VUXWEZ", Bech32Error::InvalidChecksum),
+            ("16plkw9", Bech32Error::InvalidHrp),
+            ("1p2gdwpf", Bech32Error::InvalidHrp),
+        ] {
+            assert_eq!(decode(s), Err(error), "{}", s);
+        }
+    }
+
+    #[test]
+    fn segwit_addresses() {
+        for (address, hrp, script) in [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "bc",
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                "tb",
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+                "tb",
+                "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+        ] {
+            let script = hex::decode(script).unwrap();
+            let (version, program) = decode_segwit_address(hrp, address).unwrap();
+            assert_eq!((version, &program[..]), (0, &script[2..]));
+            assert_eq!(
+                encode_segwit_address(hrp, version, &program).unwrap(),
+                address.to_ascii_lowercase()
+            );
+        }
+
+        assert_eq!(
+            decode_segwit_address("bc", "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty"),
+            Err(Bech32Error::WrongHrp)
+        );
+        assert_eq!(
+            decode_segwit_address("bc", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+            Err(Bech32Error::InvalidChecksum)
+        );
+        assert_eq!(
+            decode_segwit_address("bc", "BC13W508D6QEJXTDG4Y5R3ZARVARY0C5XW7KN40WF2"),
+            Err(Bech32Error::InvalidWitnessVersion(17))
+        );
+        assert_eq!(
+            decode_segwit_address("bc", "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P"),
+            Err(Bech32Error::InvalidProgramLength(16))
+        );
+        assert_eq!(
+            decode_segwit_address(
+                "tb",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7"
+            ),
+            Err(Bech32Error::MixedCase)
+        );
+        assert_eq!(
+            decode_segwit_address(
+                "tb",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3pjxtptv"
+            ),
+            Err(Bech32Error::InvalidPadding)
+        );
+        assert_eq!(
+            decode_segwit_address("bc", "bc1gmk9yu"),
+            Err(Bech32Error::InvalidProgramLength(0))
+        );
+
+        assert_eq!(
+            encode_segwit_address("bc", 0, &[0; 21]),
+            Err(Bech32Error::InvalidProgramLength(21))
+        );
+        assert_eq!(
+            encode_segwit_address("bc", 17, &[0; 20]),
+            Err(Bech32Error::InvalidWitnessVersion(17))
+        );
+    }
+
+    // BIP350 addresses for witness versions 1 to 16.
+    #[test]
+    fn bech32m_segwit_addresses() {
+        for (address, hrp, script) in [
+            (
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                "bc",
+                "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            ("BC1SW50QGDZ25J", "bc", "6002751e"),
+            (
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+                "bc",
+                "5210751e76e8199196d454941c45d1b3a323",
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                "tb",
+                "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "bc",
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ] {
+            let script = hex::decode(script).unwrap();
+            // OP_1..OP_16 are 0x51..0x60.
+            let expected_version = script[0] - 0x50;
+            let (version, program) = decode_segwit_address(hrp, address).unwrap();
+            assert_eq!((version, &program[..]), (expected_version, &script[2..]));
+            assert_eq!(
+                encode_segwit_address(hrp, version, &program).unwrap(),
+                address.to_ascii_lowercase()
+            );
+        }
+
+        for (hrp, address, error) in [
+            (
+                "bc",
+                "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+                Bech32Error::WrongHrp,
+            ),
+            // Bech32 checksums on version 1+ programs, and bech32m on version 0.
+            (
+                "bc",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "tb",
+                "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "tb",
+                "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+                Bech32Error::InvalidCharacter {
+                    character: 'o',
+                    position: 59,
+                },
+            ),
+            (
+                "bc",
+                "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+                Bech32Error::InvalidWitnessVersion(17),
+            ),
+            ("bc", "bc1pw5dgrnzv", Bech32Error::InvalidProgramLength(1)),
+            (
+                "bc",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+                Bech32Error::InvalidProgramLength(41),
+            ),
+            (
+                "tb",
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+                Bech32Error::MixedCase,
+            ),
+            (
+                "bc",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+                Bech32Error::InvalidPadding,
+            ),
+            (
+                "tb",
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+                Bech32Error::InvalidPadding,
+            ),
+        ] {
+            assert_eq!(
+                decode_segwit_address(hrp, address),
+                Err(error),
+                "{}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn convert_bits_rejects_bad_input() {
+        assert_eq!(convert_bits(&[0xff], 8, 5, true), Ok(vec![31, 28]));
+        assert_eq!(convert_bits(&[31, 28], 5, 8, false), Ok(vec![0xff]));
+        assert_eq!(
+            convert_bits(&[31, 29], 5, 8, false),
+            Err(Bech32Error::InvalidPadding)
+        );
+        assert_eq!(
+            convert_bits(&[31, 28, 0], 5, 8, false),
+            Err(Bech32Error::InvalidPadding)
+        );
+        assert_eq!(
+            convert_bits(&[32], 5, 8, false),
+            Err(Bech32Error::InvalidData(32))
+        );
+    }
+
+    // The first BOLT11 example invoice: a 243-character bech32 string under the "lnbc" hrp.
+    #[test]
+    fn long_strings_with_any_hrp() {
+        let invoice = "lnbc1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdpl2pkx2ctnv5sxxmmwwd5kgetjypeh2ursdae8g6twvus8g6rfwvs8qun0dfjkxaq8rkx3yf5tcsyz3d73gafnh3cax9rn449d9p5uxz9ezhhypd0elx87sjle52x86fux2ypatgddc6k63n7erqz25le42c4u4ecky03ylcqca784w";
+        assert_eq!(
+            decode(invoice),
+            Err(Bech32Error::TooLong { len: 243, max: 90 })
+        );
+        let (hrp, data, variant) = decode_with_limit(invoice, 1023).unwrap();
+        assert_eq!(hrp, "lnbc");
+        assert_eq!(variant, Variant::Bech32);
+        assert_eq!(data.len(), 243 - 5 - CHECKSUM_LEN);
+        assert_eq!(
+            encode_with_limit(&hrp, &data, variant, 1023).unwrap(),
+            invoice
+        );
+        assert_eq!(
+            encode_with_limit(&hrp, &data, variant, 242),
+            Err(Bech32Error::TooLong { len: 243, max: 242 })
+        );
+
+        // Arbitrary bytes under a custom hrp.
+        let data = convert_bits(b"hello bech32", 8, 5, true).unwrap();
+        let encoded = encode("custom", &data, Variant::Bech32m).unwrap();
+        let (hrp, decoded, variant) = decode(&encoded).unwrap();
+        assert_eq!((hrp.as_str(), variant), ("custom", Variant::Bech32m));
+        assert_eq!(
+            convert_bits(&decoded, 5, 8, false).unwrap(),
+            b"hello bech32"
+        );
+    }
+}
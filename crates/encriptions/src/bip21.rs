@@ -0,0 +1,352 @@
+use crate::address::{Address, AddressError};
+use crate::amount::{Amount, AmountError};
+use std::fmt;
+use std::str::FromStr;
+
+const SCHEME: &str = "bitcoin:";
+
+// A BIP21 "bitcoin:" URI. Parameters other than amount, label and message are kept in order so
+// a parsed URI displays with nothing lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentUri {
+    address: Address,
+    amount: Option<Amount>,
+    label: Option<String>,
+    message: Option<String>,
+    extra_params: Vec<(String, String)>,
+}
+
+impl PaymentUri {
+    pub fn new(address: Address) -> Self {
+        PaymentUri {
+            address,
+            amount: None,
+            label: None,
+            message: None,
+            extra_params: Vec::new(),
+        }
+    }
+
+    pub fn with_amount(mut self, amount: Amount) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    // For parameters this type has no field for. amount, label and message have their own
+    // setters, and a "req-" key would make the URI one that FromStr rejects, so those are errors.
+    pub fn with_param(mut self, key: &str, value: &str) -> Result<Self, Bip21Error> {
+        if matches!(key, "amount" | "label" | "message") || key.starts_with("req-") {
+            return Err(Bip21Error::ReservedParameter(key.to_string()));
+        }
+        self.extra_params.push((key.to_string(), value.to_string()));
+        Ok(self)
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn amount(&self) -> Option<Amount> {
+        self.amount
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn extra_params(&self) -> &[(String, String)] {
+        &self.extra_params
+    }
+}
+
+impl fmt::Display for PaymentUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", SCHEME, self.address)?;
+        let mut params = Vec::new();
+        if let Some(amount) = self.amount {
+            params.push(("amount".to_string(), amount.to_string()));
+        }
+        if let Some(label) = &self.label {
+            params.push(("label".to_string(), percent_encode(label)));
+        }
+        if let Some(message) = &self.message {
+            params.push(("message".to_string(), percent_encode(message)));
+        }
+        for (key, value) in &self.extra_params {
+            params.push((percent_encode(key), percent_encode(value)));
+        }
+        for (i, (key, value)) in params.iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{}{}={}", separator, key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = Bip21Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The scheme is case-insensitive so that uppercase QR codes parse.
+        // `get` rather than indexing, since byte 8 of arbitrary input may not be a char boundary.
+        let scheme = s.get(..SCHEME.len());
+        if !scheme.is_some_and(|scheme| scheme.eq_ignore_ascii_case(SCHEME)) {
+            return Err(Bip21Error::MissingScheme);
+        }
+        let rest = &s[SCHEME.len()..];
+        let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let mut uri = PaymentUri::new(address.parse().map_err(Bip21Error::Address)?);
+
+        for param in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let key = percent_decode(key)?;
+            let value = percent_decode(value)?;
+            let duplicate = match key.as_str() {
+                "amount" => uri
+                    .amount
+                    .replace(value.parse().map_err(Bip21Error::Amount)?)
+                    .is_some(),
+                "label" => uri.label.replace(value).is_some(),
+                "message" => uri.message.replace(value).is_some(),
+                _ if key.starts_with("req-") => {
+                    return Err(Bip21Error::UnknownRequiredParameter(key))
+                }
+                _ => {
+                    uri.extra_params.push((key, value));
+                    continue;
+                }
+            };
+            if duplicate {
+                return Err(Bip21Error::DuplicateParameter(key));
+            }
+        }
+        Ok(uri)
+    }
+}
+
+// Everything but RFC 3986's unreserved characters is escaped, UTF-8 byte by byte.
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+// '+' is left alone: BIP21 follows RFC 3986, not HTML form encoding.
+fn percent_decode(s: &str) -> Result<String, Bip21Error> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let digits = bytes
+                .get(i + 1..i + 3)
+                .filter(|d| d.iter().all(u8::is_ascii_hexdigit))
+                .ok_or(Bip21Error::InvalidPercentEncoding)?;
+            let digits = std::str::from_utf8(digits).expect("hex digits are ASCII");
+            out.push(u8::from_str_radix(digits, 16).expect("checked hex digits"));
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).map_err(|_| Bip21Error::InvalidPercentEncoding)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bip21Error {
+    MissingScheme,
+    Address(AddressError),
+    Amount(AmountError),
+    InvalidPercentEncoding,
+    DuplicateParameter(String),
+    UnknownRequiredParameter(String),
+    ReservedParameter(String),
+}
+
+impl fmt::Display for Bip21Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bip21Error::MissingScheme => write!(f, "URI does not start with \"bitcoin:\""),
+            Bip21Error::Address(e) => write!(f, "invalid address in URI: {}", e),
+            Bip21Error::Amount(e) => write!(f, "invalid amount in URI: {}", e),
+            Bip21Error::InvalidPercentEncoding => write!(f, "invalid percent-encoding in URI"),
+            Bip21Error::DuplicateParameter(key) => {
+                write!(f, "parameter {:?} appears more than once", key)
+            }
+            Bip21Error::UnknownRequiredParameter(key) => {
+                write!(f, "unsupported required parameter {:?}", key)
+            }
+            Bip21Error::ReservedParameter(key) => {
+                write!(f, "parameter {:?} cannot be set as an extra parameter", key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Bip21Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1";
+
+    // The BIP21 examples, with the book's address in place of the spec's.
+    #[test]
+    fn bip21_examples() {
+        let address: Address = ADDRESS.parse().unwrap();
+        let base = PaymentUri::new(address);
+        for (s, uri) in [
+            (format!("bitcoin:{}", ADDRESS), base.clone()),
+            (
+                format!("bitcoin:{}?label=Luke-Jr", ADDRESS),
+                base.clone().with_label("Luke-Jr"),
+            ),
+            (
+                format!("bitcoin:{}?amount=20.3&label=Luke-Jr", ADDRESS),
+                base.clone()
+                    .with_amount("20.3".parse().unwrap())
+                    .with_label("Luke-Jr"),
+            ),
+            (
+                format!(
+                    "bitcoin:{}?amount=50&label=Luke-Jr&message=Donation%20for%20project%20xyz",
+                    ADDRESS
+                ),
+                base.clone()
+                    .with_amount(Amount::from_sat(5_000_000_000).unwrap())
+                    .with_label("Luke-Jr")
+                    .with_message("Donation for project xyz"),
+            ),
+            (
+                format!(
+                    "bitcoin:{}?somethingyoudontunderstand=50&somethingelseyoudontget=999",
+                    ADDRESS
+                ),
+                base.clone()
+                    .with_param("somethingyoudontunderstand", "50")
+                    .and_then(|uri| uri.with_param("somethingelseyoudontget", "999"))
+                    .unwrap(),
+            ),
+        ] {
+            assert_eq!(s.parse(), Ok(uri.clone()));
+            assert_eq!(uri.to_string(), s);
+        }
+
+        assert_eq!(
+            format!(
+                "bitcoin:{}?req-somethingyoudontunderstand=50&req-somethingelseyoudontget=999",
+                ADDRESS
+            )
+            .parse::<PaymentUri>(),
+            Err(Bip21Error::UnknownRequiredParameter(
+                "req-somethingyoudontunderstand".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn percent_encoding() {
+        let uri = PaymentUri::new(ADDRESS.parse().unwrap())
+            .with_label("Café & Co+")
+            .with_param("note", "50%=half")
+            .unwrap();
+        let s = uri.to_string();
+        assert_eq!(
+            s,
+            format!(
+                "bitcoin:{}?label=Caf%C3%A9%20%26%20Co%2B&note=50%25%3Dhalf",
+                ADDRESS
+            )
+        );
+        assert_eq!(s.parse(), Ok(uri));
+
+        // '+' is a literal plus, not a space.
+        let uri: PaymentUri = format!("bitcoin:{}?message=a+b%2fc", ADDRESS)
+            .parse()
+            .unwrap();
+        assert_eq!(uri.message(), Some("a+b/c"));
+    }
+
+    #[test]
+    fn uppercase_qr_uri() {
+        let uri: PaymentUri = "BITCOIN:BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4?amount=0.01"
+            .parse()
+            .unwrap();
+        assert!(matches!(uri.address(), Address::P2wpkh { .. }));
+        assert_eq!(uri.amount(), Some(Amount::from_sat(1_000_000).unwrap()));
+        assert_eq!(uri.label(), None);
+        assert_eq!(
+            uri.to_string(),
+            "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4?amount=0.01"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert_eq!(
+            ADDRESS.parse::<PaymentUri>(),
+            Err(Bip21Error::MissingScheme)
+        );
+        // Byte 8 falls inside the 'é', so the scheme can't be sliced off by index.
+        assert_eq!(
+            "bitcoiné…".parse::<PaymentUri>(),
+            Err(Bip21Error::MissingScheme)
+        );
+        assert!(matches!(
+            "bitcoin:1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF2".parse::<PaymentUri>(),
+            Err(Bip21Error::Address(_))
+        ));
+        for (query, err) in [
+            (
+                "amount=1.000000001",
+                Bip21Error::Amount(AmountError::TooPrecise),
+            ),
+            (
+                "amount=1&amount=2",
+                Bip21Error::DuplicateParameter("amount".to_string()),
+            ),
+            ("label=%4", Bip21Error::InvalidPercentEncoding),
+            ("label=%+1", Bip21Error::InvalidPercentEncoding),
+            ("label=%zz", Bip21Error::InvalidPercentEncoding),
+            ("label=%ff", Bip21Error::InvalidPercentEncoding),
+        ] {
+            assert_eq!(
+                format!("bitcoin:{}?{}", ADDRESS, query).parse::<PaymentUri>(),
+                Err(err)
+            );
+        }
+    }
+
+    #[test]
+    fn with_param_rejects_reserved_keys() {
+        let uri = PaymentUri::new(ADDRESS.parse().unwrap());
+        for key in ["amount", "label", "message", "req-foo"] {
+            assert_eq!(
+                uri.clone().with_param(key, "1"),
+                Err(Bip21Error::ReservedParameter(key.to_string()))
+            );
+        }
+    }
+}
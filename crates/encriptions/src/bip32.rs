@@ -0,0 +1,978 @@
+use crate::base58::{decode_check, encode_check, Base58Error};
+use crate::field::{Choice, ConstantTimeEq, ScalarFieldElement};
+use crate::hashes::hmac_sha512;
+use crate::keys::{AddressKind, PrivateKey, PublicKey, SecError};
+use crate::network::Network;
+use crate::point::S256Point;
+use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+
+pub const HARDENED_OFFSET: u32 = 1 << 31;
+const SERIALIZED_LEN: usize = 78;
+
+// The raw 32-bit index, with bit 31 set for hardened children. The field is private so that a
+// hardened index of 2^31 or more, which has no encoding, can't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    pub fn normal(index: u32) -> Result<Self, PathError> {
+        if index >= HARDENED_OFFSET {
+            return Err(PathError::IndexOutOfRange(index));
+        }
+        Ok(ChildNumber(index))
+    }
+
+    pub fn hardened(index: u32) -> Result<Self, PathError> {
+        if index >= HARDENED_OFFSET {
+            return Err(PathError::IndexOutOfRange(index));
+        }
+        Ok(ChildNumber(index + HARDENED_OFFSET))
+    }
+
+    // Every u32 is a valid child: those from 2^31 up are hardened.
+    pub fn from_index(index: u32) -> Self {
+        ChildNumber(index)
+    }
+
+    pub fn to_index(self) -> u32 {
+        self.0
+    }
+
+    // The index without the hardened bit, as written in a path.
+    pub fn index(self) -> u32 {
+        self.0 & !HARDENED_OFFSET
+    }
+
+    pub fn is_hardened(self) -> bool {
+        self.0 >= HARDENED_OFFSET
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+// Accepts "44'", "44h" and "44H" for hardened children.
+impl FromStr for ChildNumber {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PathError::InvalidChildNumber(s.to_string());
+        let (digits, hardened) = match s.strip_suffix(&['\'', 'h', 'H'][..]) {
+            Some(digits) => (digits, true),
+            None => (s, false),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let index: u32 = digits.parse().map_err(|_| invalid())?;
+        if hardened {
+            ChildNumber::hardened(index)
+        } else {
+            ChildNumber::normal(index)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    pub fn master() -> Self {
+        Self(vec![])
+    }
+
+    pub fn child(&self, child: ChildNumber) -> Self {
+        let mut path = self.0.clone();
+        path.push(child);
+        Self(path)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ChildNumber> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // purpose'/coin_type'/account'/change/address_index, the layout BIP44 fixed and BIP49, BIP84
+    // and BIP86 reuse with their own purpose.
+    fn standard(
+        purpose: u32,
+        coin_type: u32,
+        account: u32,
+        change: u32,
+        index: u32,
+    ) -> Result<Self, PathError> {
+        Ok(Self(vec![
+            ChildNumber::hardened(purpose)?,
+            ChildNumber::hardened(coin_type)?,
+            ChildNumber::hardened(account)?,
+            ChildNumber::normal(change)?,
+            ChildNumber::normal(index)?,
+        ]))
+    }
+
+    // Legacy P2PKH.
+    pub fn bip44(coin_type: u32, account: u32, change: u32, index: u32) -> Result<Self, PathError> {
+        Self::standard(44, coin_type, account, change, index)
+    }
+
+    // P2WPKH nested in P2SH.
+    pub fn bip49(coin_type: u32, account: u32, change: u32, index: u32) -> Result<Self, PathError> {
+        Self::standard(49, coin_type, account, change, index)
+    }
+
+    // Native P2WPKH.
+    pub fn bip84(coin_type: u32, account: u32, change: u32, index: u32) -> Result<Self, PathError> {
+        Self::standard(84, coin_type, account, change, index)
+    }
+
+    // Single-key P2TR.
+    pub fn bip86(coin_type: u32, account: u32, change: u32, index: u32) -> Result<Self, PathError> {
+        Self::standard(86, coin_type, account, change, index)
+    }
+}
+
+impl<'a> IntoIterator for &'a DerivationPath {
+    type Item = &'a ChildNumber;
+    type IntoIter = std::slice::Iter<'a, ChildNumber>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(children: Vec<ChildNumber>) -> Self {
+        Self(children)
+    }
+}
+
+impl AsRef<[ChildNumber]> for DerivationPath {
+    fn as_ref(&self) -> &[ChildNumber] {
+        &self.0
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        self.0.iter().try_for_each(|child| write!(f, "/{}", child))
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(PathError::MissingMaster);
+        }
+        parts.map(str::parse).collect::<Result<_, _>>().map(Self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    MissingMaster,
+    InvalidChildNumber(String),
+    IndexOutOfRange(u32),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::MissingMaster => write!(f, "derivation path must start with \"m\""),
+            PathError::InvalidChildNumber(s) => write!(f, "invalid child number {:?}", s),
+            PathError::IndexOutOfRange(i) => {
+                write!(f, "child index {} does not fit below 2^31", i)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+// HMAC-SHA512 split into the left half (the key material) and the right half (the chain code).
+fn hmac_halves(key: &[u8], parts: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let out = hmac_sha512(key, parts);
+    (out[..32].try_into().unwrap(), out[32..].try_into().unwrap())
+}
+
+// The first four bytes of hash160 of the compressed key.
+fn fingerprint(key: &PublicKey) -> [u8; 4] {
+    key.hash160().as_byte_array()[..4].try_into().unwrap()
+}
+
+// version || depth || parent fingerprint || child number || chain code || key, 78 bytes before
+// the Base58Check checksum.
+fn serialize(
+    version: u32,
+    depth: u8,
+    parent_fingerprint: &[u8; 4],
+    child_number: ChildNumber,
+    chain_code: &[u8; 32],
+    key: &[u8],
+) -> String {
+    let mut data = Vec::with_capacity(SERIALIZED_LEN);
+    data.extend(version.to_be_bytes());
+    data.push(depth);
+    data.extend(parent_fingerprint);
+    data.extend(child_number.to_index().to_be_bytes());
+    data.extend(chain_code);
+    data.extend(key);
+    encode_check(&data)
+}
+
+struct Decoded {
+    version: u32,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    key: [u8; 33],
+}
+
+fn deserialize(s: &str) -> Result<Decoded, Bip32Error> {
+    let data = decode_check(s).map_err(Bip32Error::InvalidBase58Check)?;
+    if data.len() != SERIALIZED_LEN {
+        return Err(Bip32Error::InvalidLength(data.len()));
+    }
+    let decoded = Decoded {
+        version: u32::from_be_bytes(data[..4].try_into().unwrap()),
+        depth: data[4],
+        parent_fingerprint: data[5..9].try_into().unwrap(),
+        child_number: ChildNumber::from_index(u32::from_be_bytes(data[9..13].try_into().unwrap())),
+        chain_code: data[13..45].try_into().unwrap(),
+        key: data[45..].try_into().unwrap(),
+    };
+    // A master key has no parent, so both fields must be zero (BIP32 test vector 5).
+    if decoded.depth == 0
+        && (decoded.parent_fingerprint != [0; 4] || decoded.child_number.to_index() != 0)
+    {
+        return Err(Bip32Error::InvalidMasterKey);
+    }
+    Ok(decoded)
+}
+
+// SLIP-132 (public, private) versions. Beyond the network, the prefix tells wallets which script
+// type the account's addresses use: xpub/tpub for P2PKH, ypub/upub for P2SH-P2WPKH and zpub/vpub
+// for P2WPKH. BIP86 taproot accounts reuse xpub/tpub.
+const VERSIONS: [(Network, AddressKind, u32, u32); 6] = [
+    (Network::Mainnet, AddressKind::P2pkh, 0x0488b21e, 0x0488ade4),
+    (
+        Network::Mainnet,
+        AddressKind::P2shP2wpkh,
+        0x049d7cb2,
+        0x049d7878,
+    ),
+    (
+        Network::Mainnet,
+        AddressKind::P2wpkh,
+        0x04b24746,
+        0x04b2430c,
+    ),
+    (Network::Testnet, AddressKind::P2pkh, 0x043587cf, 0x04358394),
+    (
+        Network::Testnet,
+        AddressKind::P2shP2wpkh,
+        0x044a5262,
+        0x044a4e28,
+    ),
+    (
+        Network::Testnet,
+        AddressKind::P2wpkh,
+        0x045f1cf6,
+        0x045f18bc,
+    ),
+];
+
+fn versions(network: Network, script_type: AddressKind) -> Option<(u32, u32)> {
+    VERSIONS
+        .iter()
+        .find(|(n, kind, _, _)| *n == network.base58_network() && *kind == script_type)
+        .map(|&(_, _, public, private)| (public, private))
+}
+
+fn lookup_version(version: u32, private: bool) -> Result<(Network, AddressKind), Bip32Error> {
+    VERSIONS
+        .iter()
+        .find(|&&(_, _, public, secret)| version == if private { secret } else { public })
+        .map(|&(network, kind, _, _)| (network, kind))
+        .ok_or(Bip32Error::UnknownVersion(version))
+}
+
+#[derive(Clone)]
+pub struct ExtendedPrivKey {
+    network: Network,
+    script_type: AddressKind,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    private_key: PrivateKey,
+}
+
+impl ExtendedPrivKey {
+    // HMAC-SHA512 keyed with "Bitcoin seed": the left half is the master secret and the right
+    // half the chain code.
+    pub fn new_master(network: Network, seed: &[u8]) -> Result<Self, Bip32Error> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Bip32Error::InvalidSeedLength(seed.len()));
+        }
+        let (il, chain_code) = hmac_halves(b"Bitcoin seed", &[seed]);
+        let private_key = ScalarFieldElement::from_bytes_be(&il)
+            .and_then(PrivateKey::new)
+            .ok_or(Bip32Error::InvalidKey)?;
+        Ok(Self {
+            network,
+            script_type: AddressKind::P2pkh,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: ChildNumber::from_index(0),
+            chain_code,
+            private_key,
+        })
+    }
+
+    // CKDpriv: hardened children hash the secret, normal ones the public key, so normal children
+    // can also be derived from the extended public key alone. The child secret is IL + k.
+    pub fn ckd_priv(&self, child: ChildNumber) -> Result<Self, Bip32Error> {
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::DepthExceeded)?;
+        let index = child.to_index().to_be_bytes();
+        let (il, chain_code) = if child.is_hardened() {
+            let secret = self.private_key.secret().to_bytes_be();
+            hmac_halves(&self.chain_code, &[&[0], &secret, &index])
+        } else {
+            let sec = self.private_key.public_key().to_sec(true);
+            hmac_halves(&self.chain_code, &[&sec, &index])
+        };
+        let il = ScalarFieldElement::from_bytes_be(&il).ok_or(Bip32Error::InvalidKey)?;
+        let private_key =
+            PrivateKey::new(&il + self.private_key.secret()).ok_or(Bip32Error::InvalidKey)?;
+        Ok(Self {
+            network: self.network,
+            script_type: self.script_type,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+            chain_code,
+            private_key,
+        })
+    }
+
+    pub fn derive_priv(&self, path: &DerivationPath) -> Result<Self, Bip32Error> {
+        path.iter()
+            .try_fold(self.clone(), |key, &child| key.ckd_priv(child))
+    }
+
+    // Selects the SLIP-132 prefix used when serializing; keys derived from this one keep it.
+    pub fn with_script_type(self, script_type: AddressKind) -> Result<Self, Bip32Error> {
+        versions(self.network, script_type)
+            .ok_or(Bip32Error::UnsupportedScriptType(script_type))?;
+        Ok(Self {
+            script_type,
+            ..self
+        })
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    pub fn script_type(&self) -> AddressKind {
+        self.script_type
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(self.private_key.public_key())
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+}
+
+// The chain code and key are compared in constant time; the public metadata is folded in.
+impl PartialEq for ExtendedPrivKey {
+    fn eq(&self, other: &Self) -> bool {
+        let public = self.network == other.network
+            && self.script_type == other.script_type
+            && self.depth == other.depth
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.child_number == other.child_number;
+        let secret =
+            self.chain_code.ct_eq(&other.chain_code) & self.private_key.ct_eq(&other.private_key);
+        (Choice::from(public as u8) & secret).into()
+    }
+}
+
+// The chain code and key stay out of logs.
+impl fmt::Debug for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ExtendedPrivKey({}, depth {}, …)",
+            self.child_number, self.depth
+        )
+    }
+}
+
+impl fmt::Display for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut key = vec![0];
+        key.extend(self.private_key.secret().to_bytes_be());
+        let (_, version) = versions(self.network, self.script_type).unwrap();
+        let encoded = serialize(
+            version,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        );
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for ExtendedPrivKey {
+    type Err = Bip32Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decoded = deserialize(s)?;
+        let (network, script_type) = lookup_version(decoded.version, true)?;
+        if decoded.key[0] != 0 {
+            return Err(Bip32Error::InvalidPrivateKeyPrefix(decoded.key[0]));
+        }
+        let private_key = ScalarFieldElement::from_bytes_be(decoded.key[1..].try_into().unwrap())
+            .and_then(PrivateKey::new)
+            .ok_or(Bip32Error::InvalidKey)?;
+        Ok(Self {
+            network,
+            script_type,
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+            chain_code: decoded.chain_code,
+            private_key,
+        })
+    }
+}
+
+// Derives the same normal children as the ExtendedPrivKey it came from, with no private
+// material: enough for a watch-only wallet to generate receiving addresses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtendedPubKey {
+    network: Network,
+    script_type: AddressKind,
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+impl ExtendedPubKey {
+    pub fn from_priv(key: &ExtendedPrivKey) -> Self {
+        Self {
+            network: key.network,
+            script_type: key.script_type,
+            depth: key.depth,
+            parent_fingerprint: key.parent_fingerprint,
+            child_number: key.child_number,
+            chain_code: key.chain_code,
+            public_key: *key.private_key.public_key(),
+        }
+    }
+
+    // CKDpub: the child key is IL·G + K. Hardened children need the parent secret.
+    pub fn ckd_pub(&self, child: ChildNumber) -> Result<Self, Bip32Error> {
+        if child.is_hardened() {
+            return Err(Bip32Error::HardenedFromPublic(child));
+        }
+        let depth = self.depth.checked_add(1).ok_or(Bip32Error::DepthExceeded)?;
+        let sec = self.public_key.to_sec(true);
+        let (il, chain_code) =
+            hmac_halves(&self.chain_code, &[&sec, &child.to_index().to_be_bytes()]);
+        let il = ScalarFieldElement::from_bytes_be(&il).ok_or(Bip32Error::InvalidKey)?;
+        let point = &il * S256Point::generator() + *self.public_key.point();
+        let public_key = PublicKey::from_point(point).ok_or(Bip32Error::InvalidKey)?;
+        Ok(Self {
+            network: self.network,
+            script_type: self.script_type,
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+            chain_code,
+            public_key,
+        })
+    }
+
+    // The path is relative to this key, so "m/0/5" is the sixth receiving address of an account
+    // xpub.
+    pub fn derive_pub(&self, path: &DerivationPath) -> Result<Self, Bip32Error> {
+        path.iter()
+            .try_fold(*self, |key, &child| key.ckd_pub(child))
+    }
+
+    pub fn with_script_type(self, script_type: AddressKind) -> Result<Self, Bip32Error> {
+        versions(self.network, script_type)
+            .ok_or(Bip32Error::UnsupportedScriptType(script_type))?;
+        Ok(Self {
+            script_type,
+            ..self
+        })
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    pub fn script_type(&self) -> AddressKind {
+        self.script_type
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> ChildNumber {
+        self.child_number
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key)
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn address(&self, kind: AddressKind) -> String {
+        self.public_key.to_address(self.network, kind)
+    }
+}
+
+impl fmt::Display for ExtendedPubKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (version, _) = versions(self.network, self.script_type).unwrap();
+        let encoded = serialize(
+            version,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.public_key.to_sec(true),
+        );
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for ExtendedPubKey {
+    type Err = Bip32Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decoded = deserialize(s)?;
+        let (network, script_type) = lookup_version(decoded.version, false)?;
+        let public_key = PublicKey::from_sec(&decoded.key).map_err(Bip32Error::InvalidPublicKey)?;
+        Ok(Self {
+            network,
+            script_type,
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+            chain_code: decoded.chain_code,
+            public_key,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bip32Error {
+    HardenedFromPublic(ChildNumber),
+    InvalidSeedLength(usize),
+    // IL ≥ n or a zero/infinite child, which BIP32 says to skip by moving to the next index.
+    InvalidKey,
+    DepthExceeded,
+    InvalidBase58Check(Base58Error),
+    InvalidLength(usize),
+    // Depth 0 with a nonzero parent fingerprint or child number.
+    InvalidMasterKey,
+    UnknownVersion(u32),
+    InvalidPrivateKeyPrefix(u8),
+    InvalidPublicKey(SecError),
+    UnsupportedScriptType(AddressKind),
+}
+
+impl fmt::Display for Bip32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bip32Error::HardenedFromPublic(child) => write!(
+                f,
+                "hardened child {} cannot be derived from a public key",
+                child
+            ),
+            Bip32Error::InvalidSeedLength(len) => {
+                write!(f, "seed must be 16 to 64 bytes, got {}", len)
+            }
+            Bip32Error::InvalidKey => write!(f, "derived key is invalid; use the next index"),
+            Bip32Error::DepthExceeded => write!(f, "derivation deeper than 255 levels"),
+            Bip32Error::InvalidBase58Check(e) => write!(f, "invalid extended key: {}", e),
+            Bip32Error::InvalidLength(len) => {
+                write!(f, "extended key must be 78 bytes, got {}", len)
+            }
+            Bip32Error::InvalidMasterKey => write!(
+                f,
+                "depth-0 key must have a zero parent fingerprint and child number"
+            ),
+            Bip32Error::UnknownVersion(version) => {
+                write!(f, "unknown extended key version 0x{:08x}", version)
+            }
+            Bip32Error::InvalidPrivateKeyPrefix(prefix) => {
+                write!(
+                    f,
+                    "private key must be prefixed with 0x00, got 0x{:02x}",
+                    prefix
+                )
+            }
+            Bip32Error::InvalidPublicKey(e) => write!(f, "invalid public key: {}", e),
+            Bip32Error::UnsupportedScriptType(kind) => {
+                write!(f, "no SLIP-132 version for {:?} keys", kind)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Bip32Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39::Mnemonic;
+    use crate::hex;
+
+    #[test]
+    fn parse_and_display() {
+        let path: DerivationPath = "m/84'/0'/0'/0/5".parse().unwrap();
+        assert_eq!(path, DerivationPath::bip84(0, 0, 0, 5).unwrap());
+        assert_eq!(path.to_string(), "m/84'/0'/0'/0/5");
+        assert_eq!(
+            path.iter().map(|c| c.to_index()).collect::<Vec<_>>(),
+            vec![HARDENED_OFFSET + 84, HARDENED_OFFSET, HARDENED_OFFSET, 0, 5]
+        );
+
+        assert_eq!(
+            "m/44h/1H/2'".parse::<DerivationPath>().unwrap().to_string(),
+            "m/44'/1'/2'"
+        );
+        assert_eq!("m".parse::<DerivationPath>(), Ok(DerivationPath::master()));
+        assert_eq!(
+            DerivationPath::master()
+                .child(ChildNumber::hardened(86).unwrap())
+                .child(ChildNumber::normal(1).unwrap())
+                .to_string(),
+            "m/86'/1"
+        );
+        assert_eq!(
+            ChildNumber::from_index(HARDENED_OFFSET + 3),
+            ChildNumber::hardened(3).unwrap()
+        );
+        assert_eq!(ChildNumber::from_index(HARDENED_OFFSET + 3).index(), 3);
+        assert_eq!(
+            ChildNumber::hardened(HARDENED_OFFSET),
+            Err(PathError::IndexOutOfRange(HARDENED_OFFSET))
+        );
+        assert_eq!(
+            ChildNumber::normal(HARDENED_OFFSET),
+            Err(PathError::IndexOutOfRange(HARDENED_OFFSET))
+        );
+    }
+
+    #[test]
+    fn standard_paths() {
+        assert_eq!(
+            DerivationPath::bip44(0, 0, 0, 0).unwrap().to_string(),
+            "m/44'/0'/0'/0/0"
+        );
+        assert_eq!(
+            DerivationPath::bip49(1, 2, 1, 7).unwrap().to_string(),
+            "m/49'/1'/2'/1/7"
+        );
+        assert_eq!(
+            DerivationPath::bip86(0, 0, 1, 0).unwrap().to_string(),
+            "m/86'/0'/0'/1/0"
+        );
+        assert_eq!(
+            DerivationPath::bip84(0, HARDENED_OFFSET, 0, 0),
+            Err(PathError::IndexOutOfRange(HARDENED_OFFSET))
+        );
+    }
+
+    fn vector1_master() -> ExtendedPrivKey {
+        ExtendedPrivKey::new_master(
+            Network::Mainnet,
+            &hex::decode("000102030405060708090a0b0c0d0e0f").unwrap(),
+        )
+        .unwrap()
+    }
+
+    // BIP32 test vector 1.
+    #[test]
+    fn bip32_vector_1() {
+        let master = vector1_master();
+        let vectors = [
+            (
+                "m",
+                "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ),
+            (
+                "m/0'",
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            ),
+            (
+                "m/0'/1",
+                "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            ),
+            (
+                "m/0'/1/2'",
+                "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+                "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+            ),
+        ];
+        for (path, xpub, xprv) in vectors {
+            let key = master.derive_priv(&path.parse().unwrap()).unwrap();
+            assert_eq!(key.to_string(), xprv, "{}", path);
+            assert_eq!(
+                ExtendedPubKey::from_priv(&key).to_string(),
+                xpub,
+                "{}",
+                path
+            );
+            assert_eq!(xprv.parse::<ExtendedPrivKey>(), Ok(key.clone()));
+            assert_eq!(xpub.parse(), Ok(ExtendedPubKey::from_priv(&key)));
+        }
+
+        let testnet = ExtendedPrivKey::new_master(
+            Network::Testnet,
+            &hex::decode("000102030405060708090a0b0c0d0e0f").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            ExtendedPubKey::from_priv(&testnet).to_string(),
+            "tpubD6NzVbkrYhZ4XgiXtGrdW5XDAPFCL9h7we1vwNCpn8tGbBcgfVYjXyhWo4E1xkh56hjod1RhGjxbaTLV3X4FyWuejifB9jusQ46QzG87VKp"
+        );
+    }
+
+    #[test]
+    fn watch_only_derivation() {
+        let account: ExtendedPubKey = "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5"
+            .parse()
+            .unwrap();
+        let child = account
+            .derive_pub(&"m/2/1000000000".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            child.to_string(),
+            "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"
+        );
+        assert_eq!(child.depth(), 5);
+
+        // Public and private derivation agree, down to the addresses.
+        let private = vector1_master()
+            .derive_priv(&"m/0'/1/2'/2/1000000000".parse().unwrap())
+            .unwrap();
+        assert_eq!(child.public_key(), private.private_key().public_key());
+        assert_eq!(
+            child.address(AddressKind::P2wpkh),
+            private
+                .private_key()
+                .public_key()
+                .to_address(Network::Mainnet, AddressKind::P2wpkh)
+        );
+
+        assert_eq!(
+            account.ckd_pub(ChildNumber::hardened(0).unwrap()),
+            Err(Bip32Error::HardenedFromPublic(
+                ChildNumber::hardened(0).unwrap()
+            ))
+        );
+        assert_eq!(
+            account.derive_pub(&"m/0/1'/2".parse().unwrap()),
+            Err(Bip32Error::HardenedFromPublic(
+                ChildNumber::hardened(1).unwrap()
+            ))
+        );
+    }
+
+    // BIP84 and BIP49 account keys for "abandon ... about" with no passphrase.
+    #[test]
+    fn slip132_versions() {
+        let seed = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                    abandon abandon about"
+            .parse::<Mnemonic>()
+            .unwrap()
+            .to_seed("");
+        let master = ExtendedPrivKey::new_master(Network::Mainnet, &seed).unwrap();
+
+        let bip84 = master
+            .clone()
+            .with_script_type(AddressKind::P2wpkh)
+            .unwrap()
+            .derive_priv(&"m/84'/0'/0'".parse().unwrap())
+            .unwrap();
+        let zprv = "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE";
+        let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+        assert_eq!(bip84.to_string(), zprv);
+        assert_eq!(ExtendedPubKey::from_priv(&bip84).to_string(), zpub);
+        assert_eq!(zprv.parse(), Ok(bip84));
+
+        // A pasted zpub carries its script type through derivation.
+        let account: ExtendedPubKey = zpub.parse().unwrap();
+        assert_eq!(account.script_type(), AddressKind::P2wpkh);
+        let first = account.derive_pub(&"m/0/0".parse().unwrap()).unwrap();
+        assert_eq!(
+            first.address(first.script_type()),
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"
+        );
+
+        let bip49 = master
+            .clone()
+            .with_script_type(AddressKind::P2shP2wpkh)
+            .unwrap()
+            .derive_priv(&"m/49'/0'/0'".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            ExtendedPubKey::from_priv(&bip49).to_string(),
+            "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP"
+        );
+
+        let testnet = ExtendedPrivKey::new_master(Network::Testnet, &seed)
+            .unwrap()
+            .with_script_type(AddressKind::P2wpkh)
+            .unwrap()
+            .derive_priv(&"m/84'/1'/0'".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            ExtendedPubKey::from_priv(&testnet).to_string(),
+            "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc"
+        );
+
+        assert_eq!(
+            master.with_script_type(AddressKind::P2tr),
+            Err(Bip32Error::UnsupportedScriptType(AddressKind::P2tr))
+        );
+    }
+
+    // BIP32 test vector 5: master keys with a parent fingerprint or child number.
+    #[test]
+    fn rejects_master_keys_with_parent_fields() {
+        for xprv in [
+            "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+            "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+        ] {
+            assert_eq!(
+                xprv.parse::<ExtendedPrivKey>(),
+                Err(Bip32Error::InvalidMasterKey)
+            );
+        }
+        for xpub in [
+            "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+            "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+        ] {
+            assert_eq!(
+                xpub.parse::<ExtendedPubKey>(),
+                Err(Bip32Error::InvalidMasterKey)
+            );
+        }
+    }
+
+    #[test]
+    fn debug_hides_secrets() {
+        let master = ExtendedPrivKey::new_master(Network::Mainnet, &[7; 16]).unwrap();
+        let debug = format!("{:?}", master);
+        assert_eq!(debug, "ExtendedPrivKey(0, depth 0, …)");
+        assert!(!debug.contains(&hex::encode(&master.chain_code)));
+    }
+
+    #[test]
+    fn rejects_malformed_extended_keys() {
+        let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        assert_eq!(
+            xprv.parse::<ExtendedPubKey>(),
+            Err(Bip32Error::UnknownVersion(0x0488ade4))
+        );
+        assert_eq!(
+            xpub.parse::<ExtendedPrivKey>(),
+            Err(Bip32Error::UnknownVersion(0x0488b21e))
+        );
+        assert!(matches!(
+            xpub[..xpub.len() - 1].parse::<ExtendedPubKey>(),
+            Err(Bip32Error::InvalidBase58Check(
+                Base58Error::ChecksumMismatch { .. }
+            ))
+        ));
+        assert_eq!(
+            encode_check(&[0; 4]).parse::<ExtendedPubKey>(),
+            Err(Bip32Error::InvalidLength(4))
+        );
+        assert_eq!(
+            ExtendedPrivKey::new_master(Network::Mainnet, &[0; 15]),
+            Err(Bip32Error::InvalidSeedLength(15))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert_eq!(
+            "84'/0'".parse::<DerivationPath>(),
+            Err(PathError::MissingMaster)
+        );
+        assert_eq!("".parse::<DerivationPath>(), Err(PathError::MissingMaster));
+        for bad in ["m/", "m//1", "m/1''", "m/-1", "m/+1", "m/x", "m/4294967296"] {
+            assert!(
+                matches!(
+                    bad.parse::<DerivationPath>(),
+                    Err(PathError::InvalidChildNumber(_))
+                ),
+                "{}",
+                bad
+            );
+        }
+        assert_eq!(
+            "m/2147483648".parse::<DerivationPath>(),
+            Err(PathError::IndexOutOfRange(HARDENED_OFFSET))
+        );
+    }
+}
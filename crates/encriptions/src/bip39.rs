@@ -0,0 +1,281 @@
+use crate::field::ConstantTimeEq;
+use crate::hashes::{pbkdf2_sha512, PBKDF2_ITERATIONS};
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+const ENGLISH: &str = include_str!("wordlists/english.txt");
+
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        let words: Vec<&str> = ENGLISH.lines().collect();
+        assert_eq!(words.len(), 2048, "BIP39 wordlist must have 2048 words");
+        words
+    })
+}
+
+#[derive(Clone)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    // 128 to 256 bits of entropy in steps of 32, giving 12 to 24 words.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        if !(16..=32).contains(&entropy.len()) || !entropy.len().is_multiple_of(4) {
+            return Err(MnemonicError::InvalidEntropyLength(entropy.len()));
+        }
+        Ok(Self {
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    pub fn generate<R: RngCore + CryptoRng>(
+        rng: &mut R,
+        word_count: usize,
+    ) -> Result<Self, MnemonicError> {
+        if !(12..=24).contains(&word_count) || !word_count.is_multiple_of(3) {
+            return Err(MnemonicError::InvalidWordCount(word_count));
+        }
+        let mut entropy = vec![0u8; word_count / 3 * 4];
+        rng.fill_bytes(&mut entropy);
+        Self::from_entropy(&entropy)
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    // The entropy followed by the first ENT/32 bits of its SHA-256, read in 11-bit groups.
+    pub fn words(&self) -> Vec<&'static str> {
+        let checksum = Sha256::digest(&self.entropy)[0];
+        let bit = |i: usize| {
+            let byte = self.entropy.get(i / 8).copied().unwrap_or(checksum);
+            (byte >> (7 - i % 8)) & 1
+        };
+        let word_count = self.entropy.len() * 8 * 33 / 32 / 11;
+        (0..word_count)
+            .map(|w| {
+                let index = (0..11).fold(0usize, |acc, j| acc << 1 | bit(w * 11 + j) as usize);
+                wordlist()[index]
+            })
+            .collect()
+    }
+
+    // PBKDF2-HMAC-SHA512 of the sentence with salt "mnemonic" + passphrase. The passphrase is
+    // used as given; callers with non-ASCII passphrases must NFKD-normalize them first.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let salt = format!("mnemonic{}", passphrase);
+        let mut seed = [0u8; 64];
+        pbkdf2_sha512(
+            self.to_string().as_bytes(),
+            salt.as_bytes(),
+            PBKDF2_ITERATIONS,
+            &mut seed,
+        );
+        seed
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.words().join(" "))
+    }
+}
+
+// The length is public, so a mismatch returns early; the bytes are compared in constant time.
+impl PartialEq for Mnemonic {
+    fn eq(&self, other: &Self) -> bool {
+        self.entropy.ct_eq(&other.entropy).into()
+    }
+}
+
+impl Eq for Mnemonic {}
+
+// Keep the phrase out of logs.
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mnemonic({} words)", self.words().len())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Mnemonic {
+    fn zeroize(&mut self) {
+        self.entropy.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Mnemonic {}
+
+impl FromStr for Mnemonic {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        if !(12..=24).contains(&words.len()) || !words.len().is_multiple_of(3) {
+            return Err(MnemonicError::InvalidWordCount(words.len()));
+        }
+
+        let mut bits = Vec::with_capacity(words.len() * 11);
+        for (position, word) in words.iter().enumerate() {
+            let index = wordlist()
+                .binary_search(word)
+                .map_err(|_| MnemonicError::UnknownWord {
+                    position,
+                    word: word.to_string(),
+                })?;
+            bits.extend((0..11).rev().map(|j| (index >> j) & 1 == 1));
+        }
+
+        let entropy_bits = bits.len() * 32 / 33;
+        let entropy: Vec<u8> = bits[..entropy_bits]
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, &b| acc << 1 | b as u8))
+            .collect();
+        let mnemonic = Self::from_entropy(&entropy)?;
+        if mnemonic.words() != words {
+            return Err(MnemonicError::InvalidChecksum);
+        }
+        Ok(mnemonic)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MnemonicError {
+    InvalidEntropyLength(usize),
+    InvalidWordCount(usize),
+    UnknownWord { position: usize, word: String },
+    InvalidChecksum,
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MnemonicError::InvalidEntropyLength(len) => write!(
+                f,
+                "entropy must be 16 to 32 bytes in steps of 4, got {}",
+                len
+            ),
+            MnemonicError::InvalidWordCount(count) => {
+                write!(
+                    f,
+                    "mnemonic must have 12, 15, 18, 21 or 24 words, got {}",
+                    count
+                )
+            }
+            MnemonicError::UnknownWord { position, word } => {
+                write!(
+                    f,
+                    "word {} ({:?}) is not in the BIP39 wordlist",
+                    position, word
+                )
+            }
+            MnemonicError::InvalidChecksum => write!(f, "mnemonic checksum does not match"),
+        }
+    }
+}
+
+impl std::error::Error for MnemonicError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // From the Trezor reference vectors, all with passphrase "TREZOR".
+    #[test]
+    fn trezor_vectors() {
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                 abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d182\
+                 64c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559\
+                 a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+                "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a\
+                 589620c6f15b11c61dee327651a14c34e18231052e48c069",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                 abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                 abandon abandon abandon art",
+                "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245caf\
+                 a9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+            ),
+        ];
+        for (entropy, phrase, seed) in vectors {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.to_string(), phrase);
+            assert_eq!(phrase.parse::<Mnemonic>(), Ok(mnemonic.clone()));
+            assert_eq!(hex::encode(&mnemonic.to_seed("TREZOR")), seed);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_phrases() {
+        assert_eq!(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon abandon"
+                .parse::<Mnemonic>(),
+            Err(MnemonicError::InvalidChecksum)
+        );
+        assert_eq!(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon bitcoins"
+                .parse::<Mnemonic>(),
+            Err(MnemonicError::UnknownWord {
+                position: 11,
+                word: "bitcoins".to_string()
+            })
+        );
+        assert_eq!(
+            "zoo zoo zoo".parse::<Mnemonic>(),
+            Err(MnemonicError::InvalidWordCount(3))
+        );
+        assert_eq!(
+            Mnemonic::from_entropy(&[0; 15]),
+            Err(MnemonicError::InvalidEntropyLength(15))
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let mut rng = StdRng::seed_from_u64(589);
+        let mnemonic = Mnemonic::generate(&mut rng, 24).unwrap();
+        assert_eq!(mnemonic.entropy().len(), 32);
+        assert_eq!(
+            mnemonic.to_string().parse::<Mnemonic>(),
+            Ok(mnemonic.clone())
+        );
+        assert_eq!(format!("{:?}", mnemonic), "Mnemonic(24 words)");
+        assert_eq!(
+            Mnemonic::generate(&mut rng, 13),
+            Err(MnemonicError::InvalidWordCount(13))
+        );
+    }
+}
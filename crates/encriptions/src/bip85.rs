@@ -0,0 +1,155 @@
+use crate::bip32::{Bip32Error, ChildNumber, DerivationPath, ExtendedPrivKey, PathError};
+use crate::bip39::Mnemonic;
+use crate::hashes::hmac_sha512;
+use std::fmt;
+
+const PURPOSE: u32 = 83696968;
+const APP_BIP39: u32 = 39;
+const APP_HEX: u32 = 128169;
+// Only the English wordlist is bundled.
+const LANGUAGE_ENGLISH: u32 = 0;
+
+fn hardened_path(indices: &[u32]) -> Result<DerivationPath, Bip85Error> {
+    std::iter::once(PURPOSE)
+        .chain(indices.iter().copied())
+        .map(|i| ChildNumber::hardened(i).map_err(Bip85Error::InvalidPath))
+        .collect::<Result<Vec<_>, _>>()
+        .map(DerivationPath::from)
+}
+
+// HMAC-SHA512 keyed with "bip-entropy-from-k" over the derived private key. Every step must be
+// hardened so the entropy reveals nothing about the root or its siblings.
+pub fn derive_entropy(
+    root: &ExtendedPrivKey,
+    path: &DerivationPath,
+) -> Result<[u8; 64], Bip85Error> {
+    if let Some(&child) = path.iter().find(|c| !c.is_hardened()) {
+        return Err(Bip85Error::NotHardened(child));
+    }
+    let key = root.derive_priv(path).map_err(Bip85Error::Derivation)?;
+    let secret = key.private_key().secret().to_bytes_be();
+    Ok(hmac_sha512(b"bip-entropy-from-k", &[&secret]))
+}
+
+// m/83696968'/39'/0'/{words}'/{index}', truncated to the entropy length for 12, 18 or 24 words.
+pub fn derive_mnemonic(
+    root: &ExtendedPrivKey,
+    words: usize,
+    index: u32,
+) -> Result<Mnemonic, Bip85Error> {
+    if ![12, 18, 24].contains(&words) {
+        return Err(Bip85Error::InvalidWordCount(words));
+    }
+    let path = hardened_path(&[APP_BIP39, LANGUAGE_ENGLISH, words as u32, index])?;
+    let entropy = derive_entropy(root, &path)?;
+    Ok(Mnemonic::from_entropy(&entropy[..words / 3 * 4]).expect("12, 18 or 24 words of entropy"))
+}
+
+// m/83696968'/128169'/{len}'/{index}', between 16 and 64 raw bytes.
+pub fn derive_bytes(root: &ExtendedPrivKey, len: usize, index: u32) -> Result<Vec<u8>, Bip85Error> {
+    if !(16..=64).contains(&len) {
+        return Err(Bip85Error::InvalidLength(len));
+    }
+    let path = hardened_path(&[APP_HEX, len as u32, index])?;
+    Ok(derive_entropy(root, &path)?[..len].to_vec())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bip85Error {
+    NotHardened(ChildNumber),
+    InvalidPath(PathError),
+    InvalidWordCount(usize),
+    InvalidLength(usize),
+    Derivation(Bip32Error),
+}
+
+impl fmt::Display for Bip85Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bip85Error::NotHardened(child) => {
+                write!(f, "child {} must be hardened to derive entropy", child)
+            }
+            Bip85Error::InvalidPath(e) => write!(f, "invalid path: {}", e),
+            Bip85Error::InvalidWordCount(n) => {
+                write!(f, "mnemonics have 12, 18 or 24 words, not {}", n)
+            }
+            Bip85Error::InvalidLength(n) => {
+                write!(f, "entropy must be 16 to 64 bytes, not {}", n)
+            }
+            Bip85Error::Derivation(e) => write!(f, "derivation failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Bip85Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    // From the BIP85 test vectors.
+    fn root() -> ExtendedPrivKey {
+        "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn bip85_vectors() {
+        let root = root();
+        assert_eq!(
+            hex::encode(&derive_entropy(&root, &"m/83696968'/0'/0'".parse().unwrap()).unwrap()),
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f0\
+             0b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+        );
+        assert_eq!(
+            hex::encode(&derive_entropy(&root, &"m/83696968'/0'/1'".parse().unwrap()).unwrap()),
+            "70c6e3e8ebee8dc4c0dbba66076819bb8c09672527c4277ca8729532ad711872\
+             218f826919f6b67218adde99018a6df9095ab2b58d803b5b93ec9802085a690e"
+        );
+
+        assert_eq!(
+            derive_mnemonic(&root, 12, 0).unwrap().to_string(),
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+        );
+        assert_eq!(
+            derive_mnemonic(&root, 18, 0).unwrap().to_string(),
+            "near account window bike charge season chef number sketch tomorrow excuse sniff \
+             circle vital hockey outdoor supply token"
+        );
+        assert_eq!(
+            derive_mnemonic(&root, 24, 0).unwrap().to_string(),
+            "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget \
+             divorce twin tonight reason outdoor destroy simple truth cigar social volcano"
+        );
+
+        assert_eq!(
+            hex::encode(&derive_bytes(&root, 64, 0).unwrap()),
+            "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f8785\
+             55d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+        );
+        assert_ne!(derive_bytes(&root, 16, 0), derive_bytes(&root, 16, 1));
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let root = root();
+        assert_eq!(
+            derive_entropy(&root, &"m/83696968'/0'/0".parse().unwrap()),
+            Err(Bip85Error::NotHardened(ChildNumber::normal(0).unwrap()))
+        );
+        assert_eq!(
+            derive_mnemonic(&root, 15, 0).unwrap_err(),
+            Bip85Error::InvalidWordCount(15)
+        );
+        assert_eq!(
+            derive_bytes(&root, 65, 0),
+            Err(Bip85Error::InvalidLength(65))
+        );
+        assert_eq!(
+            derive_bytes(&root, 16, 1 << 31),
+            Err(Bip85Error::InvalidPath(PathError::IndexOutOfRange(1 << 31)))
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::hashes::{hash256, BlockHash, Sha256d, Txid};
+use num::BigUint;
+use std::convert::TryInto;
+
+// Block headers are 80 bytes: version, previous block, merkle root, time, bits and nonce.
+pub const HEADER_LEN: usize = 80;
+
+pub fn block_hash(header: &[u8; HEADER_LEN]) -> BlockHash {
+    BlockHash::hash(header)
+}
+
+// bits packs the target as a one-byte exponent and a three-byte coefficient:
+// target = coefficient * 256^(exponent - 3). The coefficient's top bit is a sign, as in Bitcoin
+// Core's compact encoding; negative targets and ones past 256 bits are rejected with None.
+pub fn bits_to_target(bits: u32) -> Option<BigUint> {
+    let exponent = bits >> 24;
+    let coefficient = bits & 0x007f_ffff;
+    let target = if exponent >= 3 {
+        BigUint::from(coefficient) << (8 * (exponent - 3))
+    } else {
+        BigUint::from(coefficient >> (8 * (3 - exponent)))
+    };
+    if target != BigUint::from(0u32) && (bits & 0x0080_0000 != 0 || target.bits() > 256) {
+        return None;
+    }
+    Some(target)
+}
+
+// The header's hash, read as a little-endian number, must not exceed the target its bits encode.
+pub fn check_pow(header: &[u8; HEADER_LEN]) -> bool {
+    let bits = u32::from_le_bytes(header[72..76].try_into().unwrap());
+    meets_target(&hash256(header), bits)
+}
+
+// As in Bitcoin Core's CheckProofOfWork, a hash equal to the target is valid.
+pub fn meets_target(hash: &[u8; 32], bits: u32) -> bool {
+    match bits_to_target(bits) {
+        Some(target) => BigUint::from_bytes_le(hash) <= target,
+        None => false,
+    }
+}
+
+pub fn merkle_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left);
+    data[32..].copy_from_slice(right);
+    hash256(&data)
+}
+
+// Pairs hashes level by level, repeating the last one of an odd level. None for no txids.
+pub fn merkle_root(txids: &[Txid]) -> Option<Sha256d> {
+    let mut level: Vec<[u8; 32]> = txids.iter().map(|txid| txid.to_byte_array()).collect();
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(level[level.len() - 1]);
+        }
+        level = level
+            .chunks_exact(2)
+            .map(|pair| merkle_parent(&pair[0], &pair[1]))
+            .collect();
+    }
+    level.first().map(|&root| Sha256d::from_byte_array(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    fn header(s: &str) -> [u8; HEADER_LEN] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    // Block 100000.
+    const HEADER: &str = "0100000050120119172a610421a6c3011dd330d9df07b63616c2cc1f1cd00200000000006657a9252aacd5c0b2940996ecff952228c3067cc38d4885efb5a4ac4247e9f337221b4d4c86041b0f2b5710";
+    const TXIDS: [&str; 4] = [
+        "8c14f0db3df150123e6f3dbbf30f8b955a8249b62ac1d1ff16284aefa3d06d87",
+        "fff2525b8931402dd09222c50775608f75787bd2b87e56995a7bdd30f79702c4",
+        "6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4",
+        "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d",
+    ];
+
+    #[test]
+    fn proof_of_work() {
+        let header = header(HEADER);
+        assert_eq!(
+            block_hash(&header).to_string(),
+            "000000000003ba27aa200b1cecaad478d2b00432346c3f1f3986da1afd33e506"
+        );
+        assert!(check_pow(&header));
+
+        let mut tampered = header;
+        tampered[79] ^= 1;
+        assert!(!check_pow(&tampered));
+
+        // The genesis difficulty, 0xffff * 256^26.
+        assert_eq!(
+            bits_to_target(0x1d00ffff),
+            Some(BigUint::from(0xffffu32) << (8 * 26))
+        );
+        assert_eq!(
+            bits_to_target(0x1b04864c).unwrap().to_str_radix(16),
+            "4864c000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(bits_to_target(0x0200_8000), Some(BigUint::from(0x80u32)));
+        assert_eq!(bits_to_target(0x0112_3456), Some(BigUint::from(0x12u32)));
+    }
+
+    #[test]
+    fn hash_equal_to_target_is_valid() {
+        let target = bits_to_target(0x1d00ffff).unwrap();
+        let as_hash = |n: &BigUint| {
+            let mut hash = [0u8; 32];
+            let bytes = n.to_bytes_le();
+            hash[..bytes.len()].copy_from_slice(&bytes);
+            hash
+        };
+
+        assert!(meets_target(&as_hash(&target), 0x1d00ffff));
+        assert!(meets_target(&as_hash(&(&target - 1u32)), 0x1d00ffff));
+        assert!(!meets_target(&as_hash(&(&target + 1u32)), 0x1d00ffff));
+    }
+
+    #[test]
+    fn rejects_negative_and_overflowing_bits() {
+        // The sign bit only counts when the coefficient left after shifting is nonzero.
+        assert_eq!(bits_to_target(0x1d80ffff), None);
+        assert_eq!(bits_to_target(0x0492_3456), None);
+        assert_eq!(bits_to_target(0x0180_3456), Some(BigUint::from(0u32)));
+
+        // 0xff * 256^31 is the largest that fits in 256 bits.
+        assert_eq!(
+            bits_to_target(0x2200_00ff),
+            Some(BigUint::from(0xffu32) << (8 * 31))
+        );
+        assert_eq!(bits_to_target(0x2200_ffff), None);
+        assert_eq!(bits_to_target(0x2300_0001), None);
+
+        let mut negative = header(HEADER);
+        negative[72..76].copy_from_slice(&0x1d80ffffu32.to_le_bytes());
+        assert!(!check_pow(&negative));
+    }
+
+    #[test]
+    fn merkle_roots() {
+        let txids: Vec<Txid> = TXIDS.iter().map(|s| s.parse().unwrap()).collect();
+        // The root is stored in the header at bytes 36..68.
+        let root = merkle_root(&txids).unwrap();
+        assert_eq!(root.as_byte_array()[..], header(HEADER)[36..68]);
+        assert_eq!(
+            root.to_string(),
+            "f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766"
+        );
+
+        // An odd level pairs its last hash with itself.
+        assert_eq!(
+            merkle_root(&txids[..3]).unwrap().to_string(),
+            "fa435470825de273081dcc706b25514c936fa6dc80ab965ce6970d68ddd0b553"
+        );
+        assert_eq!(
+            merkle_root(&txids[..1]).unwrap().to_byte_array(),
+            txids[0].to_byte_array()
+        );
+        assert_eq!(merkle_root(&[]), None);
+    }
+}
@@ -1,56 +1,407 @@
-use crate::field::Field;
-use crate::point::Point;
-use num::traits::real::Real;
-use num::BigInt;
+use crate::fe256::Fe256;
+use crate::field::{
+    parse_biguint, rem_euclid, Curve25519Prime, Field, FiniteFieldElement, P256Prime, Prime,
+    Prime223, PrimeField, Secp256k1Order,
+};
+use crate::point::{GeneralPoint, Point};
+use crate::rational::RationalElement;
+use num::{BigInt, BigUint, ToPrimitive};
 use num_traits::Pow;
-use std::ops::{Add, AddAssign, Mul};
+use std::fmt;
+use std::marker::PhantomData;
 
-pub trait EllipticCurve<T> {
-    fn on<'a>(point: &impl Point<T>) -> bool
+pub trait EllipticCurve {
+    type CurveField: Field;
+
+    fn on(point: &impl Point<Self::CurveField>) -> bool {
+        Self::check(point).is_ok()
+    }
+
+    // Evaluates both sides of y² = x³ + ax + b, so a failure shows how far off the point is.
+    fn check(point: &impl Point<Self::CurveField>) -> Result<(), OffCurve<Self::CurveField>> {
+        let (x, y) = match (point.x(), point.y()) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Ok(()),
+        };
+
+        let lhs = y.clone() * y;
+        let rhs = x.clone() * x.clone() * x.clone() + Self::a() * x + Self::b();
+        if lhs == rhs {
+            Ok(())
+        } else {
+            Err(OffCurve { lhs, rhs })
+        }
+    }
+
+    // The chord-and-tangent law only forms a group on non-singular curves: 4a³ + 27b² ≠ 0.
+    // Checked when a point is constructed from outside; the group law's own results skip it.
+    fn is_valid() -> bool {
+        let (a, b) = (Self::a(), Self::b());
+        let cubed = a.clone() * a.clone() * a;
+        Self::CurveField::from(4) * cubed + Self::CurveField::from(27) * b.clone() * b
+            != Self::CurveField::from(0)
+    }
+
+    // Counts the points, infinity included, as 1 + Σ (1 + (x³ + ax + b | p)) over every x.
+    // The loop runs p times, so this is only for the small fields used in examples. In F_2 every
+    // element is its own square root, so each x has exactly one y; Euler's criterion doesn't
+    // apply there, as its exponent (p - 1) / 2 is zero.
+    fn count_points() -> u64
     where
-        T: Field,
+        Self::CurveField: PrimeField,
     {
-        if point.is_finite() {
-            T::eq(
-                &(T::from(point.y().unwrap().pow(BigInt::from(2)))),
-                &(T::from(
-                    T::from(
-                        T::from(point.x().unwrap().pow(BigInt::from(3)))
-                            + T::from(Self::a() * point.x().unwrap()),
-                    ) + Self::b(),
-                )),
-            )
-        } else {
-            true
+        let p = Self::CurveField::modulus()
+            .to_u64()
+            .filter(|p| *p <= i64::MAX as u64)
+            .expect("count_points needs a prime below 2^63");
+        let euler = BigInt::from((p - 1) / 2);
+        let (zero, one) = (Self::CurveField::from(0), Self::CurveField::from(1));
+
+        let mut count = 1;
+        for x in 0..p as i64 {
+            let x = Self::CurveField::from(x);
+            let rhs = x.clone() * x.clone() * x.clone() + Self::a() * x + Self::b();
+            if rhs == zero || p == 2 {
+                count += 1;
+            } else if rhs.pow(euler.clone()) == one {
+                count += 2;
+            }
+        }
+        count
+    }
+
+    fn a() -> Self::CurveField;
+    fn b() -> Self::CurveField;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffCurve<T> {
+    pub lhs: T,
+    pub rhs: T,
+}
+
+impl<T: fmt::Display> fmt::Display for OffCurve<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "point is not on the curve: y² = {} but x³ + ax + b = {}",
+            self.lhs, self.rhs
+        )
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> std::error::Error for OffCurve<T> {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveError {
+    Singular,
+    NotOnCurve,
+}
+
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveError::Singular => write!(f, "curve is singular: 4a³ + 27b² = 0"),
+            CurveError::NotOnCurve => write!(f, "point is not on the curve"),
         }
     }
+}
+
+impl std::error::Error for CurveError {}
 
-    fn a() -> T;
-    fn b() -> T;
+// Curves with a distinguished base point of prime order, which keys and signatures need.
+pub trait CurveGroup: EllipticCurve {
+    fn generator() -> GeneralPoint<Self::CurveField>;
+    fn order() -> BigUint;
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Secp256k1;
 
-impl<'a, T: Field + From<i64>> EllipticCurve<T> for Secp256k1 {
-    fn a() -> T {
-        T::from(0)
+impl EllipticCurve for Secp256k1 {
+    type CurveField = Fe256;
+
+    fn a() -> Fe256 {
+        Fe256::from(0)
+    }
+
+    fn b() -> Fe256 {
+        Fe256::from(7)
+    }
+}
+
+impl CurveGroup for Secp256k1 {
+    fn generator() -> GeneralPoint<Fe256> {
+        GeneralPoint::finite(
+            "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+                .parse()
+                .unwrap(),
+            "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+                .parse()
+                .unwrap(),
+        )
     }
 
-    fn b() -> T {
-        T::from(7)
+    fn order() -> BigUint {
+        Secp256k1Order::get_prime()
     }
 }
 
-#[derive(Debug, PartialEq)]
+// Parses a decimal or 0x-prefixed hex constant, optionally negative, for `def_curve!`.
+pub(crate) fn parse_constant(value: &str) -> BigInt {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let magnitude =
+        BigInt::from(parse_biguint(digits).unwrap_or_else(|e| panic!("curve constant: {}", e)));
+    if negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+pub(crate) fn curve_constant<P: Prime>(value: &str) -> FiniteFieldElement<P> {
+    FiniteFieldElement::new(rem_euclid(&parse_constant(value), &P::get_prime())).unwrap()
+}
+
+// Declares a curve y² = x³ + ax + b over FiniteFieldElement<prime> with base point (gx, gy)
+// of the given order. Constants are integers or decimal/hex strings, parsed once on first use,
+// and each curve gets a test checking that its generator is on the curve and has the stated
+// order.
+macro_rules! def_curve {
+    ($name:ident, $a:expr, $b:expr, $prime:ty, $gx:expr, $gy:expr, $n:expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $name;
+
+        impl $crate::curve::EllipticCurve for $name {
+            type CurveField = $crate::field::FiniteFieldElement<$prime>;
+
+            fn a() -> Self::CurveField {
+                static A: std::sync::OnceLock<$crate::field::FiniteFieldElement<$prime>> =
+                    std::sync::OnceLock::new();
+                A.get_or_init(|| $crate::curve::curve_constant(&$a.to_string()))
+                    .clone()
+            }
+
+            fn b() -> Self::CurveField {
+                static B: std::sync::OnceLock<$crate::field::FiniteFieldElement<$prime>> =
+                    std::sync::OnceLock::new();
+                B.get_or_init(|| $crate::curve::curve_constant(&$b.to_string()))
+                    .clone()
+            }
+        }
+
+        impl $crate::curve::CurveGroup for $name {
+            fn generator() -> $crate::point::GeneralPoint<Self::CurveField> {
+                static G: std::sync::OnceLock<
+                    $crate::point::GeneralPoint<$crate::field::FiniteFieldElement<$prime>>,
+                > = std::sync::OnceLock::new();
+                G.get_or_init(|| {
+                    $crate::point::GeneralPoint::finite(
+                        $crate::curve::curve_constant(&$gx.to_string()),
+                        $crate::curve::curve_constant(&$gy.to_string()),
+                    )
+                })
+                .clone()
+            }
+
+            fn order() -> num::BigUint {
+                static N: std::sync::OnceLock<num::BigUint> = std::sync::OnceLock::new();
+                N.get_or_init(|| {
+                    $crate::curve::parse_constant(&$n.to_string())
+                        .to_biguint()
+                        .unwrap()
+                })
+                .clone()
+            }
+        }
+
+        paste::paste! {
+            #[cfg(test)]
+            mod [<$name:snake _generator>] {
+                use $crate::curve::{CurveGroup, EllipticCurve};
+                use $crate::point::PointOnCurve;
+
+                #[test]
+                fn generator_on_curve_with_order() {
+                    assert!(<super::$name as EllipticCurve>::is_valid());
+                    if let Err(e) = super::$name::check(&super::$name::generator()) {
+                        panic!("{}: {}", stringify!($name), e);
+                    }
+
+                    let g = PointOnCurve::<_, super::$name>::generator();
+                    let n = num::BigInt::from(super::$name::order());
+                    assert_eq!(g.mul_wnaf(&n), PointOnCurve::infinity());
+                }
+            }
+        }
+    };
+}
+
+// The book's running example: y² = x³ + 7 over F223, where (15, 86) has order 7.
+def_curve!(ToyCurve223, 0, 7, Prime223, 15, 86, 7);
+
+// NIST P-256 (secp256r1): y² = x³ - 3x + b.
+def_curve!(
+    P256,
+    -3,
+    "0x5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+    P256Prime,
+    "0x6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+    "0x4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+    "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+);
+
+// Curve25519 (v² = u³ + 486662u² + u) in short Weierstrass form, via x = u + 486662/3.
+// The base point is the image of u = 9; the group has cofactor 8, and the order given is
+// that of the prime-order subgroup it generates.
+def_curve!(
+    Wei25519,
+    "0x2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa984914a144",
+    "0x7b425ed097b425ed097b425ed097b425ed097b425ed097b4260b5e9c7710c864",
+    Curve25519Prime,
+    "0x2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaad245a",
+    "0x20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9",
+    "0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed"
+);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TestEllipticCurve;
 
-impl<'a, T: Field + From<i64>> EllipticCurve<T> for TestEllipticCurve {
-    fn a() -> T {
-        T::from(5)
+impl EllipticCurve for TestEllipticCurve {
+    type CurveField = RationalElement;
+
+    fn a() -> RationalElement {
+        RationalElement::from(5)
     }
 
-    fn b() -> T {
-        T::from(7)
+    fn b() -> RationalElement {
+        RationalElement::from(7)
+    }
+}
+
+/// The equation of curve `C` taken over a field `F` other than its canonical one.
+///
+/// Points must name this wrapper explicitly, so mixing a curve with the wrong field
+/// is a deliberate choice rather than an accident:
+///
+/// ```compile_fail
+/// use encriptions::curve::Secp256k1;
+/// use encriptions::field::{FiniteFieldElement, Prime223};
+/// use encriptions::point::PointOnCurve;
+///
+/// let _: Option<PointOnCurve<FiniteFieldElement<Prime223>, Secp256k1>> = None;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverField<C, F>(PhantomData<fn() -> (C, F)>);
+
+impl<F: Field> EllipticCurve for OverField<Secp256k1, F> {
+    type CurveField = F;
+
+    fn a() -> F {
+        F::from(0)
+    }
+
+    fn b() -> F {
+        F::from(7)
+    }
+}
+
+// secp256k1 with Montgomery-form coordinates, for generic code that wants limb arithmetic
+// without the secp256k1-specific reduction of `Fe256`.
+#[cfg(feature = "montgomery")]
+pub type MontgomerySecp256k1 =
+    OverField<Secp256k1, crate::montgomery::MontgomeryFieldElement<crate::field::Secp256k1Prime>>;
+
+impl<F: Field> EllipticCurve for OverField<TestEllipticCurve, F> {
+    type CurveField = F;
+
+    fn a() -> F {
+        F::from(5)
+    }
+
+    fn b() -> F {
+        F::from(7)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Prime, Prime13, Prime19, Prime29};
+    use crate::point::PointOnCurve;
+    use crate::small_field::{SmallPrime, SmallPrimeField};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Prime2;
+
+    impl Prime for Prime2 {
+        fn get_prime() -> BigUint {
+            BigUint::from(2u64)
+        }
+    }
+
+    impl SmallPrime for Prime2 {
+        const MODULUS: u64 = 2;
+    }
+
+    // Counts by brute force over every (x, y).
+    fn count_by_enumeration<C: EllipticCurve>(p: i64) -> u64 {
+        let field = |v| C::CurveField::from(v);
+        1 + (0..p)
+            .flat_map(|x| (0..p).map(move |y| (x, y)))
+            .filter(|&(x, y)| C::on(&GeneralPoint::finite(field(x), field(y))))
+            .count() as u64
+    }
+
+    fn assert_hasse_bound(count: u64, p: u64) {
+        let trace = (p + 1) as i64 - count as i64;
+        assert!(
+            (trace * trace) as u64 <= 4 * p,
+            "{} points over F{} violates Hasse's bound",
+            count,
+            p
+        );
+    }
+
+    #[test]
+    fn count_points_small_curves() {
+        type Toy = ToyCurve223;
+        let count = Toy::count_points();
+        assert_eq!(count, count_by_enumeration::<Toy>(223));
+        assert_hasse_bound(count, 223);
+        // Generator orders divide the group order.
+        assert_eq!(count % Toy::order().to_u64().unwrap(), 0);
+        assert_eq!(count % 21, 0);
+
+        fn check<C: EllipticCurve>(p: u64)
+        where
+            C::CurveField: PrimeField,
+        {
+            let count = C::count_points();
+            assert_eq!(count, count_by_enumeration::<C>(p as i64));
+            assert_hasse_bound(count, p);
+        }
+        check::<OverField<Secp256k1, SmallPrimeField<Prime13>>>(13);
+        check::<OverField<Secp256k1, SmallPrimeField<Prime19>>>(19);
+        check::<OverField<Secp256k1, SmallPrimeField<Prime29>>>(29);
+        check::<OverField<TestEllipticCurve, SmallPrimeField<Prime29>>>(29);
+        check::<OverField<Secp256k1, SmallPrimeField<Prime223>>>(223);
+        check::<OverField<TestEllipticCurve, FiniteFieldElement<Prime223>>>(223);
+
+        // y² = x³ + 7 over F_2 is y² = x³ + 1: (0, 1), (1, 0) and infinity.
+        assert_eq!(
+            OverField::<Secp256k1, SmallPrimeField<Prime2>>::count_points(),
+            3
+        );
+        check::<OverField<Secp256k1, SmallPrimeField<Prime2>>>(2);
+        check::<OverField<TestEllipticCurve, FiniteFieldElement<Prime2>>>(2);
+
+        assert_eq!(
+            PointOnCurve::<_, Toy>::generator().mul_naive(&BigInt::from(count)),
+            PointOnCurve::infinity()
+        );
     }
 }
@@ -0,0 +1,270 @@
+use crate::curve::EllipticCurve;
+use crate::field::{Curve25519Prime, Field, FiniteFieldElement};
+use crate::point::{GeneralPoint, Point, PointOnCurve};
+use std::marker::PhantomData;
+
+// Montgomery form: B·v² = u³ + A·u² + u. The point at infinity is the identity.
+pub trait MontgomeryCurve {
+    type CurveField: Field;
+
+    fn a() -> Self::CurveField;
+    fn b() -> Self::CurveField;
+
+    fn on(point: &GeneralPoint<Self::CurveField>) -> bool {
+        match (point.x(), point.y()) {
+            (Some(u), Some(v)) => {
+                Self::b() * v.clone() * v
+                    == u.clone() * u.clone() * u.clone() + Self::a() * u.clone() * u.clone() + u
+            }
+            _ => true,
+        }
+    }
+}
+
+// Twisted Edwards form: a·x² + y² = 1 + d·x²·y². The identity is (0, 1).
+pub trait EdwardsCurve {
+    type CurveField: Field;
+
+    fn a() -> Self::CurveField;
+    fn d() -> Self::CurveField;
+
+    fn on(point: &(Self::CurveField, Self::CurveField)) -> bool {
+        let (x2, y2) = (
+            point.0.clone() * point.0.clone(),
+            point.1.clone() * point.1.clone(),
+        );
+        Self::a() * x2.clone() + y2.clone() == Self::CurveField::from(1) + Self::d() * x2 * y2
+    }
+
+    // The unified addition law; it has no exceptional cases when a is a square and d is not.
+    fn add(
+        p: &(Self::CurveField, Self::CurveField),
+        q: &(Self::CurveField, Self::CurveField),
+    ) -> (Self::CurveField, Self::CurveField) {
+        let one = Self::CurveField::from(1);
+        let (x1, y1) = p.clone();
+        let (x2, y2) = q.clone();
+        let t = Self::d() * x1.clone() * x2.clone() * y1.clone() * y2.clone();
+        (
+            (x1.clone() * y2.clone() + y1.clone() * x2.clone()) / (one.clone() + t.clone()),
+            (y1 * y2 - Self::a() * x1 * x2) / (one - t),
+        )
+    }
+}
+
+// The short Weierstrass curve birationally equivalent to the Montgomery curve `M`:
+// x = u/B + A/3B, y = v/B, with a = (3 - A²)/3B² and b = (2A³ - 9A)/27B³.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeierstrassForm<M>(PhantomData<fn() -> M>);
+
+impl<M: MontgomeryCurve> EllipticCurve for WeierstrassForm<M> {
+    type CurveField = M::CurveField;
+
+    fn a() -> M::CurveField {
+        let (a, b) = (M::a(), M::b());
+        (M::CurveField::from(3) - a.clone() * a) / (M::CurveField::from(3) * b.clone() * b)
+    }
+
+    fn b() -> M::CurveField {
+        let (a, b) = (M::a(), M::b());
+        let a3 = a.clone() * a.clone() * a.clone();
+        (M::CurveField::from(2) * a3 - M::CurveField::from(9) * a)
+            / (M::CurveField::from(27) * b.clone() * b.clone() * b)
+    }
+}
+
+// The Montgomery curve birationally equivalent to the twisted Edwards curve `E`:
+// A = 2(a + d)/(a - d), B = 4/(a - d).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MontgomeryForm<E>(PhantomData<fn() -> E>);
+
+impl<E: EdwardsCurve> MontgomeryCurve for MontgomeryForm<E> {
+    type CurveField = E::CurveField;
+
+    fn a() -> E::CurveField {
+        E::CurveField::from(2) * (E::a() + E::d()) / (E::a() - E::d())
+    }
+
+    fn b() -> E::CurveField {
+        E::CurveField::from(4) / (E::a() - E::d())
+    }
+}
+
+pub fn montgomery_to_weierstrass<M: MontgomeryCurve>(
+    point: &GeneralPoint<M::CurveField>,
+) -> Option<PointOnCurve<M::CurveField, WeierstrassForm<M>>> {
+    if !M::on(point) {
+        return None;
+    }
+    let mapped = match (point.x(), point.y()) {
+        (Some(u), Some(v)) => {
+            let b = M::b();
+            let x = u / b.clone() + M::a() / (M::CurveField::from(3) * b.clone());
+            GeneralPoint::finite(x, v / b)
+        }
+        _ => GeneralPoint::Infinite,
+    };
+    PointOnCurve::new(mapped)
+}
+
+pub fn weierstrass_to_montgomery<M: MontgomeryCurve>(
+    point: &PointOnCurve<M::CurveField, WeierstrassForm<M>>,
+) -> GeneralPoint<M::CurveField> {
+    match (point.x(), point.y()) {
+        (Some(x), Some(y)) => {
+            let b = M::b();
+            let u = b.clone() * x - M::a() / M::CurveField::from(3);
+            GeneralPoint::finite(u, b * y)
+        }
+        _ => GeneralPoint::Infinite,
+    }
+}
+
+// u = (1 + y)/(1 - y), v = u/x. The identity (0, 1) maps to infinity and (0, -1) to the
+// 2-torsion point (0, 0).
+pub fn edwards_to_montgomery<E: EdwardsCurve>(
+    point: &(E::CurveField, E::CurveField),
+) -> Option<GeneralPoint<E::CurveField>> {
+    if !E::on(point) {
+        return None;
+    }
+    let (x, y) = point.clone();
+    let (zero, one) = (E::CurveField::from(0), E::CurveField::from(1));
+    if x == zero {
+        return Some(if y == one {
+            GeneralPoint::Infinite
+        } else {
+            GeneralPoint::finite(zero.clone(), zero)
+        });
+    }
+    let u = (one.clone() + y.clone()) / (one - y);
+    Some(GeneralPoint::finite(u.clone(), u / x))
+}
+
+// x = u/v, y = (u - 1)/(u + 1). Returns None for the points that land at infinity on
+// incomplete Edwards curves (u = -1, or v = 0 with u ≠ 0).
+pub fn montgomery_to_edwards<E: EdwardsCurve>(
+    point: &GeneralPoint<E::CurveField>,
+) -> Option<(E::CurveField, E::CurveField)> {
+    let (zero, one) = (E::CurveField::from(0), E::CurveField::from(1));
+    let (u, v) = match (point.x(), point.y()) {
+        (Some(u), Some(v)) => (u, v),
+        _ => return Some((zero, one)),
+    };
+    if u == zero && v == zero {
+        return Some((zero, -one));
+    }
+    if v == zero || u == -one.clone() {
+        return None;
+    }
+    Some((u.clone() / v, (u.clone() - one.clone()) / (u + one)))
+}
+
+// The Montgomery curve behind X25519: v² = u³ + 486662u² + u over 2^255 - 19.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Curve25519;
+
+impl MontgomeryCurve for Curve25519 {
+    type CurveField = FiniteFieldElement<Curve25519Prime>;
+
+    fn a() -> Self::CurveField {
+        FiniteFieldElement::from(486662)
+    }
+
+    fn b() -> Self::CurveField {
+        FiniteFieldElement::from(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::Wei25519;
+    use crate::field::Prime13;
+    use crate::small_field::SmallPrimeField;
+
+    type F13 = SmallPrimeField<Prime13>;
+
+    // x² + y² = 1 + 2x²y² over F13; 2 is not a square mod 13, so the curve is complete.
+    #[derive(Debug)]
+    struct SmallEdwards;
+
+    impl EdwardsCurve for SmallEdwards {
+        type CurveField = F13;
+
+        fn a() -> F13 {
+            F13::from(1)
+        }
+
+        fn d() -> F13 {
+            F13::from(2)
+        }
+    }
+
+    fn edwards_points() -> Vec<(F13, F13)> {
+        (0..13)
+            .flat_map(|x| (0..13).map(move |y| (F13::from(x), F13::from(y))))
+            .filter(SmallEdwards::on)
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_on_small_curve() {
+        type M = MontgomeryForm<SmallEdwards>;
+
+        let points = edwards_points();
+        assert!(points.len() > 4);
+        for p in &points {
+            let m = edwards_to_montgomery::<SmallEdwards>(p).unwrap();
+            assert!(M::on(&m), "{:?} maps off the Montgomery curve", p);
+
+            let w = montgomery_to_weierstrass::<M>(&m).unwrap();
+            assert_eq!(weierstrass_to_montgomery::<M>(&w), m);
+            assert_eq!(montgomery_to_edwards::<SmallEdwards>(&m), Some(*p));
+        }
+    }
+
+    #[test]
+    fn maps_are_group_homomorphisms() {
+        type M = MontgomeryForm<SmallEdwards>;
+        let to_weierstrass = |p: &(F13, F13)| {
+            montgomery_to_weierstrass::<M>(&edwards_to_montgomery::<SmallEdwards>(p).unwrap())
+                .unwrap()
+        };
+
+        let points = edwards_points();
+        for p in &points {
+            for q in &points {
+                let sum = SmallEdwards::add(p, q);
+                assert!(SmallEdwards::on(&sum));
+                assert_eq!(to_weierstrass(&sum), to_weierstrass(p) + to_weierstrass(q));
+            }
+        }
+    }
+
+    fn assert_same_curve<A, B>()
+    where
+        A: EllipticCurve,
+        B: EllipticCurve<CurveField = A::CurveField>,
+        A::CurveField: std::fmt::Debug,
+    {
+        assert_eq!(A::a(), B::a());
+        assert_eq!(A::b(), B::b());
+    }
+
+    #[test]
+    fn curve25519_is_wei25519() {
+        assert_same_curve::<WeierstrassForm<Curve25519>, Wei25519>();
+
+        let base = GeneralPoint::finite(
+            FiniteFieldElement::from(9),
+            "14781619447589544791020593568409986887264606134616475288964881837755586237401"
+                .parse()
+                .unwrap(),
+        );
+        let mapped = montgomery_to_weierstrass::<Curve25519>(&base).unwrap();
+        let g = PointOnCurve::<_, Wei25519>::generator();
+        assert_eq!((mapped.x(), mapped.y()), (g.x(), g.y()));
+        assert_eq!(weierstrass_to_montgomery::<Curve25519>(&mapped), base);
+    }
+}
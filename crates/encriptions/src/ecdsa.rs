@@ -0,0 +1,816 @@
+use crate::fe256::Fe256;
+use crate::field::{Inverse, Prime, ScalarFieldElement, Secp256k1Order};
+use crate::hashes::{self, hmac_sha256};
+use crate::keys::{PrivateKey, PublicKey};
+use crate::msm::{multi_scalar_mul, strauss};
+use crate::point::S256Point;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use num::{BigInt, BigUint, Zero};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+use std::fmt;
+use std::marker::PhantomData;
+
+// What sign and verify take: anything that determines the 256-bit integer z. A BigUint is used as
+// z directly, the way the book computes it; digests are read big-endian. Byte slices are hashed,
+// so digest bytes go through `Prehashed`.
+pub trait Message {
+    fn z(&self) -> BigUint;
+}
+
+impl Message for BigUint {
+    fn z(&self) -> BigUint {
+        self.clone()
+    }
+}
+
+/// A 32-byte digest that is already the message hash, used as z without hashing again.
+///
+/// A bare array is not a message, so digest bytes have to be marked as such rather than
+/// being silently signed unhashed where a slice of the same bytes would be hashed:
+///
+/// ```compile_fail
+/// use encriptions::field::ScalarFieldElement;
+/// use encriptions::keys::PrivateKey;
+///
+/// let key = PrivateKey::new(ScalarFieldElement::from(12345)).unwrap();
+/// key.sign(&[0u8; 32]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prehashed(pub [u8; 32]);
+
+impl Message for Prehashed {
+    fn z(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.0)
+    }
+}
+
+// Plain bytes are hashed with hash256 first, as Raw::new does.
+impl Message for [u8] {
+    fn z(&self) -> BigUint {
+        Raw::new(self).z()
+    }
+}
+
+impl Message for hashes::Sha256 {
+    fn z(&self) -> BigUint {
+        BigUint::from_bytes_be(self.as_byte_array())
+    }
+}
+
+impl Message for hashes::Sha256d {
+    fn z(&self) -> BigUint {
+        BigUint::from_bytes_be(self.as_byte_array())
+    }
+}
+
+// The hash a `Raw` message is signed under.
+pub trait MessageHash {
+    fn hash(data: &[u8]) -> [u8; 32];
+}
+
+impl MessageHash for hashes::Sha256 {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+}
+
+impl MessageHash for hashes::Sha256d {
+    fn hash(data: &[u8]) -> [u8; 32] {
+        hashes::hash256(data)
+    }
+}
+
+// Bytes that still need hashing, with double SHA-256 (hash256) unless another hash is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Raw<'a, H = hashes::Sha256d> {
+    bytes: &'a [u8],
+    hash: PhantomData<H>,
+}
+
+impl<'a> Raw<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_hash(bytes)
+    }
+}
+
+impl<'a, H: MessageHash> Raw<'a, H> {
+    pub fn with_hash(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            hash: PhantomData,
+        }
+    }
+}
+
+impl<H: MessageHash> Message for Raw<'_, H> {
+    fn z(&self) -> BigUint {
+        BigUint::from_bytes_be(&H::hash(self.bytes))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub r: ScalarFieldElement,
+    pub s: ScalarFieldElement,
+}
+
+impl Signature {
+    pub fn new(r: ScalarFieldElement, s: ScalarFieldElement) -> Self {
+        Self { r, s }
+    }
+
+    // Bitcoin nodes treat s > n/2 as non-standard (BIP62/BIP146); n - s verifies just the same.
+    pub fn is_low_s(&self) -> bool {
+        self.s.value() <= &(Secp256k1Order::get_prime() >> 1)
+    }
+
+    // An r below 2^255 needs no 0x00 pad in DER, so together with low s the encoding is at most
+    // 70 bytes.
+    pub fn is_low_r(&self) -> bool {
+        self.r.to_bytes_be()[0] < 0x80
+    }
+
+    pub fn normalize_s(&mut self) {
+        if !self.is_low_s() {
+            self.s = -self.s.clone();
+        }
+    }
+
+    // Q = (s·R - z·G)/r, where R is the point with x = r (+ n when bit 1 of the id is set) and
+    // the y parity given by bit 0.
+    pub fn recover<M: Message + ?Sized>(&self, msg: &M, recovery_id: u8) -> Option<PublicKey> {
+        if recovery_id > 3 || self.r.is_zero() || self.s.is_zero() {
+            return None;
+        }
+
+        let mut x = self.r.value().clone();
+        if recovery_id & 2 != 0 {
+            x += Secp256k1Order::get_prime();
+        }
+        let big_r = S256Point::lift_x(Fe256::from_biguint(&x)?, recovery_id & 1 == 1)?;
+
+        let z = ScalarFieldElement::from_biguint_reduced(&msg.z());
+        let r_inv = self.r.inverse().ok()?;
+        let u = -(&z * &r_inv);
+        let v = &self.s * &r_inv;
+        PublicKey::from_point(strauss(&[
+            (BigInt::from(u.value().clone()), S256Point::generator()),
+            (BigInt::from(v.value().clone()), big_r),
+        ]))
+    }
+
+    // 0x30 len 0x02 len r 0x02 len s, each integer minimal and padded with a zero byte when its
+    // high bit is set so it does not read as negative.
+    pub fn der(&self) -> Vec<u8> {
+        let mut body = der_integer(&self.r);
+        body.extend(der_integer(&self.s));
+
+        let mut out = vec![0x30, body.len() as u8];
+        out.extend(body);
+        out
+    }
+
+    // Strict in the way BIP66 is: the lengths must add up exactly, integers are minimal and
+    // non-negative, and nothing may follow the sequence.
+    pub fn parse_der(bytes: &[u8]) -> Result<Self, DerError> {
+        if bytes.len() < 8 || bytes.len() > 72 {
+            return Err(DerError::InvalidLength(bytes.len()));
+        }
+        if bytes[0] != 0x30 {
+            return Err(DerError::NotASequence);
+        }
+        let declared = bytes[1] as usize;
+        if declared != bytes.len() - 2 {
+            return Err(DerError::SequenceLengthMismatch {
+                declared,
+                actual: bytes.len() - 2,
+            });
+        }
+
+        let mut rest = &bytes[2..];
+        let r = parse_der_integer(&mut rest)?;
+        let s = parse_der_integer(&mut rest)?;
+        if !rest.is_empty() {
+            return Err(DerError::TrailingBytes);
+        }
+        Ok(Self { r, s })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerError {
+    InvalidLength(usize),
+    NotASequence,
+    SequenceLengthMismatch { declared: usize, actual: usize },
+    Truncated,
+    NotAnInteger,
+    EmptyInteger,
+    NegativeInteger,
+    NonMinimalInteger,
+    ScalarOutOfRange,
+    TrailingBytes,
+}
+
+impl fmt::Display for DerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerError::InvalidLength(len) => {
+                write!(f, "DER signature must be 8 to 72 bytes, got {}", len)
+            }
+            DerError::NotASequence => write!(f, "DER signature does not start with 0x30"),
+            DerError::SequenceLengthMismatch { declared, actual } => write!(
+                f,
+                "DER sequence declares {} bytes but {} follow",
+                declared, actual
+            ),
+            DerError::Truncated => write!(f, "DER integer runs past the end of the signature"),
+            DerError::NotAnInteger => write!(f, "expected a DER integer (0x02)"),
+            DerError::EmptyInteger => write!(f, "DER integer has zero length"),
+            DerError::NegativeInteger => write!(f, "DER integer is negative"),
+            DerError::NonMinimalInteger => write!(f, "DER integer has excess zero padding"),
+            DerError::ScalarOutOfRange => write!(f, "signature value is not below the group order"),
+            DerError::TrailingBytes => write!(f, "unexpected bytes after the second DER integer"),
+        }
+    }
+}
+
+impl std::error::Error for DerError {}
+
+// The 65-byte form wallets use for signed messages: a header byte 27 + recovery id, plus 4 when
+// the signer's address uses the compressed public key, then r and s as 32 bytes each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoverableSignature {
+    pub signature: Signature,
+    pub recovery_id: u8,
+    pub compressed: bool,
+}
+
+impl RecoverableSignature {
+    pub fn to_compact(&self) -> [u8; 65] {
+        let mut out = [0u8; 65];
+        out[0] = 27 + self.recovery_id + if self.compressed { 4 } else { 0 };
+        out[1..33].copy_from_slice(&self.signature.r.to_bytes_be());
+        out[33..].copy_from_slice(&self.signature.s.to_bytes_be());
+        out
+    }
+
+    pub fn from_compact(bytes: &[u8]) -> Result<Self, CompactError> {
+        if bytes.len() != 65 {
+            return Err(CompactError::InvalidLength(bytes.len()));
+        }
+        let header = bytes[0];
+        if !(27..=34).contains(&header) {
+            return Err(CompactError::InvalidHeader(header));
+        }
+
+        let scalar = |b: &[u8]| {
+            ScalarFieldElement::from_bytes_be(b.try_into().unwrap())
+                .ok_or(CompactError::ScalarOutOfRange)
+        };
+        Ok(Self {
+            signature: Signature::new(scalar(&bytes[1..33])?, scalar(&bytes[33..])?),
+            recovery_id: (header - 27) & 3,
+            compressed: header >= 31,
+        })
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.to_compact())
+    }
+
+    pub fn from_base64(s: &str) -> Result<Self, CompactError> {
+        let bytes = STANDARD
+            .decode(s)
+            .map_err(|_| CompactError::InvalidBase64)?;
+        Self::from_compact(&bytes)
+    }
+
+    pub fn recover<M: Message + ?Sized>(&self, msg: &M) -> Option<PublicKey> {
+        self.signature.recover(msg, self.recovery_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactError {
+    InvalidBase64,
+    InvalidLength(usize),
+    InvalidHeader(u8),
+    ScalarOutOfRange,
+}
+
+impl fmt::Display for CompactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactError::InvalidBase64 => write!(f, "invalid base64"),
+            CompactError::InvalidLength(len) => {
+                write!(f, "compact signature must be 65 bytes, got {}", len)
+            }
+            CompactError::InvalidHeader(header) => {
+                write!(f, "compact signature header {} is not in 27..=34", header)
+            }
+            CompactError::ScalarOutOfRange => {
+                write!(f, "signature value is not below the group order")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompactError {}
+
+fn der_integer(value: &ScalarFieldElement) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(31);
+    let digits = &bytes[start..];
+
+    let mut out = vec![0x02];
+    if digits[0] & 0x80 != 0 {
+        out.extend([digits.len() as u8 + 1, 0]);
+    } else {
+        out.push(digits.len() as u8);
+    }
+    out.extend(digits);
+    out
+}
+
+fn parse_der_integer(input: &mut &[u8]) -> Result<ScalarFieldElement, DerError> {
+    if input.len() < 2 {
+        return Err(DerError::Truncated);
+    }
+    if input[0] != 0x02 {
+        return Err(DerError::NotAnInteger);
+    }
+    let len = input[1] as usize;
+    if input.len() - 2 < len {
+        return Err(DerError::Truncated);
+    }
+    let digits = &input[2..2 + len];
+    *input = &input[2 + len..];
+
+    if digits.is_empty() {
+        return Err(DerError::EmptyInteger);
+    }
+    if digits[0] & 0x80 != 0 {
+        return Err(DerError::NegativeInteger);
+    }
+    if digits.len() > 1 && digits[0] == 0 && digits[1] & 0x80 == 0 {
+        return Err(DerError::NonMinimalInteger);
+    }
+    ScalarFieldElement::new(BigUint::from_bytes_be(digits)).ok_or(DerError::ScalarOutOfRange)
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Signature({:x},{:x})", self.r, self.s)
+    }
+}
+
+// Plain (r, s) pairs cannot be batched: only x(R) is known, and the unknown sign of each R breaks
+// the linear combination. The recovery id pins R down, so s·R = z·G + r·P holds exactly and
+// Σ a_i·(s_i·R_i - r_i·P_i) - (Σ a_i·z_i)·G = O checks the whole batch with one multi-scalar
+// multiplication. The 128-bit weights a_i are hashed from the entire batch, so a forger cannot
+// choose signatures whose errors cancel out.
+pub fn verify_batch<M: Message>(items: &[(M, RecoverableSignature, PublicKey)]) -> bool {
+    let zs: Vec<ScalarFieldElement> = items
+        .iter()
+        .map(|(msg, _, _)| ScalarFieldElement::from_biguint_reduced(&msg.z()))
+        .collect();
+    let mut seed = Sha256::new();
+    for (z, (_, sig, key)) in zs.iter().zip(items) {
+        seed.update(z.to_bytes_be());
+        seed.update(sig.to_compact());
+        seed.update(key.to_sec(true));
+    }
+    let seed = seed.finalize();
+    let weight = |i: usize| {
+        if i == 0 {
+            return ScalarFieldElement::from(1);
+        }
+        let hash = Sha256::new()
+            .chain_update(seed)
+            .chain_update((i as u64).to_le_bytes())
+            .finalize();
+        ScalarFieldElement::from_biguint_reduced(&BigUint::from_bytes_be(&hash[..16]))
+    };
+
+    let mut terms = Vec::with_capacity(2 * items.len() + 1);
+    let mut g_coefficient = ScalarFieldElement::zero();
+    for (i, (z, (_, sig, key))) in zs.iter().zip(items).enumerate() {
+        let Signature { r, s } = &sig.signature;
+        if r.is_zero() || s.is_zero() || sig.recovery_id > 3 {
+            return false;
+        }
+        let mut x = r.value().clone();
+        if sig.recovery_id & 2 != 0 {
+            x += Secp256k1Order::get_prime();
+        }
+        let big_r = match Fe256::from_biguint(&x)
+            .and_then(|x| S256Point::lift_x(x, sig.recovery_id & 1 == 1))
+        {
+            Some(point) => point,
+            None => return false,
+        };
+
+        let a = weight(i);
+        g_coefficient = &g_coefficient - &(&a * z);
+        terms.push((BigInt::from((&a * s).value().clone()), big_r));
+        terms.push((BigInt::from((-(&a * r)).value().clone()), *key.point()));
+    }
+    terms.push((
+        BigInt::from(g_coefficient.value().clone()),
+        S256Point::generator(),
+    ));
+
+    multi_scalar_mul(&terms).x().is_none()
+}
+
+// Two signatures sharing r were made with the same k, up to sign since low-S normalization may
+// have negated either s: s₁ ∓ s₂ = (z₁ - z₂)/k, then e = (s₁·k - z₁)/r. The right candidate for k
+// is the one whose x(k·G) gives r back. This is how a repeated k gave away the PS3 signing key.
+pub fn recover_private_key<M: Message + ?Sized>(
+    sig1: &Signature,
+    sig2: &Signature,
+    msg1: &M,
+    msg2: &M,
+) -> Option<PrivateKey> {
+    if sig1.r != sig2.r {
+        return None;
+    }
+    let z1 = ScalarFieldElement::from_biguint_reduced(&msg1.z());
+    let dz = &z1 - &ScalarFieldElement::from_biguint_reduced(&msg2.z());
+    if dz.is_zero() {
+        return None;
+    }
+    [&sig1.s - &sig2.s, &sig1.s + &sig2.s]
+        .iter()
+        .find_map(|ds| {
+            let k = &dz * &ds.inverse().ok()?;
+            let x = (&k * S256Point::generator()).x()?.to_biguint();
+            if ScalarFieldElement::from_biguint_reduced(&x) != sig1.r {
+                return None;
+            }
+            PrivateKey::new(&(&(&sig1.s * &k) - &z1) * &sig1.r.inverse().ok()?)
+        })
+}
+
+// RFC 6979 section 3.2 with HMAC-SHA256. z is reduced mod n first, which is what bits2octets
+// does for 256-bit hashes.
+pub fn deterministic_k(secret: &ScalarFieldElement, z: &BigUint) -> ScalarFieldElement {
+    deterministic_k_with_extra(secret, z, &[])
+}
+
+// The same with additional data appended after the hash, as section 3.6 allows. Different extra
+// data gives an unrelated k for the same key and message.
+pub fn deterministic_k_with_extra(
+    secret: &ScalarFieldElement,
+    z: &BigUint,
+    extra: &[u8],
+) -> ScalarFieldElement {
+    let z = ScalarFieldElement::from_biguint_reduced(z).to_bytes_be();
+    let secret = secret.to_bytes_be();
+
+    let mut k = [0u8; 32];
+    let mut v = [1u8; 32];
+    k = hmac_sha256(&k, &[&v, &[0], &secret, &z, extra]);
+    v = hmac_sha256(&k, &[&v]);
+    k = hmac_sha256(&k, &[&v, &[1], &secret, &z, extra]);
+    v = hmac_sha256(&k, &[&v]);
+    loop {
+        v = hmac_sha256(&k, &[&v]);
+        if let Some(candidate) = ScalarFieldElement::from_bytes_be(&v) {
+            if !candidate.is_zero() {
+                return candidate;
+            }
+        }
+        k = hmac_sha256(&k, &[&v, &[0]]);
+        v = hmac_sha256(&k, &[&v]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use crate::keys::PrivateKey;
+
+    fn scalar(s: &str) -> ScalarFieldElement {
+        ScalarFieldElement::new(BigUint::parse_bytes(s.as_bytes(), 16).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn der_book_vector() {
+        let sig = Signature::new(
+            scalar("37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6"),
+            scalar("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"),
+        );
+        assert_eq!(
+            hex::encode(&sig.der()),
+            "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6\
+             0221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"
+        );
+    }
+
+    #[test]
+    fn der_strips_leading_zeros() {
+        let sig = Signature::new(ScalarFieldElement::from(0x80), ScalarFieldElement::from(1));
+        assert_eq!(
+            sig.der(),
+            vec![0x30, 0x07, 0x02, 0x02, 0x00, 0x80, 0x02, 0x01, 0x01]
+        );
+
+        let sig = Signature::new(scalar("ff"), ScalarFieldElement::zero());
+        assert_eq!(
+            sig.der(),
+            vec![0x30, 0x07, 0x02, 0x02, 0x00, 0xff, 0x02, 0x01, 0x00]
+        );
+    }
+
+    #[test]
+    fn parse_der_round_trips() {
+        let sig = Signature::new(
+            scalar("37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6"),
+            scalar("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"),
+        );
+        assert_eq!(Signature::parse_der(&sig.der()), Ok(sig));
+
+        let small = Signature::new(ScalarFieldElement::from(0x80), ScalarFieldElement::zero());
+        assert_eq!(Signature::parse_der(&small.der()), Ok(small));
+    }
+
+    #[test]
+    fn parse_der_rejects_malformed() {
+        let parse = Signature::parse_der;
+        let ok = [0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01];
+        assert!(parse(&ok).is_ok());
+
+        assert_eq!(parse(&ok[..7]), Err(DerError::InvalidLength(7)));
+        assert_eq!(parse(&[0x30; 73]), Err(DerError::InvalidLength(73)));
+        assert_eq!(
+            parse(&[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]),
+            Err(DerError::NotASequence)
+        );
+        assert_eq!(
+            parse(&[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]),
+            Err(DerError::SequenceLengthMismatch {
+                declared: 7,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            parse(&[0x30, 0x06, 0x03, 0x01, 0x01, 0x02, 0x01, 0x01]),
+            Err(DerError::NotAnInteger)
+        );
+        assert_eq!(
+            parse(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x02, 0x01]),
+            Err(DerError::Truncated)
+        );
+        assert_eq!(
+            parse(&[0x30, 0x06, 0x02, 0x00, 0x02, 0x02, 0x01, 0x01]),
+            Err(DerError::EmptyInteger)
+        );
+        assert_eq!(
+            parse(&[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01]),
+            Err(DerError::NegativeInteger)
+        );
+        assert_eq!(
+            parse(&[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01]),
+            Err(DerError::NonMinimalInteger)
+        );
+        assert_eq!(
+            parse(&[0x30, 0x09, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x05, 0x00, 0x00]),
+            Err(DerError::TrailingBytes)
+        );
+
+        let mut too_big = vec![0x30, 0x26, 0x02, 0x21, 0x00];
+        too_big.extend([0xff; 32]);
+        too_big.extend([0x02, 0x01, 0x01]);
+        assert_eq!(parse(&too_big), Err(DerError::ScalarOutOfRange));
+    }
+
+    #[test]
+    fn normalize_s() {
+        let half: BigUint = Secp256k1Order::get_prime() >> 1;
+        let mut sig = Signature::new(
+            ScalarFieldElement::from(1),
+            ScalarFieldElement::new(half.clone()).unwrap(),
+        );
+        assert!(sig.is_low_s());
+
+        sig.s = ScalarFieldElement::new(half + 1u64).unwrap();
+        assert!(!sig.is_low_s());
+        let high = sig.s.clone();
+        sig.normalize_s();
+        assert!(sig.is_low_s());
+        assert_eq!(sig.s, -high);
+
+        let before = sig.clone();
+        sig.normalize_s();
+        assert_eq!(sig, before);
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let sig = RecoverableSignature {
+            signature: Signature::new(
+                scalar("37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6"),
+                scalar("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"),
+            ),
+            recovery_id: 1,
+            compressed: true,
+        };
+        let compact = sig.to_compact();
+        assert_eq!(compact[0], 32);
+        assert_eq!(
+            RecoverableSignature::from_compact(&compact),
+            Ok(sig.clone())
+        );
+        assert_eq!(
+            RecoverableSignature::from_base64(&sig.to_base64()),
+            Ok(sig.clone())
+        );
+
+        let uncompressed = RecoverableSignature {
+            compressed: false,
+            ..sig
+        };
+        assert_eq!(uncompressed.to_compact()[0], 28);
+        assert_eq!(
+            RecoverableSignature::from_compact(&uncompressed.to_compact()),
+            Ok(uncompressed)
+        );
+    }
+
+    #[test]
+    fn compact_rejects_malformed() {
+        let mut bytes = [1u8; 65];
+        bytes[0] = 26;
+        assert_eq!(
+            RecoverableSignature::from_compact(&bytes),
+            Err(CompactError::InvalidHeader(26))
+        );
+        bytes[0] = 35;
+        assert_eq!(
+            RecoverableSignature::from_compact(&bytes),
+            Err(CompactError::InvalidHeader(35))
+        );
+        bytes[0] = 27;
+        assert!(RecoverableSignature::from_compact(&bytes).is_ok());
+        assert_eq!(
+            RecoverableSignature::from_compact(&bytes[..64]),
+            Err(CompactError::InvalidLength(64))
+        );
+        bytes[1..33].copy_from_slice(&[0xff; 32]);
+        assert_eq!(
+            RecoverableSignature::from_compact(&bytes),
+            Err(CompactError::ScalarOutOfRange)
+        );
+        assert_eq!(
+            RecoverableSignature::from_base64("not base64!"),
+            Err(CompactError::InvalidBase64)
+        );
+    }
+
+    #[test]
+    fn batch_verification() {
+        let items: Vec<(BigUint, RecoverableSignature, PublicKey)> = (1..=4)
+            .map(|i| {
+                let key = PrivateKey::new(ScalarFieldElement::from(1000 + i)).unwrap();
+                let z = BigUint::from_bytes_be(&Sha256::digest(i.to_be_bytes()));
+                let (signature, recovery_id) = key.sign_recoverable(&z);
+                let sig = RecoverableSignature {
+                    signature,
+                    recovery_id,
+                    compressed: true,
+                };
+                (z, sig, *key.public_key())
+            })
+            .collect();
+        assert!(verify_batch(&items));
+        assert!(verify_batch::<BigUint>(&[]));
+
+        let mut bad = items.clone();
+        bad[2].0 += 1u64;
+        assert!(!verify_batch(&bad));
+
+        let mut bad = items.clone();
+        bad[1].1.recovery_id ^= 1;
+        assert!(!verify_batch(&bad));
+
+        // Swapping the keys of two signatures invalidates both.
+        let mut bad = items;
+        let key_0 = bad[0].2;
+        bad[0].2 = bad[1].2;
+        bad[1].2 = key_0;
+        assert!(!verify_batch(&bad));
+    }
+
+    #[test]
+    fn low_r() {
+        let mut sig = Signature::new(ScalarFieldElement::from(1), ScalarFieldElement::from(1));
+        assert!(sig.is_low_r());
+        sig.r = -ScalarFieldElement::from(1);
+        assert!(!sig.is_low_r());
+    }
+
+    #[test]
+    fn nonce_reuse_leaks_the_key() {
+        // A signer whose RNG is stuck on one k.
+        let key = PrivateKey::new(scalar(
+            "8cd8d1a5cbdf6b1ab6a8c7d0da39dd39e2bf3e1ff2fc5fb4ad1cbcd3dd0a2a9f",
+        ))
+        .unwrap();
+        let k = ScalarFieldElement::from(1234567890);
+        let sign = |z: &BigUint| {
+            let x = (&k * S256Point::generator()).x().unwrap().to_biguint();
+            let r = ScalarFieldElement::from_biguint_reduced(&x);
+            let z = ScalarFieldElement::from_biguint_reduced(z);
+            let mut sig = Signature::new(r.clone(), (&z + &(&r * key.secret())) / k.clone());
+            sig.normalize_s();
+            sig
+        };
+        let z1 = BigUint::from_bytes_be(&Sha256::digest(b"pay alice"));
+        let z2 = BigUint::from_bytes_be(&Sha256::digest(b"pay bob"));
+        let (sig1, sig2) = (sign(&z1), sign(&z2));
+        assert!(key.public_key().verify(&z1, &sig1));
+        assert!(key.public_key().verify(&z2, &sig2));
+
+        assert_eq!(
+            recover_private_key(&sig1, &sig2, &z1, &z2).as_ref(),
+            Some(&key)
+        );
+        // Whichever s was negated, and in either order.
+        let flipped = Signature::new(sig2.r.clone(), -sig2.s.clone());
+        assert_eq!(
+            recover_private_key(&flipped, &sig1, &z2, &z1).as_ref(),
+            Some(&key)
+        );
+
+        // RFC 6979 signatures of different messages never share r.
+        assert_eq!(
+            recover_private_key(&key.sign(&z1), &key.sign(&z2), &z1, &z2),
+            None
+        );
+        assert_eq!(recover_private_key(&sig1, &sig1, &z1, &z1), None);
+    }
+
+    #[test]
+    fn message_forms_agree() {
+        let digest: [u8; 32] = Sha256::digest(Sha256::digest(b"my message")).into();
+        let z = BigUint::from_bytes_be(&digest);
+        assert_eq!(Raw::new(b"my message").z(), z);
+        assert_eq!(Prehashed(digest).z(), z);
+        assert_eq!(hashes::Sha256d::from_byte_array(digest).z(), z);
+
+        let single: [u8; 32] = Sha256::digest(b"my message").into();
+        assert_eq!(
+            Raw::<hashes::Sha256>::with_hash(b"my message").z(),
+            Prehashed(single).z()
+        );
+
+        // A bare slice is raw bytes, hashed like Raw::new.
+        let bytes: &[u8] = b"my message";
+        assert_eq!(bytes.z(), z);
+        let key = PrivateKey::new(ScalarFieldElement::from(12345)).unwrap();
+        let (sig, recovery_id) = key.sign_recoverable(bytes);
+        assert_eq!(sig, key.sign(&Raw::new(bytes)));
+        assert!(key.public_key().verify(bytes, &sig));
+        assert_eq!(
+            sig.recover(bytes, recovery_id).as_ref(),
+            Some(key.public_key())
+        );
+    }
+
+    #[test]
+    fn arrays_are_not_digests_by_accident() {
+        // The same 32 bytes as a slice are hashed; only Prehashed uses them as z.
+        let bytes: [u8; 32] = Sha256::digest(b"my message").into();
+        let key = PrivateKey::new(ScalarFieldElement::from(12345)).unwrap();
+
+        assert_eq!(key.sign(&bytes[..]), key.sign(&Raw::new(&bytes)));
+        assert_ne!(key.sign(&bytes[..]), key.sign(&Prehashed(bytes)));
+        assert_eq!(
+            key.sign(&Prehashed(bytes)),
+            key.sign(&BigUint::from_bytes_be(&bytes))
+        );
+    }
+
+    #[test]
+    fn rfc6979_secp256k1_vectors() {
+        let k = |message: &str| {
+            let z = BigUint::from_bytes_be(&Sha256::digest(message.as_bytes()));
+            format!(
+                "{:x}",
+                deterministic_k(&ScalarFieldElement::from(1), &z).value()
+            )
+        };
+        assert_eq!(
+            k("Satoshi Nakamoto"),
+            "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15"
+        );
+        assert_eq!(
+            k("All those moments will be lost in time, like tears in rain. Time to die..."),
+            "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3"
+        );
+    }
+}
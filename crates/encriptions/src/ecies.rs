@@ -0,0 +1,155 @@
+use crate::keys::{PrivateKey, PublicKey, SecError};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+use std::fmt;
+
+const INFO: &[u8] = b"encriptions ECIES secp256k1 ChaCha20Poly1305";
+const EPHEMERAL_LEN: usize = 33;
+const TAG_LEN: usize = 16;
+
+// HKDF-SHA256 over the x coordinate of the shared point, salted with both public keys so the
+// derived key is bound to this sender/recipient pair. The key is fresh for every message, so
+// deriving the nonce alongside it never repeats a (key, nonce) pair.
+fn cipher(
+    ephemeral: &PublicKey,
+    recipient: &PublicKey,
+    shared: &PublicKey,
+) -> (ChaCha20Poly1305, Nonce) {
+    let mut salt = ephemeral.to_sec(true);
+    salt.extend(recipient.to_sec(true));
+    let shared_x = &shared.to_sec(true)[1..];
+
+    let mut okm = [0u8; 44];
+    Hkdf::<Sha256>::new(Some(&salt), shared_x)
+        .expand(INFO, &mut okm)
+        .expect("44 bytes is a valid HKDF-SHA256 output length");
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&okm[..32]));
+    (cipher, *Nonce::from_slice(&okm[32..]))
+}
+
+// The output is the ephemeral key's compressed SEC followed by the ciphertext and 16-byte tag.
+pub fn encrypt<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    recipient: &PublicKey,
+    plaintext: &[u8],
+) -> Vec<u8> {
+    let ephemeral = PrivateKey::generate(rng);
+    // The recipient is a valid non-infinite point and the scalar is below the order.
+    let shared = PublicKey::from_point(ephemeral.secret() * *recipient.point()).unwrap();
+    let (cipher, nonce) = cipher(ephemeral.public_key(), recipient, &shared);
+
+    let mut out = ephemeral.public_key().to_sec(true);
+    out.extend(
+        cipher
+            .encrypt(&nonce, plaintext)
+            .expect("ChaCha20Poly1305 accepts any message that fits in memory"),
+    );
+    out
+}
+
+pub fn decrypt(key: &PrivateKey, ciphertext: &[u8]) -> Result<Vec<u8>, EciesError> {
+    if ciphertext.len() < EPHEMERAL_LEN + TAG_LEN {
+        return Err(EciesError::Truncated(ciphertext.len()));
+    }
+    let (ephemeral, sealed) = ciphertext.split_at(EPHEMERAL_LEN);
+    let ephemeral = PublicKey::from_sec(ephemeral).map_err(EciesError::InvalidEphemeralKey)?;
+    let shared = PublicKey::from_point(key.secret() * *ephemeral.point()).unwrap();
+    let (cipher, nonce) = cipher(&ephemeral, key.public_key(), &shared);
+    cipher
+        .decrypt(&nonce, sealed)
+        .map_err(|_| EciesError::AuthenticationFailed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EciesError {
+    Truncated(usize),
+    InvalidEphemeralKey(SecError),
+    AuthenticationFailed,
+}
+
+impl fmt::Display for EciesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EciesError::Truncated(len) => write!(
+                f,
+                "ciphertext of {} bytes is shorter than the key and tag",
+                len
+            ),
+            EciesError::InvalidEphemeralKey(e) => write!(f, "invalid ephemeral key: {}", e),
+            EciesError::AuthenticationFailed => {
+                write!(f, "ciphertext was tampered with or is for another key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EciesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn round_trip() {
+        let mut rng = StdRng::seed_from_u64(596);
+        let key = PrivateKey::generate(&mut rng);
+        for message in [&b""[..], b"hello", &[0xab; 1000]] {
+            let sealed = encrypt(&mut rng, key.public_key(), message);
+            assert_eq!(sealed.len(), EPHEMERAL_LEN + message.len() + TAG_LEN);
+            assert_eq!(decrypt(&key, &sealed), Ok(message.to_vec()));
+        }
+
+        // A fresh ephemeral key every time.
+        let a = encrypt(&mut rng, key.public_key(), b"hello");
+        let b = encrypt(&mut rng, key.public_key(), b"hello");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let mut rng = StdRng::seed_from_u64(597);
+        let key = PrivateKey::generate(&mut rng);
+        let sealed = encrypt(&mut rng, key.public_key(), b"attack at dawn");
+
+        // Every flipped bit in the ciphertext or tag fails authentication.
+        for i in EPHEMERAL_LEN..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert_eq!(
+                decrypt(&key, &tampered),
+                Err(EciesError::AuthenticationFailed)
+            );
+        }
+
+        // Negating the ephemeral key leaves the shared x unchanged; only the salt catches it.
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        assert_eq!(
+            decrypt(&key, &tampered),
+            Err(EciesError::AuthenticationFailed)
+        );
+        tampered[0] = 4;
+        assert_eq!(
+            decrypt(&key, &tampered),
+            Err(EciesError::InvalidEphemeralKey(SecError::InvalidLength {
+                prefix: 4,
+                len: 33
+            }))
+        );
+
+        let other = PrivateKey::generate(&mut rng);
+        assert_eq!(
+            decrypt(&other, &sealed),
+            Err(EciesError::AuthenticationFailed)
+        );
+        assert_eq!(
+            decrypt(&key, &sealed[..EPHEMERAL_LEN + TAG_LEN - 1]),
+            Err(EciesError::Truncated(EPHEMERAL_LEN + TAG_LEN - 1))
+        );
+    }
+}
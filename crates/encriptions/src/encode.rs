@@ -0,0 +1,155 @@
+use num::BigUint;
+use std::io::{self, Read, Write};
+
+// Fixed-width integers as they appear in transactions, blocks and p2p messages. Slices implement
+// Read and Vec<u8> implements Write, so these work on in-memory buffers as well as streams.
+pub fn read_u16_le(reader: &mut impl Read) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+pub fn read_u32_le(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub fn read_u64_le(reader: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+pub fn write_u16_le(writer: &mut impl Write, n: u16) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+pub fn write_u32_le(writer: &mut impl Write, n: u32) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+pub fn write_u64_le(writer: &mut impl Write, n: u64) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+// None if the value needs more than 32 bytes.
+pub fn biguint_to_be_bytes(n: &BigUint) -> Option<[u8; 32]> {
+    let bytes = n.to_bytes_be();
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    Some(out)
+}
+
+pub fn biguint_to_le_bytes(n: &BigUint) -> Option<[u8; 32]> {
+    let mut out = biguint_to_be_bytes(n)?;
+    out.reverse();
+    Some(out)
+}
+
+pub fn biguint_from_be_bytes(bytes: &[u8; 32]) -> BigUint {
+    BigUint::from_bytes_be(bytes)
+}
+
+pub fn biguint_from_le_bytes(bytes: &[u8; 32]) -> BigUint {
+    BigUint::from_bytes_le(bytes)
+}
+
+pub fn read_biguint_be(reader: &mut impl Read) -> io::Result<BigUint> {
+    let mut buf = [0u8; 32];
+    reader.read_exact(&mut buf)?;
+    Ok(biguint_from_be_bytes(&buf))
+}
+
+pub fn read_biguint_le(reader: &mut impl Read) -> io::Result<BigUint> {
+    let mut buf = [0u8; 32];
+    reader.read_exact(&mut buf)?;
+    Ok(biguint_from_le_bytes(&buf))
+}
+
+pub fn write_biguint_be(writer: &mut impl Write, n: &BigUint) -> io::Result<()> {
+    writer.write_all(&biguint_to_be_bytes(n).ok_or_else(too_large)?)
+}
+
+pub fn write_biguint_le(writer: &mut impl Write, n: &BigUint) -> io::Result<()> {
+    writer.write_all(&biguint_to_le_bytes(n).ok_or_else(too_large)?)
+}
+
+fn too_large() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "integer does not fit in 32 bytes",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::One;
+    use proptest::prelude::*;
+
+    #[test]
+    fn fixed_width_integers() {
+        // A transaction version followed by a locktime, as in the book's chapter 5 exercises.
+        let bytes = [0x01, 0x00, 0x00, 0x00, 0x19, 0x43, 0x06, 0x00];
+        let mut reader = &bytes[..];
+        assert_eq!(read_u32_le(&mut reader).unwrap(), 1);
+        assert_eq!(read_u32_le(&mut reader).unwrap(), 410_393);
+        assert_eq!(
+            read_u16_le(&mut reader).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(read_u64_le(&mut &bytes[..]).unwrap(), 0x0006_4319_0000_0001);
+
+        let mut out = Vec::new();
+        write_u16_le(&mut out, 0x0102).unwrap();
+        write_u32_le(&mut out, 0x0304_0506).unwrap();
+        write_u64_le(&mut out, 0x0708_090a_0b0c_0d0e).unwrap();
+        assert_eq!(
+            out,
+            [2, 1, 6, 5, 4, 3, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 9, 8, 7]
+        );
+    }
+
+    #[test]
+    fn biguint_bytes() {
+        let n = BigUint::from(0x0102u32);
+        let mut be = [0u8; 32];
+        be[30..].copy_from_slice(&[1, 2]);
+        let mut le = [0u8; 32];
+        le[..2].copy_from_slice(&[2, 1]);
+        assert_eq!(biguint_to_be_bytes(&n), Some(be));
+        assert_eq!(biguint_to_le_bytes(&n), Some(le));
+        assert_eq!(biguint_from_be_bytes(&be), n);
+        assert_eq!(biguint_from_le_bytes(&le), n);
+
+        let max = (BigUint::one() << 256u32) - 1u32;
+        assert_eq!(biguint_to_be_bytes(&max), Some([0xff; 32]));
+        let too_big = BigUint::one() << 256u32;
+        assert_eq!(biguint_to_be_bytes(&too_big), None);
+        assert_eq!(biguint_to_le_bytes(&too_big), None);
+        assert_eq!(
+            write_biguint_le(&mut Vec::new(), &too_big)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn biguint_stream_roundtrip(bytes in any::<[u8; 32]>()) {
+            let n = biguint_from_be_bytes(&bytes);
+            let mut out = Vec::new();
+            write_biguint_be(&mut out, &n).unwrap();
+            write_biguint_le(&mut out, &n).unwrap();
+            prop_assert_eq!(&out[..32], &bytes[..]);
+            let mut reader = &out[..];
+            prop_assert_eq!(read_biguint_be(&mut reader).unwrap(), n.clone());
+            prop_assert_eq!(read_biguint_le(&mut reader).unwrap(), n);
+        }
+    }
+}
@@ -0,0 +1,386 @@
+use crate::field::{
+    impl_assign_ops, impl_pow_variants, impl_sum_product, Field, FieldError, FiniteFieldElement,
+    Inverse, Prime,
+};
+use crate::small_field::{SmallPrime, SmallPrimeField};
+use num::{BigInt, BigUint, One, Signed, Zero};
+use num_traits::Pow;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+pub trait QuadraticNonResidue: Field {
+    // Fails only for p = 2, where every element is a square.
+    fn non_residue() -> Result<Self, FieldError>;
+}
+
+// A prime with its smallest non-residue found once. `def_prime_struct!` implements this with a
+// static OnceLock per prime; hand-written primes can do the same.
+pub trait NonResiduePrime: Prime {
+    fn non_residue() -> Option<&'static BigUint>;
+}
+
+// The smallest non-residue by Euler's criterion; None for p = 2.
+pub fn smallest_non_residue(prime: &BigUint) -> Option<BigUint> {
+    let minus_one = prime - 1u64;
+    let euler: BigUint = &minus_one >> 1u32;
+    if euler.is_zero() {
+        return None;
+    }
+    (2u64..)
+        .map(BigUint::from)
+        .find(|v| v.modpow(&euler, prime) == minus_one)
+}
+
+impl<P: NonResiduePrime + Clone + PartialEq> QuadraticNonResidue for FiniteFieldElement<P> {
+    fn non_residue() -> Result<Self, FieldError> {
+        P::non_residue()
+            .and_then(|v| Self::new(v.clone()))
+            .ok_or(FieldError::NoNonResidue)
+    }
+}
+
+impl<P: SmallPrime> QuadraticNonResidue for SmallPrimeField<P> {
+    fn non_residue() -> Result<Self, FieldError> {
+        P::NON_RESIDUE
+            .and_then(Self::new)
+            .ok_or(FieldError::NoNonResidue)
+    }
+}
+
+fn non_residue<F: QuadraticNonResidue>() -> F {
+    F::non_residue().expect("F_2 has no quadratic extension of this form")
+}
+
+// a + b·u where u² = F::non_residue(). Only fields of odd characteristic have one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadraticExtension<F>(F, F);
+
+impl<F: QuadraticNonResidue> QuadraticExtension<F> {
+    pub fn new(re: F, im: F) -> Result<Self, FieldError> {
+        F::non_residue()?;
+        Ok(Self(re, im))
+    }
+
+    pub fn re(&self) -> &F {
+        &self.0
+    }
+
+    pub fn im(&self) -> &F {
+        &self.1
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self(self.0.clone(), -self.1.clone())
+    }
+
+    pub fn norm(&self) -> F {
+        self.0.clone() * self.0.clone() - non_residue::<F>() * self.1.clone() * self.1.clone()
+    }
+}
+
+impl<'a, F: QuadraticNonResidue> Add<&'a QuadraticExtension<F>> for &'a QuadraticExtension<F> {
+    type Output = QuadraticExtension<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        QuadraticExtension(
+            self.0.clone() + rhs.0.clone(),
+            self.1.clone() + rhs.1.clone(),
+        )
+    }
+}
+
+impl<'a, F: QuadraticNonResidue> Sub<&'a QuadraticExtension<F>> for &'a QuadraticExtension<F> {
+    type Output = QuadraticExtension<F>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        QuadraticExtension(
+            self.0.clone() - rhs.0.clone(),
+            self.1.clone() - rhs.1.clone(),
+        )
+    }
+}
+
+impl<'a, F: QuadraticNonResidue> Mul<&'a QuadraticExtension<F>> for &'a QuadraticExtension<F> {
+    type Output = QuadraticExtension<F>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let re =
+            self.0.clone() * rhs.0.clone() + non_residue::<F>() * self.1.clone() * rhs.1.clone();
+        let im = self.0.clone() * rhs.1.clone() + self.1.clone() * rhs.0.clone();
+        QuadraticExtension(re, im)
+    }
+}
+
+impl<'a, F: QuadraticNonResidue> Div<&'a QuadraticExtension<F>> for &'a QuadraticExtension<F> {
+    type Output = QuadraticExtension<F>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * &rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<F: QuadraticNonResidue> Add for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<F: QuadraticNonResidue> Sub for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<F: QuadraticNonResidue> Mul for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<F: QuadraticNonResidue> Div for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        &self / &rhs
+    }
+}
+
+impl_assign_ops!([F: QuadraticNonResidue] QuadraticExtension<F>);
+impl_sum_product!([F: QuadraticNonResidue] QuadraticExtension<F>);
+impl_pow_variants!([F: QuadraticNonResidue] QuadraticExtension<F>);
+
+impl<F: QuadraticNonResidue> Neg for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl<F: QuadraticNonResidue> Inverse for QuadraticExtension<F> {
+    // (a + bu)^-1 = (a - bu) / (a² - βb²); the norm only vanishes at zero.
+    fn inverse(&self) -> Result<Self, FieldError> {
+        let norm = self.norm();
+        if norm == F::from(0) {
+            return Err(FieldError::NotInvertible);
+        }
+        let conjugate = self.conjugate();
+        Ok(Self(conjugate.0 / norm.clone(), conjugate.1 / norm))
+    }
+}
+
+impl<'a, F: QuadraticNonResidue> Pow<&'a BigUint> for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        let mut result = Self::one();
+        for i in (0..rhs.bits()).rev() {
+            result = &result * &result;
+            if rhs.bit(i) {
+                result = &result * &self;
+            }
+        }
+        result
+    }
+}
+
+impl<'a, F: QuadraticNonResidue> Pow<&'a BigInt> for QuadraticExtension<F> {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        let base = if rhs.is_negative() {
+            self.inverse().expect("division by zero")
+        } else {
+            self
+        };
+        base.pow(&rhs.magnitude().clone())
+    }
+}
+
+impl<F: QuadraticNonResidue> From<i64> for QuadraticExtension<F> {
+    fn from(v: i64) -> Self {
+        Self(F::from(v), F::from(0))
+    }
+}
+
+impl<F: QuadraticNonResidue> Zero for QuadraticExtension<F> {
+    fn zero() -> Self {
+        Self::from(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+impl<F: QuadraticNonResidue> One for QuadraticExtension<F> {
+    fn one() -> Self {
+        Self::from(1)
+    }
+}
+
+impl<F: QuadraticNonResidue> Field for QuadraticExtension<F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{OverField, Secp256k1};
+    use crate::field::{Prime13, Prime19, Prime223};
+    use crate::field_axioms::{any_element, check_fermat_identity, check_field_axioms};
+    use crate::point::{GeneralPoint, PointOnCurve};
+    use proptest::prelude::*;
+    use std::sync::OnceLock;
+
+    type Gf13Sq = QuadraticExtension<FiniteFieldElement<Prime13>>;
+
+    fn element(re: i64, im: i64) -> Gf13Sq {
+        QuadraticExtension::new(FiniteFieldElement::from(re), FiniteFieldElement::from(im)).unwrap()
+    }
+
+    #[test]
+    fn non_residue() {
+        assert_eq!(
+            FiniteFieldElement::<Prime13>::non_residue(),
+            Ok(FiniteFieldElement::from(2))
+        );
+        assert_eq!(
+            FiniteFieldElement::<Prime223>::non_residue(),
+            Ok(FiniteFieldElement::from(3))
+        );
+        assert_eq!(
+            SmallPrimeField::<Prime223>::non_residue(),
+            Ok(SmallPrimeField::from(3))
+        );
+        assert_eq!(
+            smallest_non_residue(&BigUint::from(3u64)),
+            Some(BigUint::from(2u64))
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Prime2;
+
+    impl Prime for Prime2 {
+        fn get_prime() -> BigUint {
+            BigUint::from(2u64)
+        }
+    }
+
+    impl SmallPrime for Prime2 {
+        const MODULUS: u64 = 2;
+    }
+
+    impl NonResiduePrime for Prime2 {
+        fn non_residue() -> Option<&'static BigUint> {
+            static NON_RESIDUE: OnceLock<Option<BigUint>> = OnceLock::new();
+            NON_RESIDUE
+                .get_or_init(|| smallest_non_residue(&Self::get_prime()))
+                .as_ref()
+        }
+    }
+
+    #[test]
+    fn rejects_characteristic_two() {
+        assert_eq!(
+            FiniteFieldElement::<Prime2>::non_residue(),
+            Err(FieldError::NoNonResidue)
+        );
+        assert_eq!(
+            SmallPrimeField::<Prime2>::non_residue(),
+            Err(FieldError::NoNonResidue)
+        );
+        assert_eq!(
+            QuadraticExtension::new(
+                FiniteFieldElement::<Prime2>::from(1),
+                FiniteFieldElement::from(1)
+            ),
+            Err(FieldError::NoNonResidue)
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let u = element(0, 1);
+        assert_eq!(u.clone() * u.clone(), element(2, 0));
+        assert_eq!(element(1, 1) * element(1, -1), element(1 - 2, 0));
+        assert_eq!(
+            element(3, 5) * element(3, 5).inverse().unwrap(),
+            element(1, 0)
+        );
+        assert_eq!(element(0, 0).inverse(), Err(FieldError::NotInvertible));
+        assert_eq!(u.clone().pow(BigInt::from(-2)), element(7, 0));
+        assert_eq!(u.pow(4u64), element(4, 0));
+    }
+
+    fn any_extension<F>() -> impl Strategy<Value = QuadraticExtension<F>>
+    where
+        F: QuadraticNonResidue + std::fmt::Debug,
+    {
+        (any_element(), any_element()).prop_map(|(re, im)| QuadraticExtension::new(re, im).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms_gf13sq(a in any_extension(), b in any_extension(), c in any_extension()) {
+            check_field_axioms::<Gf13Sq>(a.clone(), b, c)?;
+            check_fermat_identity::<Gf13Sq>(a, &BigUint::from(13u64 * 13))?;
+        }
+
+        #[test]
+        fn field_axioms_gf19sq(a in any_extension(), b in any_extension(), c in any_extension()) {
+            check_field_axioms::<QuadraticExtension<FiniteFieldElement<Prime19>>>(a, b, c)?;
+        }
+    }
+
+    #[test]
+    fn multiplicative_order() {
+        // Every non-zero element of GF(13²) has multiplicative order dividing 13² - 1.
+        for re in 0..13 {
+            for im in 0..13 {
+                if re != 0 || im != 0 {
+                    assert_eq!(element(re, im).pow(168u64), element(1, 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn curve_over_extension() {
+        type F223Sq = QuadraticExtension<FiniteFieldElement<Prime223>>;
+        type Point = PointOnCurve<F223Sq, OverField<Secp256k1, F223Sq>>;
+
+        // Pick x with x³ + 7 a non-residue in F223, so y only exists in the extension.
+        let (x, rhs) = (0..223)
+            .map(FiniteFieldElement::<Prime223>::from)
+            .map(|x| {
+                (
+                    x.clone(),
+                    x.clone() * x.clone() * x + FiniteFieldElement::from(7),
+                )
+            })
+            .find(|(_, rhs)| !rhs.is_square())
+            .unwrap();
+        let c = (rhs / FiniteFieldElement::non_residue().unwrap())
+            .sqrt()
+            .unwrap();
+        let p = Point::new(GeneralPoint::finite(
+            QuadraticExtension::new(x, FiniteFieldElement::from(0)).unwrap(),
+            QuadraticExtension::new(FiniteFieldElement::from(0), c).unwrap(),
+        ))
+        .unwrap();
+
+        let doubled = p.clone() + p.clone();
+        assert_eq!(doubled.clone() + p.clone(), p.mul_naive(&BigInt::from(3)));
+        assert_eq!(
+            p.mul_wnaf(&BigInt::from(1000)),
+            p.mul_naive(&BigInt::from(1000))
+        );
+        assert_eq!(p.clone() + -p, Point::infinity());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::field::{
+    forward_ref_binop, impl_assign_ops, impl_pow_variants, impl_sum_product, Field, FieldError,
+    Inverse,
+};
+use num::{BigInt, BigUint, One, ToPrimitive, Zero};
+use num_traits::Pow;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Real numbers approximated with f64. Rounding breaks the field axioms for all but small
+// values, so this is only built with the `inexact-f64-field` feature; use
+// `RationalElement` for exact arithmetic.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct f64FieldElement(f64);
+
+impl f64FieldElement {
+    pub const ZERO: Self = Self(0.0);
+    pub const ONE: Self = Self(1.0);
+}
+
+impl Add for f64FieldElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for f64FieldElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Mul for f64FieldElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl_assign_ops!([] f64FieldElement);
+impl_sum_product!([] f64FieldElement);
+impl_pow_variants!([] f64FieldElement);
+
+forward_ref_binop!(f64FieldElement, Add, add);
+forward_ref_binop!(f64FieldElement, Sub, sub);
+forward_ref_binop!(f64FieldElement, Mul, mul);
+forward_ref_binop!(f64FieldElement, Div, div);
+
+impl Neg for f64FieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Div for f64FieldElement {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self(self.0 / rhs.0)
+    }
+}
+
+impl Inverse for f64FieldElement {
+    fn inverse(&self) -> Result<Self, FieldError> {
+        if self.0 == 0.0 {
+            Err(FieldError::NotInvertible)
+        } else {
+            Ok(Self(1.0 / self.0))
+        }
+    }
+}
+
+impl<'a> Pow<&'a BigInt> for f64FieldElement {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        match rhs.to_i32() {
+            Some(e) => Self(self.0.powi(e)),
+            None => Self(self.0.powf(rhs.to_f64().unwrap())),
+        }
+    }
+}
+
+impl<'a> Pow<&'a BigUint> for f64FieldElement {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        match rhs.to_i32() {
+            Some(e) => Self(self.0.powi(e)),
+            None => Self(self.0.powf(rhs.to_f64().unwrap())),
+        }
+    }
+}
+
+impl From<f64> for f64FieldElement {
+    fn from(f: f64) -> Self {
+        Self(f)
+    }
+}
+
+impl From<i64> for f64FieldElement {
+    fn from(v: i64) -> Self {
+        Self(v.to_f64().unwrap())
+    }
+}
+
+impl Zero for f64FieldElement {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
+
+impl One for f64FieldElement {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl fmt::Display for f64FieldElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Field for f64FieldElement {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::batch_invert;
+    use crate::field_axioms::check_field_axioms;
+    use proptest::prelude::*;
+
+    #[test]
+    fn inverse_test() {
+        assert_eq!(
+            f64FieldElement::from(0.0).inverse(),
+            Err(FieldError::NotInvertible)
+        );
+        assert_eq!(
+            f64FieldElement::from(4.0).inverse(),
+            Ok(f64FieldElement::from(0.25))
+        );
+    }
+
+    #[test]
+    fn zero_one_neg_test() {
+        let b = f64FieldElement::from(2.5);
+        assert_eq!(b + f64FieldElement::zero(), b);
+        assert_eq!(b * f64FieldElement::ONE, b);
+        assert!(f64FieldElement::ZERO.is_zero());
+        assert_eq!(-b, f64FieldElement::from(-2.5));
+    }
+
+    // Signed powers of two and zero, whose sums, products and inverses stay exact; general
+    // floats break the axioms by rounding.
+    fn any_dyadic() -> impl Strategy<Value = f64FieldElement> {
+        prop_oneof![
+            Just(f64FieldElement::ZERO),
+            (-20i32..20, any::<bool>()).prop_map(|(k, negative)| {
+                let v = 2f64.powi(k);
+                f64FieldElement::from(if negative { -v } else { v })
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms(a in any_dyadic(), b in any_dyadic(), c in any_dyadic()) {
+            check_field_axioms(a, b, c)?;
+        }
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ops_test() {
+        let c = f64FieldElement::from(1.5);
+        let d = f64FieldElement::from(3.0);
+        assert_eq!(&c * &d, c * d);
+        assert_eq!(&c / &d, c / d);
+
+        let mut y = f64FieldElement::from(1.5);
+        y += f64FieldElement::from(0.5);
+        y *= &f64FieldElement::from(3.0);
+        y -= f64FieldElement::from(2.0);
+        y /= f64FieldElement::from(4.0);
+        assert_eq!(y, f64FieldElement::from(1.0));
+
+        let floats = [f64FieldElement::from(1.5), f64FieldElement::from(2.0)];
+        assert_eq!(
+            floats.iter().sum::<f64FieldElement>(),
+            f64FieldElement::from(3.5)
+        );
+        assert_eq!(
+            floats.iter().copied().product::<f64FieldElement>(),
+            f64FieldElement::from(3.0)
+        );
+        assert_eq!(f64FieldElement::from(2.5).to_string(), "2.5");
+
+        let mut floats = [f64FieldElement::from(4.0), f64FieldElement::from(0.5)];
+        batch_invert(&mut floats);
+        assert_eq!(
+            floats,
+            [f64FieldElement::from(0.25), f64FieldElement::from(2.0)]
+        );
+    }
+
+    #[test]
+    fn pow_test() {
+        let two = f64FieldElement::from(2.0);
+        assert_eq!(two.pow(10u64), f64FieldElement::from(1024.0));
+        assert_eq!(
+            f64FieldElement::ONE.pow(BigInt::from(1u64 << 40)),
+            f64FieldElement::ONE
+        );
+        assert_eq!(two.pow(BigInt::from(-(1i64 << 40))), f64FieldElement::ZERO);
+        assert!(two.pow(BigUint::from(1u64 << 40)).0.is_infinite());
+    }
+}
@@ -0,0 +1,528 @@
+use crate::field::{
+    forward_ref_binop, impl_assign_ops, impl_pow_variants, impl_sum_product, parse_biguint,
+    reduce_exponent, signed_exponent, Field, FieldError, Inverse, PrimeField,
+};
+use num::{BigInt, BigUint, One, Zero};
+use num_traits::Pow;
+use rand_core::RngCore;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use subtle::{Choice, ConstantTimeEq};
+
+// secp256k1 prime p = 2^256 - 2^32 - 977, little-endian limbs.
+const P: [u64; 4] = [
+    0xFFFF_FFFE_FFFF_FC2F,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+];
+
+// 2^256 mod p
+const C: u64 = 0x1_0000_03D1;
+
+#[inline]
+pub(crate) fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline]
+pub(crate) fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+#[inline]
+pub(crate) fn mac(acc: u64, a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = acc as u128 + (a as u128) * (b as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+fn add_small(a: [u64; 4], b: u64) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut carry = b;
+    for (r, a) in r.iter_mut().zip(a) {
+        let (v, c) = adc(a, carry, 0);
+        *r = v;
+        carry = c;
+    }
+    (r, carry)
+}
+
+pub(crate) fn sub_limbs(a: [u64; 4], b: [u64; 4]) -> ([u64; 4], u64) {
+    let mut r = [0u64; 4];
+    let mut borrow = 0;
+    for ((r, a), b) in r.iter_mut().zip(a).zip(b) {
+        let (v, b) = sbb(a, b, borrow);
+        *r = v;
+        borrow = b;
+    }
+    (r, borrow)
+}
+
+fn reduce_once(a: [u64; 4]) -> [u64; 4] {
+    let (r, borrow) = sub_limbs(a, P);
+    if borrow == 0 {
+        r
+    } else {
+        a
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fe256([u64; 4]);
+
+impl Fe256 {
+    pub const ZERO: Self = Self([0; 4]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    pub fn modulus() -> BigUint {
+        Self::to_biguint_limbs(&P)
+    }
+
+    // Compared against p limb by limb, so the conversion never allocates.
+    pub fn from_biguint(value: &BigUint) -> Option<Self> {
+        if value.bits() > 256 {
+            return None;
+        }
+
+        let mut limbs = [0u64; 4];
+        for (limb, digit) in limbs.iter_mut().zip(value.iter_u64_digits()) {
+            *limb = digit;
+        }
+        (sub_limbs(limbs, P).1 != 0).then_some(Self(limbs))
+    }
+
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (chunk, limb) in out.chunks_exact_mut(8).zip(self.0.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+            *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        (sub_limbs(limbs, P).1 != 0).then_some(Self(limbs))
+    }
+
+    pub fn random(rng: &mut impl RngCore) -> Self {
+        loop {
+            let limbs = [
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+                rng.next_u64(),
+            ];
+            if sub_limbs(limbs, P).1 != 0 {
+                return Self(limbs);
+            }
+        }
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        Self::to_biguint_limbs(&self.0)
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    // p ≡ 3 (mod 4), so a square root, when there is one, is a^((p + 1)/4).
+    pub fn sqrt(&self) -> Option<Self> {
+        let root = self.pow(&((Self::modulus() + 1u64) >> 2));
+        (root * root == *self).then_some(root)
+    }
+
+    // Goes through a stack buffer, so the only allocation is the BigUint's own.
+    fn to_biguint_limbs(limbs: &[u64; 4]) -> BigUint {
+        let mut bytes = [0u8; 32];
+        for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    fn square(self) -> Self {
+        self * self
+    }
+
+    // Expects an exponent already reduced by reduce_exponent.
+    fn pow_reduced(self, exponent: &BigUint) -> Self {
+        let mut result = Self::ONE;
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result *= self;
+            }
+        }
+        result
+    }
+}
+
+impl Add for Fe256 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut r = [0u64; 4];
+        let mut carry = 0;
+        for ((r, a), b) in r.iter_mut().zip(self.0).zip(rhs.0) {
+            let (v, c) = adc(a, b, carry);
+            *r = v;
+            carry = c;
+        }
+        if carry != 0 {
+            r = add_small(r, C).0;
+        }
+        Self(reduce_once(r))
+    }
+}
+
+impl Sub for Fe256 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (r, borrow) = sub_limbs(self.0, rhs.0);
+        if borrow != 0 {
+            Self(sub_limbs(r, [C, 0, 0, 0]).0)
+        } else {
+            Self(r)
+        }
+    }
+}
+
+impl Neg for Fe256 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::ZERO - self
+    }
+}
+
+impl Mul for Fe256 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut t = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                let (v, c) = mac(t[i + j], self.0[i], rhs.0[j], carry);
+                t[i + j] = v;
+                carry = c;
+            }
+            t[i + 4] = carry;
+        }
+
+        // Fold the high half using 2^256 = C (mod p).
+        let mut r = [0u64; 4];
+        let mut carry = 0;
+        for (r, (lo, hi)) in r.iter_mut().zip(t[..4].iter().zip(&t[4..])) {
+            let (v, c) = mac(*lo, *hi, C, carry);
+            *r = v;
+            carry = c;
+        }
+
+        let (v, mut c) = mac(r[0], carry, C, 0);
+        r[0] = v;
+        for limb in r.iter_mut().skip(1) {
+            let (v, cc) = adc(*limb, c, 0);
+            *limb = v;
+            c = cc;
+        }
+        if c != 0 {
+            r = add_small(r, C).0;
+        }
+        Self(reduce_once(r))
+    }
+}
+
+forward_ref_binop!(Fe256, Add, add);
+forward_ref_binop!(Fe256, Sub, sub);
+forward_ref_binop!(Fe256, Mul, mul);
+forward_ref_binop!(Fe256, Div, div);
+impl_assign_ops!([] Fe256);
+impl_sum_product!([] Fe256);
+impl_pow_variants!([] Fe256);
+
+impl Inverse for Fe256 {
+    fn inverse(&self) -> Result<Self, FieldError> {
+        if self.is_zero() {
+            Err(FieldError::NotInvertible)
+        } else {
+            Ok(self.pow_reduced(&(Self::modulus() - 2u64)))
+        }
+    }
+}
+
+impl Div for Fe256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<'a> Pow<&'a BigInt> for Fe256 {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        self.pow(&signed_exponent(rhs, &Self::modulus()))
+    }
+}
+
+impl<'a> Pow<&'a BigUint> for Fe256 {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        self.pow_reduced(&reduce_exponent(rhs, &Self::modulus()))
+    }
+}
+
+impl From<i64> for Fe256 {
+    fn from(v: i64) -> Self {
+        let magnitude = Self([v.unsigned_abs(), 0, 0, 0]);
+        if v < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl Zero for Fe256 {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+}
+
+impl One for Fe256 {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl ConstantTimeEq for Fe256 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+// Fe256 is Copy, so it cannot wipe itself on drop; callers holding secrets zeroize explicitly.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Fe256 {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Display for Fe256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:064x}", self.to_biguint())
+    }
+}
+
+impl fmt::LowerHex for Fe256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.to_biguint(), f)
+    }
+}
+
+impl FromStr for Fe256 {
+    type Err = FieldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(parse_biguint(s)?)
+    }
+}
+
+impl TryFrom<BigUint> for Fe256 {
+    type Error = FieldError;
+
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        Self::from_biguint(&value).ok_or_else(|| FieldError::OutOfRange {
+            value: value.into(),
+            prime: Self::modulus(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Fe256 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Fe256 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        crate::field::parse_hex(&s)
+            .and_then(Self::try_from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Field for Fe256 {}
+
+impl PrimeField for Fe256 {
+    fn modulus() -> BigUint {
+        Fe256::modulus()
+    }
+
+    fn num_bytes() -> usize {
+        32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::rem_euclid;
+    use crate::field_axioms::{any_element, check_fermat_identity, check_field_axioms};
+    use proptest::prelude::*;
+
+    fn reference(limbs: [u64; 4]) -> (Fe256, BigUint) {
+        let value = Fe256::to_biguint_limbs(&limbs) % Fe256::modulus();
+        (Fe256::from_biguint(&value).unwrap(), value)
+    }
+
+    #[test]
+    fn edge_cases() {
+        let p = Fe256::modulus();
+        let max = Fe256::from_biguint(&(&p - 1u64)).unwrap();
+
+        assert_eq!(Fe256::from_biguint(&p), None);
+        assert_eq!(max + Fe256::from(1), Fe256::from(0));
+        assert_eq!(Fe256::from(0) - Fe256::from(1), max);
+        assert_eq!(max * max, Fe256::from(1));
+        assert_eq!(Fe256::from(-5).to_biguint(), &p - 5u64);
+        assert_eq!(Fe256::from(0).inverse(), Err(FieldError::NotInvertible));
+    }
+
+    #[test]
+    fn sqrt() {
+        let two = Fe256::from(2);
+        let root = (two * two).sqrt().unwrap();
+        assert!(root == two || root == -two);
+        assert!(Fe256::from(-2).is_odd() && !Fe256::from(-1).is_odd());
+        assert_eq!(Fe256::from(7).sqrt(), None);
+    }
+
+    #[test]
+    fn display_and_parse() {
+        let a: Fe256 = "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            a.to_string(),
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!(Fe256::from(7).to_string(), format!("{:0>64}", "7"));
+        assert_eq!(format!("{:x}", Fe256::from(255)), "ff");
+        assert_eq!("255".parse(), Ok(Fe256::from(255)));
+        assert_eq!(
+            Fe256::modulus().to_string().parse::<Fe256>(),
+            Err(FieldError::OutOfRange {
+                value: Fe256::modulus().into(),
+                prime: Fe256::modulus()
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let a = Fe256::from(-7);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, format!("\"{}\"", a));
+        assert_eq!(serde_json::from_str::<Fe256>(&json).unwrap(), a);
+
+        let p = format!("\"{:x}\"", Fe256::modulus());
+        assert!(serde_json::from_str::<Fe256>(&p).is_err());
+    }
+
+    #[test]
+    fn ct_eq() {
+        let a = Fe256::from(-1);
+        assert!(bool::from(a.ct_eq(&(Fe256::ZERO - Fe256::ONE))));
+        assert!(!bool::from(a.ct_eq(&Fe256::ONE)));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        use zeroize::Zeroize;
+
+        let mut a = Fe256::from(-1);
+        a.zeroize();
+        assert_eq!(a, Fe256::ZERO);
+    }
+
+    #[test]
+    fn bytes_be() {
+        let g: Fe256 = "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            .parse()
+            .unwrap();
+        let bytes = g.to_bytes_be();
+        assert_eq!(bytes[..2], [0x79, 0xbe]);
+        assert_eq!(bytes[30..], [0x17, 0x98]);
+        assert_eq!(bytes.to_vec(), g.to_biguint().to_bytes_be());
+        assert_eq!(Fe256::from_bytes_be(&bytes), Some(g));
+
+        let mut p = [0u8; 32];
+        p.copy_from_slice(&Fe256::modulus().to_bytes_be());
+        assert_eq!(Fe256::from_bytes_be(&p), None);
+        p[31] -= 1;
+        assert_eq!(Fe256::from_bytes_be(&p), Some(Fe256::from(-1)));
+    }
+
+    #[test]
+    fn random() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(256);
+        let a = Fe256::random(&mut rng);
+        let b = Fe256::random(&mut rng);
+        assert_ne!(a, b);
+        assert!(a.to_biguint() < Fe256::modulus());
+        assert_eq!(Fe256::from_biguint(&b.to_biguint()), Some(b));
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<Fe256>(a, b, c)?;
+            check_fermat_identity::<Fe256>(a, &Fe256::modulus())?;
+        }
+
+        #[test]
+        fn matches_biguint(a: [u64; 4], b: [u64; 4], e: i64) {
+            let p = Fe256::modulus();
+            let (fa, ba) = reference(a);
+            let (fb, bb) = reference(b);
+
+            prop_assert_eq!((fa + fb).to_biguint(), (&ba + &bb) % &p);
+            prop_assert_eq!((fa - fb).to_biguint(), (&ba + &p - &bb) % &p);
+            prop_assert_eq!((fa * fb).to_biguint(), (&ba * &bb) % &p);
+            prop_assert_eq!(
+                fa.pow(BigInt::from(e)).to_biguint(),
+                ba.modpow(&rem_euclid(&BigInt::from(e), &(&p - 1u64)), &p)
+            );
+            if bb != BigUint::from(0u64) {
+                prop_assert_eq!((fa / fb) * fb, fa);
+            }
+        }
+    }
+}
@@ -1,50 +1,331 @@
-use num::{BigInt, BigUint, Integer, One, Signed, ToPrimitive};
-use num_bigint::{Sign, ToBigInt};
+use crate::encode::biguint_to_be_bytes;
+use num::{BigInt, BigUint, Integer, One, Signed, ToPrimitive, Zero};
+use num_bigint::ToBigInt;
 use num_traits::Pow;
+use rand_core::RngCore;
+use std::convert::TryFrom;
+use std::fmt;
 use std::marker::PhantomData;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+pub use subtle::{Choice, ConstantTimeEq};
 
 pub trait Prime {
     fn get_prime() -> BigUint;
+
+    fn num_bytes() -> usize {
+        Self::get_prime().bits().div_ceil(8) as usize
+    }
+}
+
+// Primes of at most 256 bits, whose elements always fit the 32-byte encoding.
+pub trait Prime256: Prime {}
+
+// Caches each prime's Montgomery constants in its own static, so lookups take no lock.
+macro_rules! impl_montgomery_prime {
+    ($name: ident) => {
+        #[cfg(feature = "montgomery")]
+        impl $crate::montgomery::MontgomeryPrime for $name {
+            fn params() -> &'static $crate::montgomery::MontgomeryParams {
+                static PARAMS: std::sync::OnceLock<$crate::montgomery::MontgomeryParams> =
+                    std::sync::OnceLock::new();
+                PARAMS.get_or_init(|| {
+                    $crate::montgomery::MontgomeryParams::new(
+                        <$name as $crate::field::Prime>::get_prime(),
+                    )
+                })
+            }
+        }
+    };
+}
+
+// Finds each prime's quadratic non-residue once, for extension fields and twists.
+macro_rules! impl_non_residue_prime {
+    ($name: ident) => {
+        impl $crate::extension::NonResiduePrime for $name {
+            fn non_residue() -> Option<&'static num::BigUint> {
+                static NON_RESIDUE: std::sync::OnceLock<Option<num::BigUint>> =
+                    std::sync::OnceLock::new();
+                NON_RESIDUE
+                    .get_or_init(|| {
+                        $crate::extension::smallest_non_residue(
+                            &<$name as $crate::field::Prime>::get_prime(),
+                        )
+                    })
+                    .as_ref()
+            }
+        }
+    };
 }
 
+// Integer literals are checked at compile time. Larger primes are given as hex strings and
+// checked with Miller-Rabin the first time they are used.
 macro_rules! def_prime_struct {
+    ($name: ident, hex $value: literal) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name;
+
+        impl $crate::field::Prime for $name {
+            fn get_prime() -> num::BigUint {
+                static PRIME: std::sync::OnceLock<num::BigUint> = std::sync::OnceLock::new();
+                PRIME
+                    .get_or_init(|| $crate::field::checked_prime(stringify!($name), $value))
+                    .clone()
+            }
+        }
+
+        impl_montgomery_prime!($name);
+        impl_non_residue_prime!($name);
+    };
     ($name: ident, $value: literal) => {
+        const _: () = assert!(
+            $crate::field::is_prime_u64($value as u64),
+            concat!(
+                stringify!($name),
+                " = ",
+                stringify!($value),
+                " is not prime"
+            )
+        );
+
         #[derive(Debug, Clone, PartialEq)]
         pub struct $name;
 
-        impl Prime for $name {
-            fn get_prime() -> BigUint {
-                BigUint::from($value as u64)
+        impl $crate::field::Prime for $name {
+            fn get_prime() -> num::BigUint {
+                num::BigUint::from($value as u64)
             }
         }
+
+        impl $crate::small_field::SmallPrime for $name {
+            const MODULUS: u64 = $value as u64;
+        }
+
+        impl $crate::field::Prime256 for $name {}
+
+        impl_montgomery_prime!($name);
+        impl_non_residue_prime!($name);
     };
 }
 
+const MILLER_RABIN_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+const fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+pub(crate) const fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+// Deterministic for every u64 with the first twelve primes as bases.
+pub const fn is_prime_u64(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < MILLER_RABIN_BASES.len() {
+        let p = MILLER_RABIN_BASES[i];
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+        i += 1;
+    }
+
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+
+    let mut i = 0;
+    'bases: while i < MILLER_RABIN_BASES.len() {
+        let mut x = pow_mod(MILLER_RABIN_BASES[i], d, n);
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+// Miller-Rabin with fixed bases: exact below 2^64, overwhelmingly likely above.
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    if let Some(n) = n.to_u64() {
+        return is_prime_u64(n);
+    }
+    if n.is_even() {
+        return false;
+    }
+
+    let n_minus_one = n - 1u64;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+
+    MILLER_RABIN_BASES.iter().all(|base| {
+        let mut x = BigUint::from(*base).modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            return true;
+        }
+        for _ in 1..s {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+pub(crate) fn checked_prime(name: &str, hex: &str) -> BigUint {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+    let prime = BigUint::parse_bytes(digits.as_bytes(), 16)
+        .unwrap_or_else(|| panic!("{}: {:?} is not a hex number", name, hex));
+    assert!(
+        is_probable_prime(&prime),
+        "{} = 0x{} is not prime",
+        name,
+        digits
+    );
+    prime
+}
+
 def_prime_struct!(Prime13, 13);
 def_prime_struct!(Prime19, 19);
 def_prime_struct!(Prime29, 29);
 def_prime_struct!(Prime223, 223);
+def_prime_struct!(
+    Secp256k1Prime,
+    hex "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+);
+def_prime_struct!(
+    Secp256k1Order,
+    hex "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+);
+def_prime_struct!(
+    P256Prime,
+    hex "0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+);
+def_prime_struct!(
+    Curve25519Prime,
+    hex "0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed"
+);
+
+impl Prime256 for Secp256k1Prime {}
+impl Prime256 for Secp256k1Order {}
+impl Prime256 for P256Prime {}
+impl Prime256 for Curve25519Prime {}
+
+/// Integers modulo the secp256k1 group order N: private keys, nonces and signature
+/// values. Coordinates live modulo P instead, and the two do not mix:
+///
+/// ```compile_fail
+/// use encriptions::field::{FiniteFieldElement, ScalarFieldElement, Secp256k1Prime};
+///
+/// let _ = ScalarFieldElement::from(1) + FiniteFieldElement::<Secp256k1Prime>::from(1);
+/// ```
+pub type ScalarFieldElement = FiniteFieldElement<Secp256k1Order>;
 
 pub trait Field:
-    Add<Output = <Self as Field>::Output>
-    + Sub<Output = <Self as Field>::Output>
-    + Mul<Output = <Self as Field>::Output>
-    + Div<Output = <Self as Field>::Output>
+    Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Pow<BigInt, Output = Self>
     + Sized
-    + Pow<BigInt, Output = <Self as Field>::Output>
+    + Clone
     + PartialEq
-    + From<<Self as Field>::Output>
-    + From<Self>
     + From<i64>
 {
-    type Output: Field;
+}
+
+// Fields of prime order, so generic code can size encodings and reduce values without
+// hardcoding the modulus.
+pub trait PrimeField: Field {
+    fn modulus() -> BigUint;
+
+    fn num_bytes() -> usize {
+        Self::modulus().bits().div_ceil(8) as usize
+    }
+}
+
+pub trait FieldRef<'a, T: 'a>:
+    Add<&'a T, Output = T> + Sub<&'a T, Output = T> + Mul<&'a T, Output = T> + Div<&'a T, Output = T>
+{
+}
+
+impl<'a, T: 'a, R> FieldRef<'a, T> for R where
+    R: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Div<&'a T, Output = T>
+{
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldError {
+    NotInvertible,
+    InvalidNumber(String),
+    OutOfRange { value: BigInt, prime: BigUint },
+    NoNonResidue,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::NotInvertible => write!(f, "element has no multiplicative inverse"),
+            FieldError::InvalidNumber(s) => write!(f, "invalid field element: {:?}", s),
+            FieldError::OutOfRange { value, prime } => {
+                write!(f, "{} is out of range for prime {}", value, prime)
+            }
+            FieldError::NoNonResidue => write!(f, "F_2 has no quadratic non-residue"),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+// Accepts decimal or 0x-prefixed hex.
+pub(crate) fn parse_biguint(s: &str) -> Result<BigUint, FieldError> {
+    let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+        None => BigUint::parse_bytes(s.as_bytes(), 10),
+    };
+    parsed.ok_or_else(|| FieldError::InvalidNumber(s.to_string()))
+}
+
+pub trait Inverse: Sized {
+    fn inverse(&self) -> Result<Self, FieldError>;
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FiniteFieldElement<P: Prime>(BigUint, PhantomData<P>);
 
 impl<P: Prime> FiniteFieldElement<P> {
+    // BigUint cannot hold a non-zero value in a const, so use One::one() instead of ONE.
+    pub const ZERO: Self = Self(BigUint::ZERO, PhantomData);
+
     pub fn new(value: BigUint) -> Option<Self> {
         if value >= P::get_prime() {
             None
@@ -53,12 +334,116 @@ impl<P: Prime> FiniteFieldElement<P> {
         }
     }
 
+    pub fn from_biguint_reduced(value: &BigUint) -> Self {
+        Self(value % P::get_prime(), PhantomData)
+    }
+
     pub fn new_from_u64(value: u64) -> Option<Self> {
         Self::new(BigUint::from(value))
     }
+
+    pub fn value(&self) -> &BigUint {
+        &self.0
+    }
+
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Option<Self> {
+        Self::new(BigUint::from_bytes_be(bytes))
+    }
+
+    // Rejection sampling over the prime's bit length keeps the distribution uniform.
+    pub fn random(rng: &mut impl RngCore) -> Self {
+        let prime = P::get_prime();
+        let bits = prime.bits();
+        let mut bytes = vec![0u8; P::num_bytes()];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            bytes[0] &= 0xff >> (bytes.len() as u64 * 8 - bits);
+            let candidate = BigUint::from_bytes_be(&bytes);
+            if candidate < prime {
+                return Self(candidate, PhantomData);
+            }
+        }
+    }
+
+    pub fn legendre_symbol(&self) -> i8 {
+        if self.0.is_zero() {
+            return 0;
+        }
+
+        let prime = P::get_prime();
+        if self.0.modpow(&((&prime - 1u64) >> 1), &prime).is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.legendre_symbol() >= 0
+    }
+
+    pub fn sqrt(&self) -> Option<Self> {
+        if !self.is_square() {
+            return None;
+        }
+
+        let prime = P::get_prime();
+        if self.0.is_zero() || prime == BigUint::from(2u64) {
+            return Some(Self(self.0.clone(), PhantomData));
+        }
+
+        let root = if (&prime % 4u64) == BigUint::from(3u64) {
+            self.0.modpow(&((&prime + 1u64) / 4u64), &prime)
+        } else {
+            tonelli_shanks(&self.0, &prime)?
+        };
+
+        Some(Self(root, PhantomData))
+    }
 }
 
-impl<P: Prime> Add for FiniteFieldElement<P> {
+impl<P: Prime256> FiniteFieldElement<P> {
+    pub fn to_bytes_be(&self) -> [u8; 32] {
+        biguint_to_be_bytes(&self.0).expect("a Prime256 element fits in 32 bytes")
+    }
+}
+
+fn tonelli_shanks(n: &BigUint, prime: &BigUint) -> Option<BigUint> {
+    let p_minus_one = prime - 1u64;
+    let s = p_minus_one.trailing_zeros()?;
+    let q = &p_minus_one >> s;
+
+    let half = &p_minus_one >> 1;
+    let z = num::range(BigUint::from(2u64), prime.clone())
+        .find(|z| z.modpow(&half, prime) == p_minus_one)?;
+
+    let mut m = s;
+    let mut c = z.modpow(&q, prime);
+    let mut t = n.modpow(&q, prime);
+    let mut r = n.modpow(&((&q + 1u64) >> 1), prime);
+
+    while !t.is_one() {
+        let mut i = 0;
+        let mut t2i = t.clone();
+        while !t2i.is_one() {
+            t2i = (&t2i * &t2i) % prime;
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), prime);
+        m = i;
+        c = (&b * &b) % prime;
+        t = (&t * &c) % prime;
+        r = (&r * &b) % prime;
+    }
+
+    Some(r)
+}
+
+impl<'a, P: Prime> Add<&'a FiniteFieldElement<P>> for &'a FiniteFieldElement<P> {
     type Output = FiniteFieldElement<P>;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -66,11 +451,45 @@ impl<P: Prime> Add for FiniteFieldElement<P> {
     }
 }
 
+impl<'a, P: Prime> Sub<&'a FiniteFieldElement<P>> for &'a FiniteFieldElement<P> {
+    type Output = FiniteFieldElement<P>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let prime = P::get_prime();
+        FiniteFieldElement((&self.0 + &prime - &rhs.0) % prime, PhantomData)
+    }
+}
+
+impl<'a, P: Prime> Mul<&'a FiniteFieldElement<P>> for &'a FiniteFieldElement<P> {
+    type Output = FiniteFieldElement<P>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        FiniteFieldElement((&self.0 * &rhs.0) % P::get_prime(), PhantomData)
+    }
+}
+
+impl<'a, P: Prime> Div<&'a FiniteFieldElement<P>> for &'a FiniteFieldElement<P> {
+    type Output = FiniteFieldElement<P>;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * &rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<P: Prime> Add for FiniteFieldElement<P> {
+    type Output = FiniteFieldElement<P>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
 impl<P: Prime> Sub for FiniteFieldElement<P> {
     type Output = FiniteFieldElement<P>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        FiniteFieldElement((&self.0 + (-rhs).0) % P::get_prime(), PhantomData)
+        &self - &rhs
     }
 }
 
@@ -78,7 +497,7 @@ impl<P: Prime> Mul for FiniteFieldElement<P> {
     type Output = FiniteFieldElement<P>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        FiniteFieldElement((&self.0 * &rhs.0) % P::get_prime(), PhantomData)
+        &self * &rhs
     }
 }
 
@@ -97,105 +516,368 @@ impl<P: Prime> Div for FiniteFieldElement<P> {
     type Output = FiniteFieldElement<P>;
 
     fn div(self, rhs: Self) -> Self::Output {
-        self * rhs.pow(P::get_prime().to_bigint().unwrap() - &BigInt::from(2u64))
+        &self / &rhs
+    }
+}
+
+impl<P: Prime> Inverse for FiniteFieldElement<P> {
+    fn inverse(&self) -> Result<Self, FieldError> {
+        let prime = P::get_prime();
+        let gcd = self
+            .0
+            .to_bigint()
+            .unwrap()
+            .extended_gcd(&prime.to_bigint().unwrap());
+
+        if gcd.gcd.is_one() {
+            Ok(Self(rem_euclid(&gcd.x, &prime), PhantomData))
+        } else {
+            Err(FieldError::NotInvertible)
+        }
     }
 }
 
-impl<P: Prime> Pow<BigInt> for FiniteFieldElement<P> {
+impl<'a, P: Prime> Pow<&'a BigUint> for FiniteFieldElement<P> {
     type Output = FiniteFieldElement<P>;
 
-    fn pow(self, rhs: BigInt) -> Self::Output {
-        let exponent = rem_euclid(&rhs, &(P::get_prime() - BigUint::one()));
-        FiniteFieldElement(self.0.modpow(&exponent, &P::get_prime()), PhantomData)
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        FiniteFieldElement(self.0.modpow(rhs, &P::get_prime()), PhantomData)
+    }
+}
+
+impl<'a, P: Prime> Pow<&'a BigInt> for FiniteFieldElement<P> {
+    type Output = FiniteFieldElement<P>;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        self.pow(&signed_exponent(rhs, &P::get_prime()))
     }
 }
 
 impl<P: Prime> From<i64> for FiniteFieldElement<P> {
     fn from(v: i64) -> Self {
-        Self::new(rem_euclid(&v.to_bigint().unwrap(), &P::get_prime())).unwrap()
+        Self(rem_euclid(&BigInt::from(v), &P::get_prime()), PhantomData)
+    }
+}
+
+impl<P: Prime> Zero for FiniteFieldElement<P> {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
     }
 }
 
-fn rem_euclid(a: &BigInt, b: &BigUint) -> BigUint {
-    let sign = a.sign();
+impl<P: Prime> One for FiniteFieldElement<P> {
+    fn one() -> Self {
+        Self(BigUint::one() % P::get_prime(), PhantomData)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn parse_hex(s: &str) -> Result<BigUint, FieldError> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    BigUint::parse_bytes(digits.as_bytes(), 16)
+        .ok_or_else(|| FieldError::InvalidNumber(s.to_string()))
+}
+
+#[cfg(feature = "serde")]
+impl<P: Prime> serde::Serialize for FiniteFieldElement<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let width = P::get_prime().bits().div_ceil(4) as usize;
+        serializer.serialize_str(&format!("{:0width$x}", self.0, width = width))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: Prime> serde::Deserialize<'de> for FiniteFieldElement<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        parse_hex(&s)
+            .and_then(Self::try_from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+// Compares every bit of the prime's encoded width, so the number of steps is fixed by the prime
+// rather than by either value, and no buffer is built for the values. BigUint stores only its
+// significant digits, though, and `bit` checks whether the index falls among them; only the
+// accumulated comparison itself is branch-free.
+impl<P: Prime> ConstantTimeEq for FiniteFieldElement<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let bits = P::num_bytes() as u64 * 8;
+        (0..bits).fold(Choice::from(1), |acc, i| {
+            acc & (self.0.bit(i) as u8).ct_eq(&(other.0.bit(i) as u8))
+        })
+    }
+}
 
-    match sign {
-        Sign::Minus => {
-            let la = a.abs().to_biguint().unwrap();
-            let x = la.div_floor(b);
-            let x: BigUint = b.mul(&(x + (1u64)));
-            x.sub(a.abs().to_biguint().unwrap()).rem(b)
+#[cfg(feature = "zeroize")]
+impl<P: Prime> zeroize::Zeroize for FiniteFieldElement<P> {
+    // Clearing from the lowest bit up zeroes every limb in place before BigUint trims its
+    // length, so no digit survives in the released buffer. Temporaries created by the
+    // arithmetic itself are not covered.
+    fn zeroize(&mut self) {
+        for i in 0..self.0.bits() {
+            self.0.set_bit(i, false);
         }
-        _ => a.abs().to_biguint().unwrap().rem(b),
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
     }
 }
 
-impl<'a, P: Prime + PartialEq> Field for FiniteFieldElement<P> {
-    type Output = FiniteFieldElement<P>;
+#[cfg(feature = "zeroize")]
+impl<P: Prime> Drop for FiniteFieldElement<P> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct f64FieldElement(f64);
+#[cfg(feature = "zeroize")]
+impl<P: Prime> zeroize::ZeroizeOnDrop for FiniteFieldElement<P> {}
 
-impl Add for f64FieldElement {
-    type Output = Self;
+impl<P: Prime> fmt::Display for FiniteFieldElement<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Every 32-byte field prints as fixed-width hex, even if its prime is below 2^255.
+        if P::num_bytes() == 32 {
+            write!(f, "{:064x}", self.0)
+        } else {
+            write!(f, "FieldElement_{}({})", P::get_prime(), self.0)
+        }
+    }
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0)
+impl<P: Prime> fmt::LowerHex for FiniteFieldElement<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
     }
 }
 
-impl Sub for f64FieldElement {
-    type Output = Self;
+impl<P: Prime> FromStr for FiniteFieldElement<P> {
+    type Err = FieldError;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(parse_biguint(s)?)
     }
 }
 
-impl Mul for f64FieldElement {
-    type Output = Self;
+impl<P: Prime> TryFrom<BigUint> for FiniteFieldElement<P> {
+    type Error = FieldError;
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        Self(self.0 * rhs.0)
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        let prime = P::get_prime();
+        if value >= prime {
+            Err(FieldError::OutOfRange {
+                value: value.into(),
+                prime,
+            })
+        } else {
+            Ok(Self(value, PhantomData))
+        }
     }
 }
 
-impl Div for f64FieldElement {
-    type Output = Self;
+impl<P: Prime> TryFrom<BigInt> for FiniteFieldElement<P> {
+    type Error = FieldError;
 
-    fn div(self, rhs: Self) -> Self::Output {
-        Self(self.0 / rhs.0)
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        match value.to_biguint() {
+            Some(v) => Self::try_from(v),
+            None => Err(FieldError::OutOfRange {
+                value,
+                prime: P::get_prime(),
+            }),
+        }
     }
 }
 
-impl Pow<BigInt> for f64FieldElement {
-    type Output = <Self as Field>::Output;
+impl<P: Prime> TryFrom<u64> for FiniteFieldElement<P> {
+    type Error = FieldError;
 
-    fn pow(self, rhs: BigInt) -> Self::Output {
-        Self(self.0.powi(rhs.to_i32().unwrap()))
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::try_from(BigUint::from(value))
     }
 }
 
-impl From<f64> for f64FieldElement {
-    fn from(f: f64) -> Self {
-        Self(f)
+// Montgomery's trick: one inversion and 3n multiplications. Zeros are left untouched.
+pub fn batch_invert<F: Field + Inverse + Zero>(elements: &mut [F])
+where
+    for<'a> &'a F: FieldRef<'a, F>,
+{
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut acc = F::from(1);
+    for e in elements.iter() {
+        prefix.push(acc.clone());
+        if !e.is_zero() {
+            acc = &acc * e;
+        }
+    }
+
+    let mut inv = acc
+        .inverse()
+        .expect("product of non-zero elements is invertible");
+    for (e, p) in elements.iter_mut().zip(prefix).rev() {
+        if e.is_zero() {
+            continue;
+        }
+        let next = &inv * e;
+        *e = &inv * &p;
+        inv = next;
     }
 }
 
-impl From<i64> for f64FieldElement {
-    fn from(v: i64) -> Self {
-        Self(v.to_f64().unwrap())
+// The least non-negative residue of `a` modulo `b`: a single division on |a|, then one
+// subtraction when `a` is negative.
+pub(crate) fn rem_euclid(a: &BigInt, b: &BigUint) -> BigUint {
+    let r = a.magnitude() % b;
+    if a.is_negative() && !r.is_zero() {
+        b - r
+    } else {
+        r
     }
 }
 
-impl<'a> Field for f64FieldElement {
-    type Output = Self;
+// Reduces a positive exponent into 1..=p-1 rather than 0..p-1: the two agree for a non-zero base
+// by Fermat, but only the former keeps 0^(p-1) at zero instead of turning it into 0^0.
+pub(crate) fn reduce_exponent(exponent: &BigUint, prime: &BigUint) -> BigUint {
+    if exponent.is_zero() {
+        return BigUint::zero();
+    }
+    (exponent - 1u64) % (prime - 1u64) + 1u64
+}
+
+// Non-negative exponents pass through unchanged; a negative k becomes k mod p-1, which gives the
+// inverse power for any non-zero base.
+pub(crate) fn signed_exponent(exponent: &BigInt, prime: &BigUint) -> BigUint {
+    exponent
+        .to_biguint()
+        .unwrap_or_else(|| rem_euclid(exponent, &(prime - 1u64)))
 }
 
+impl<P: Prime + Clone + PartialEq> Field for FiniteFieldElement<P> {}
+
+impl<P: Prime + Clone + PartialEq> PrimeField for FiniteFieldElement<P> {
+    fn modulus() -> BigUint {
+        P::get_prime()
+    }
+
+    fn num_bytes() -> usize {
+        P::num_bytes()
+    }
+}
+
+macro_rules! forward_ref_binop {
+    ([$($g:tt)*] $t:ty, $imp:ident, $method:ident) => {
+        impl<'a, $($g)*> $imp<&'a $t> for &'a $t {
+            type Output = $t;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+    };
+    ($t:ty, $imp:ident, $method:ident) => {
+        forward_ref_binop!([] $t, $imp, $method);
+    };
+}
+
+pub(crate) use forward_ref_binop;
+
+macro_rules! impl_assign_ops {
+    ([$($g:tt)*] $t:ty) => {
+        impl_assign_ops!(@op [$($g)*] $t, AddAssign, add_assign, +);
+        impl_assign_ops!(@op [$($g)*] $t, SubAssign, sub_assign, -);
+        impl_assign_ops!(@op [$($g)*] $t, MulAssign, mul_assign, *);
+        impl_assign_ops!(@op [$($g)*] $t, DivAssign, div_assign, /);
+    };
+    (@op [$($g:tt)*] $t:ty, $imp:ident, $method:ident, $op:tt) => {
+        impl<$($g)*> $imp for $t {
+            fn $method(&mut self, rhs: Self) {
+                *self = &*self $op &rhs;
+            }
+        }
+
+        impl<'a, $($g)*> $imp<&'a $t> for $t {
+            fn $method(&mut self, rhs: &'a $t) {
+                *self = &*self $op rhs;
+            }
+        }
+    };
+}
+
+pub(crate) use impl_assign_ops;
+
+macro_rules! impl_sum_product {
+    ([$($g:tt)*] $t:ty) => {
+        impl<$($g)*> std::iter::Sum for $t {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(num::Zero::zero(), |acc, x| &acc + &x)
+            }
+        }
+
+        impl<'a, $($g)*> std::iter::Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> Self {
+                iter.fold(num::Zero::zero(), |acc, x| &acc + x)
+            }
+        }
+
+        impl<$($g)*> std::iter::Product for $t {
+            fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(num::One::one(), |acc, x| &acc * &x)
+            }
+        }
+
+        impl<'a, $($g)*> std::iter::Product<&'a $t> for $t {
+            fn product<I: Iterator<Item = &'a $t>>(iter: I) -> Self {
+                iter.fold(num::One::one(), |acc, x| &acc * x)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_sum_product;
+
+// Derives the owned and u64 exponent forms from the `Pow<&BigInt>` and `Pow<&BigUint>`
+// impls each type writes by hand.
+macro_rules! impl_pow_variants {
+    ([$($g:tt)*] $t:ty) => {
+        impl<$($g)*> num_traits::Pow<num::BigInt> for $t {
+            type Output = $t;
+
+            fn pow(self, rhs: num::BigInt) -> $t {
+                num_traits::Pow::pow(self, &rhs)
+            }
+        }
+
+        impl<$($g)*> num_traits::Pow<num::BigUint> for $t {
+            type Output = $t;
+
+            fn pow(self, rhs: num::BigUint) -> $t {
+                num_traits::Pow::pow(self, &rhs)
+            }
+        }
+
+        impl<$($g)*> num_traits::Pow<u64> for $t {
+            type Output = $t;
+
+            fn pow(self, rhs: u64) -> $t {
+                num_traits::Pow::pow(self, &num::BigUint::from(rhs))
+            }
+        }
+    };
+}
+
+pub(crate) use impl_pow_variants;
+
+impl_assign_ops!([P: Prime] FiniteFieldElement<P>);
+impl_sum_product!([P: Prime] FiniteFieldElement<P>);
+impl_pow_variants!([P: Prime] FiniteFieldElement<P>);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field_axioms::{any_element, check_fermat_identity, check_field_axioms};
+    use proptest::prelude::*;
 
     #[test]
     fn add_test() {
@@ -207,6 +889,53 @@ mod tests {
         assert_eq!(a + c, FiniteFieldElement::new_from_u64(0).unwrap());
     }
 
+    #[test]
+    fn modulus_and_num_bytes() {
+        use crate::fe256::Fe256;
+        use crate::small_field::SmallPrimeField;
+
+        fn encoded_len<F: PrimeField>() -> usize {
+            F::num_bytes()
+        }
+
+        assert_eq!(Prime13::num_bytes(), 1);
+        assert_eq!(Secp256k1Prime::num_bytes(), 32);
+        assert_eq!(encoded_len::<FiniteFieldElement<Prime223>>(), 1);
+        assert_eq!(encoded_len::<ScalarFieldElement>(), 32);
+        assert_eq!(encoded_len::<Fe256>(), 32);
+        assert_eq!(encoded_len::<SmallPrimeField<Prime223>>(), 1);
+        assert_eq!(
+            <Fe256 as PrimeField>::modulus(),
+            FiniteFieldElement::<Secp256k1Prime>::modulus()
+        );
+        assert_eq!(SmallPrimeField::<Prime29>::modulus(), BigUint::from(29u64));
+    }
+
+    #[test]
+    fn rem_euclid_edges() {
+        let p = BigUint::from(13u64);
+        let r = |a: BigInt| rem_euclid(&a, &p);
+
+        assert_eq!(r(BigInt::from(0)), BigUint::zero());
+        assert_eq!(r(BigInt::from(-13)), BigUint::zero());
+        assert_eq!(r(BigInt::from(13)), BigUint::zero());
+        assert_eq!(r(BigInt::from(-1)), BigUint::from(12u64));
+        assert_eq!(r(BigInt::from(-14)), BigUint::from(12u64));
+        assert_eq!(r(BigInt::from(27)), BigUint::from(1u64));
+
+        // |a| much larger than p: -(13 * 2^200 + 5) ≡ 8.
+        let big: BigInt = BigInt::from(13) * BigInt::from(2).pow(200u32) + 5;
+        assert_eq!(r(-big.clone()), BigUint::from(8u64));
+        assert_eq!(r(big), BigUint::from(5u64));
+
+        let secp = Secp256k1Prime::get_prime();
+        assert_eq!(
+            rem_euclid(&-BigInt::from(secp.clone()), &secp),
+            BigUint::zero()
+        );
+        assert_eq!(rem_euclid(&BigInt::from(-1), &secp), &secp - 1u64);
+    }
+
     #[test]
     fn add_1_5_1() {
         let a: FiniteFieldElement<Prime13> = FiniteFieldElement::new_from_u64(7).unwrap();
@@ -246,6 +975,377 @@ mod tests {
         assert_eq!(a / b, FiniteFieldElement::new_from_u64(9).unwrap());
     }
 
+    #[test]
+    fn inverse_test() {
+        for v in 1..223 {
+            let a: FiniteFieldElement<Prime223> = FiniteFieldElement::new_from_u64(v).unwrap();
+            assert_eq!(
+                a.clone() * a.inverse().unwrap(),
+                FiniteFieldElement::new_from_u64(1).unwrap()
+            );
+        }
+
+        let zero: FiniteFieldElement<Prime223> = FiniteFieldElement::new_from_u64(0).unwrap();
+        assert_eq!(zero.inverse(), Err(FieldError::NotInvertible));
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero() {
+        let a: FiniteFieldElement<Prime19> = FiniteFieldElement::new_from_u64(2).unwrap();
+        let _ = a / FiniteFieldElement::new_from_u64(0).unwrap();
+    }
+
+    #[test]
+    fn zero_one_test() {
+        let a: FiniteFieldElement<Prime29> = FiniteFieldElement::new_from_u64(17).unwrap();
+
+        assert_eq!(a.clone() + FiniteFieldElement::ZERO, a);
+        assert_eq!(a.clone() * FiniteFieldElement::one(), a);
+        assert!(FiniteFieldElement::<Prime29>::zero().is_zero());
+        assert!(!a.is_zero());
+        assert_eq!(
+            FiniteFieldElement::<Prime29>::one(),
+            FiniteFieldElement::new_from_u64(1).unwrap()
+        );
+    }
+
+    fn generic_neg<F: Field>(a: F) -> F {
+        -a
+    }
+
+    #[test]
+    fn neg_test() {
+        let a: FiniteFieldElement<Prime19> = FiniteFieldElement::new_from_u64(5).unwrap();
+        assert_eq!(
+            generic_neg(a),
+            FiniteFieldElement::new_from_u64(14).unwrap()
+        );
+        assert_eq!(
+            generic_neg(FiniteFieldElement::<Prime19>::ZERO),
+            FiniteFieldElement::ZERO
+        );
+    }
+
+    def_prime_struct!(
+        Secp256k1PrimeTimesThree,
+        hex "0x2fffffffffffffffffffffffffffffffffffffffffffffffffffffffcfffff48d"
+    );
+
+    #[test]
+    fn primality_test() {
+        let primes: Vec<u64> = (0..100).filter(|n| is_prime_u64(*n)).collect();
+        assert_eq!(
+            primes,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+        assert!(is_prime_u64(2305843009213693951));
+        assert!(is_prime_u64(u64::MAX - 58));
+        // A strong pseudoprime to bases 2, 3, 5 and 7.
+        assert!(!is_prime_u64(3215031751));
+        assert!(!is_prime_u64(u64::MAX));
+
+        assert!(is_probable_prime(&Secp256k1Prime::get_prime()));
+        assert!(is_probable_prime(&Secp256k1Order::get_prime()));
+        assert!(!is_probable_prime(&(Secp256k1Prime::get_prime() * 3u64)));
+        assert!(is_probable_prime(&((BigUint::one() << 127) - 1u64)));
+        assert!(!is_probable_prime(&((BigUint::one() << 128) + 1u64)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Secp256k1PrimeTimesThree = 0x2ff")]
+    fn composite_hex_prime_panics() {
+        let _ = Secp256k1PrimeTimesThree::get_prime();
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms_prime13(a in any_element::<FiniteFieldElement<Prime13>>(), b in any_element(), c in any_element()) {
+            check_field_axioms::<FiniteFieldElement<Prime13>>(a.clone(), b, c)?;
+            check_fermat_identity::<FiniteFieldElement<Prime13>>(a, &Prime13::get_prime())?;
+        }
+
+        #[test]
+        fn field_axioms_prime19(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<FiniteFieldElement<Prime19>>(a, b, c)?;
+        }
+
+        #[test]
+        fn field_axioms_prime29(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<FiniteFieldElement<Prime29>>(a, b, c)?;
+        }
+
+        #[test]
+        fn field_axioms_prime223(a in any_element::<FiniteFieldElement<Prime223>>(), b in any_element(), c in any_element()) {
+            check_field_axioms::<FiniteFieldElement<Prime223>>(a.clone(), b, c)?;
+            check_fermat_identity::<FiniteFieldElement<Prime223>>(a, &Prime223::get_prime())?;
+        }
+
+        #[test]
+        fn field_axioms_secp256k1(a in any_element::<FiniteFieldElement<Secp256k1Prime>>(), b in any_element(), c in any_element()) {
+            check_field_axioms::<FiniteFieldElement<Secp256k1Prime>>(a.clone(), b, c)?;
+            check_fermat_identity::<FiniteFieldElement<Secp256k1Prime>>(a, &Secp256k1Prime::get_prime())?;
+        }
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ref_ops_test() {
+        let a: FiniteFieldElement<Prime19> = FiniteFieldElement::new_from_u64(2).unwrap();
+        let b = FiniteFieldElement::new_from_u64(7).unwrap();
+
+        assert_eq!(&a + &b, a.clone() + b.clone());
+        assert_eq!(&a - &b, a.clone() - b.clone());
+        assert_eq!(&b - &a, b.clone() - a.clone());
+        assert_eq!(&a * &b, a.clone() * b.clone());
+        assert_eq!(&a / &b, a / b);
+    }
+
+    #[test]
+    fn assign_ops_test() {
+        let x: FiniteFieldElement<Prime223> = FiniteFieldElement::new_from_u64(10).unwrap();
+        let mut acc = FiniteFieldElement::ZERO;
+        for c in [3, 0, 2, 1] {
+            acc *= &x;
+            acc += FiniteFieldElement::from(c);
+        }
+        assert_eq!(acc, FiniteFieldElement::from(3021 % 223));
+
+        acc -= FiniteFieldElement::from(12);
+        acc /= FiniteFieldElement::from(10);
+        assert_eq!(acc, FiniteFieldElement::from(11));
+    }
+
+    #[test]
+    fn sum_product_test() {
+        let coeffs: Vec<FiniteFieldElement<Prime13>> =
+            (1..=5).map(FiniteFieldElement::from).collect();
+
+        let sum: FiniteFieldElement<Prime13> = coeffs.iter().sum();
+        assert_eq!(sum, FiniteFieldElement::from(15));
+        let product: FiniteFieldElement<Prime13> = coeffs.iter().cloned().product();
+        assert_eq!(product, FiniteFieldElement::from(120));
+
+        let empty: Vec<FiniteFieldElement<Prime13>> = vec![];
+        assert_eq!(
+            empty.iter().sum::<FiniteFieldElement<Prime13>>(),
+            FiniteFieldElement::ZERO
+        );
+        assert_eq!(
+            empty.into_iter().product::<FiniteFieldElement<Prime13>>(),
+            FiniteFieldElement::from(1)
+        );
+    }
+
+    #[test]
+    fn display_and_parse() {
+        let a: FiniteFieldElement<Prime223> = "170".parse().unwrap();
+        assert_eq!(a.to_string(), "FieldElement_223(170)");
+        assert_eq!(format!("{:x}", a), "aa");
+        assert_eq!(format!("{:#06x}", a), "0x00aa");
+        assert_eq!("0xaa".parse(), Ok(a));
+
+        let b: FiniteFieldElement<Secp256k1Prime> = "0x1f".parse().unwrap();
+        assert_eq!(b.to_string(), format!("{:0>64}", "1f"));
+        assert_eq!(format!("0x{}", b).parse(), Ok(b));
+
+        // 2^255 - 19 has 255 significant bits but the same 32-byte width.
+        let c = FiniteFieldElement::<Curve25519Prime>::from(31);
+        assert_eq!(c.to_string(), format!("{:0>64}", "1f"));
+
+        assert_eq!(
+            "223".parse::<FiniteFieldElement<Prime223>>(),
+            Err(FieldError::OutOfRange {
+                value: BigInt::from(223),
+                prime: BigUint::from(223u64)
+            })
+        );
+        for bad in ["", "0x", "-1", "12a"] {
+            assert_eq!(
+                bad.parse::<FiniteFieldElement<Prime223>>(),
+                Err(FieldError::InvalidNumber(bad.to_string()))
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let a: FiniteFieldElement<Prime223> = FiniteFieldElement::from(10);
+        assert_eq!(serde_json::to_string(&a).unwrap(), "\"0a\"");
+        assert_eq!(
+            serde_json::from_str::<FiniteFieldElement<Prime223>>("\"0a\"").unwrap(),
+            a
+        );
+        assert_eq!(
+            serde_json::from_str::<FiniteFieldElement<Prime223>>("\"0xa\"").unwrap(),
+            a
+        );
+
+        let b: FiniteFieldElement<Secp256k1Prime> = FiniteFieldElement::from(-1);
+        let json = serde_json::to_string(&b).unwrap();
+        assert_eq!(json.len(), 66);
+        assert_eq!(
+            serde_json::from_str::<FiniteFieldElement<Secp256k1Prime>>(&json).unwrap(),
+            b
+        );
+
+        for bad in ["\"df\"", "\"zz\"", "\"\"", "170"] {
+            assert!(serde_json::from_str::<FiniteFieldElement<Prime223>>(bad).is_err());
+        }
+    }
+
+    #[test]
+    fn random_test() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(13);
+        let mut seen = [false; 13];
+        for _ in 0..500 {
+            let a = FiniteFieldElement::<Prime13>::random(&mut rng);
+            seen[a.value().to_usize().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        let a = FiniteFieldElement::<Secp256k1Prime>::random(&mut rng);
+        let b = FiniteFieldElement::<Secp256k1Prime>::random(&mut rng);
+        assert!(a.value() < &Secp256k1Prime::get_prime());
+        assert_ne!(a, b);
+        assert_eq!(
+            FiniteFieldElement::<Prime223>::random(&mut StdRng::seed_from_u64(1)),
+            FiniteFieldElement::<Prime223>::random(&mut StdRng::seed_from_u64(1))
+        );
+    }
+
+    #[test]
+    fn batch_invert_test() {
+        let mut elements: Vec<FiniteFieldElement<Prime223>> = [5, 0, 1, 222, 17, 0, 100]
+            .iter()
+            .map(|v| FiniteFieldElement::from(*v))
+            .collect();
+        let expected: Vec<_> = elements
+            .iter()
+            .map(|e| e.inverse().unwrap_or(FiniteFieldElement::ZERO))
+            .collect();
+
+        batch_invert(&mut elements);
+        assert_eq!(elements, expected);
+
+        let mut empty: [FiniteFieldElement<Prime223>; 0] = [];
+        batch_invert(&mut empty);
+    }
+
+    #[test]
+    fn ct_eq_test() {
+        let a: FiniteFieldElement<Secp256k1Prime> = FiniteFieldElement::from(-1);
+        let b = FiniteFieldElement::from(1);
+
+        assert!(bool::from(a.ct_eq(&a.clone())));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert!(!bool::from(b.ct_eq(&FiniteFieldElement::ZERO)));
+        assert!(bool::from(
+            FiniteFieldElement::<Prime13>::ZERO.ct_eq(&FiniteFieldElement::from(13))
+        ));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_test() {
+        use zeroize::Zeroize;
+
+        let mut a: FiniteFieldElement<Secp256k1Prime> = FiniteFieldElement::from(-12345);
+        a.zeroize();
+        assert_eq!(a, FiniteFieldElement::ZERO);
+
+        let mut b: FiniteFieldElement<Prime223> = FiniteFieldElement::from(0);
+        b.zeroize();
+        assert!(b.is_zero());
+    }
+
+    #[test]
+    fn bytes_be_test() {
+        let a: FiniteFieldElement<Secp256k1Prime> = FiniteFieldElement::from(-1);
+        let bytes = a.to_bytes_be();
+        assert_eq!(bytes[0], 0xff);
+        assert_eq!(bytes[31], 0x2e);
+        assert_eq!(FiniteFieldElement::from_bytes_be(&bytes), Some(a));
+        assert_eq!(
+            FiniteFieldElement::<Secp256k1Prime>::from_bytes_be(&[0xff; 32]),
+            None
+        );
+
+        let b: FiniteFieldElement<Prime223> = FiniteFieldElement::from(170);
+        let mut expected = [0u8; 32];
+        expected[31] = 170;
+        assert_eq!(b.to_bytes_be(), expected);
+        assert_eq!(FiniteFieldElement::from_bytes_be(&expected), Some(b));
+        assert_eq!(
+            FiniteFieldElement::<Prime223>::from_bytes_be(&[0x01; 32]),
+            None
+        );
+    }
+
+    #[test]
+    fn pow_exponent_types() {
+        let a: FiniteFieldElement<Prime223> = FiniteFieldElement::from(47);
+        let expected = a.clone().pow(BigInt::from(100));
+
+        assert_eq!(a.clone().pow(&BigInt::from(100)), expected);
+        assert_eq!(a.clone().pow(BigUint::from(100u64)), expected);
+        assert_eq!(a.clone().pow(&BigUint::from(100u64)), expected);
+        assert_eq!(a.clone().pow(100u64), expected);
+        assert_eq!(a.clone().pow(100u64 + 222 * 7), expected);
+        assert_eq!(a.clone().pow(BigInt::from(-1)), a.inverse().unwrap());
+    }
+
+    #[test]
+    fn zero_to_multiple_of_group_order() {
+        use crate::fe256::Fe256;
+        use crate::small_field::SmallPrimeField;
+
+        fn check<F>()
+        where
+            F: PrimeField + fmt::Debug + Pow<BigUint, Output = F> + Pow<u64, Output = F>,
+        {
+            let order = F::modulus() - 1u64;
+            assert_eq!(F::from(0).pow(order.clone()), F::from(0));
+            assert_eq!(
+                F::from(0).pow(BigInt::from(order.clone() * 3u64)),
+                F::from(0)
+            );
+            assert_eq!(F::from(0).pow(0u64), F::from(1));
+            assert_eq!(F::from(5).pow(order), F::from(1));
+        }
+
+        check::<FiniteFieldElement<Prime223>>();
+        check::<SmallPrimeField<Prime223>>();
+        check::<Fe256>();
+        #[cfg(feature = "montgomery")]
+        check::<crate::montgomery::MontgomeryFieldElement<Prime223>>();
+    }
+
+    #[test]
+    fn try_from_test() {
+        type F = FiniteFieldElement<Prime223>;
+
+        assert_eq!(F::try_from(222u64), Ok(F::from(222)));
+        assert_eq!(F::try_from(BigUint::from(5u64)), Ok(F::from(5)));
+        assert_eq!(F::try_from(BigInt::from(0)), Ok(F::ZERO));
+        assert_eq!(
+            F::try_from(BigInt::from(-1)),
+            Err(FieldError::OutOfRange {
+                value: BigInt::from(-1),
+                prime: BigUint::from(223u64)
+            })
+        );
+
+        let err = F::try_from(1000u64).unwrap_err();
+        assert_eq!(err.to_string(), "1000 is out of range for prime 223");
+    }
+
     #[test]
     fn pow_minus() {
         let a: FiniteFieldElement<Prime13> = FiniteFieldElement::new_from_u64(12).unwrap();
@@ -268,4 +1368,53 @@ mod tests {
         assert_eq!(a.clone() - b, FiniteFieldElement::new_from_u64(28).unwrap());
         assert_eq!(a - c, FiniteFieldElement::new_from_u64(2).unwrap());
     }
+
+    def_prime_struct!(Prime17, 17);
+
+    fn assert_sqrt_all<P: Prime + Clone + PartialEq + std::fmt::Debug>() {
+        let prime = P::get_prime().to_u64().unwrap();
+        let squares: Vec<_> = (0..prime)
+            .map(|v| {
+                let a = FiniteFieldElement::<P>::new_from_u64(v).unwrap();
+                a.clone() * a
+            })
+            .collect();
+
+        for v in 0..prime {
+            let a = FiniteFieldElement::<P>::new_from_u64(v).unwrap();
+            match a.sqrt() {
+                Some(root) => assert_eq!(root.clone() * root, a),
+                None => assert!(!squares.contains(&a)),
+            }
+            assert_eq!(a.is_square(), squares.contains(&a));
+        }
+    }
+
+    #[test]
+    fn sqrt_test() {
+        assert_sqrt_all::<Prime13>();
+        assert_sqrt_all::<Prime17>();
+        assert_sqrt_all::<Prime19>();
+        assert_sqrt_all::<Prime29>();
+        assert_sqrt_all::<Prime223>();
+    }
+
+    #[test]
+    fn legendre_symbol_test() {
+        let symbols: Vec<i8> = (0..13)
+            .map(|v| FiniteFieldElement::<Prime13>::new_from_u64(v).unwrap())
+            .map(|a| a.legendre_symbol())
+            .collect();
+
+        assert_eq!(symbols, vec![0, 1, -1, 1, 1, -1, -1, -1, -1, 1, 1, -1, 1]);
+    }
+
+    #[test]
+    fn sqrt_non_residue() {
+        let a: FiniteFieldElement<Prime13> = FiniteFieldElement::new_from_u64(2).unwrap();
+        assert_eq!(a.sqrt(), None);
+
+        let a: FiniteFieldElement<Prime223> = FiniteFieldElement::new_from_u64(5).unwrap();
+        assert_eq!(a.sqrt(), None);
+    }
 }
@@ -0,0 +1,81 @@
+use crate::field::Field;
+use num::{BigInt, BigUint};
+use num_bigint::ToBigInt;
+use proptest::prelude::*;
+use std::fmt::Debug;
+
+// Elements spread over the whole field: five signed 62-bit digits combined in the field reach
+// every residue of a prime of up to 310 bits, and wrap around for smaller ones.
+pub(crate) fn any_element<F: Field + Debug>() -> impl Strategy<Value = F> {
+    any::<[i64; 5]>().prop_map(|digits| {
+        let radix = F::from(1 << 62);
+        digits
+            .iter()
+            .rev()
+            .fold(F::from(0), |acc, d| acc * radix.clone() + F::from(*d))
+    })
+}
+
+pub(crate) fn check_field_axioms<F: Field + Debug>(a: F, b: F, c: F) -> Result<(), TestCaseError> {
+    let zero = F::from(0);
+    let one = F::from(1);
+
+    prop_assert_eq!(a.clone() + zero.clone(), a.clone(), "additive identity");
+    prop_assert_eq!(
+        a.clone() * one.clone(),
+        a.clone(),
+        "multiplicative identity"
+    );
+    prop_assert_eq!(a.clone() + (-a.clone()), zero.clone(), "additive inverse");
+    if a != zero {
+        prop_assert_eq!(
+            a.clone() * (one.clone() / a.clone()),
+            one,
+            "multiplicative inverse of {:?}",
+            a
+        );
+    }
+
+    prop_assert_eq!(
+        a.clone() + b.clone(),
+        b.clone() + a.clone(),
+        "commutativity"
+    );
+    prop_assert_eq!(
+        a.clone() * b.clone(),
+        b.clone() * a.clone(),
+        "commutativity"
+    );
+    prop_assert_eq!(
+        a.clone() - b.clone(),
+        a.clone() + (-b.clone()),
+        "subtraction"
+    );
+
+    prop_assert_eq!(
+        (a.clone() + b.clone()) + c.clone(),
+        a.clone() + (b.clone() + c.clone()),
+        "associativity of addition"
+    );
+    prop_assert_eq!(
+        (a.clone() * b.clone()) * c.clone(),
+        a.clone() * (b.clone() * c.clone()),
+        "associativity of multiplication"
+    );
+    prop_assert_eq!(
+        a.clone() * (b.clone() + c.clone()),
+        a.clone() * b + a * c,
+        "distributivity"
+    );
+    Ok(())
+}
+
+pub(crate) fn check_fermat_identity<F: Field + Debug>(
+    a: F,
+    order: &BigUint,
+) -> Result<(), TestCaseError> {
+    let order = order.to_bigint().unwrap();
+    prop_assert_eq!(a.clone().pow(order), a, "a^p = a");
+    prop_assert_eq!(F::from(1).pow(BigInt::from(0)), F::from(1));
+    Ok(())
+}
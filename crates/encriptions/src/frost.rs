@@ -0,0 +1,725 @@
+use crate::field::ScalarFieldElement;
+use crate::keys::PublicKey;
+use crate::musig2::nonzero_scalar;
+use crate::point::S256Point;
+use crate::schnorr::challenge;
+use crate::sss::{evaluate, lagrange_at_zero};
+use crate::taproot::hash_to_scalar;
+use rand_core::{CryptoRng, RngCore};
+use std::collections::BTreeSet;
+use std::fmt;
+
+// FROST threshold Schnorr signatures (Komlo and Goldberg) producing BIP340 signatures. A
+// Pedersen-style distributed key generation gives each of n participants a Shamir share of a
+// group key nobody ever holds; any t of them then sign in two rounds. Participants are
+// identified by non-zero indices, and all messages between them are plain values the caller
+// passes around.
+
+fn sec(point: &S256Point) -> Vec<u8> {
+    PublicKey::from_point(*point)
+        .expect("FROST points are multiples of G by non-zero scalars")
+        .to_sec(true)
+}
+
+fn has_even_y(point: &S256Point) -> bool {
+    point.y().is_some_and(|y| !y.is_odd())
+}
+
+// ±1 to sign with the even-y point BIP340 assigns to x(point).
+fn parity(point: &S256Point) -> ScalarFieldElement {
+    if has_even_y(point) {
+        ScalarFieldElement::from(1)
+    } else {
+        ScalarFieldElement::from(-1)
+    }
+}
+
+fn check_distinct(indices: impl IntoIterator<Item = u32>) -> Result<(), FrostError> {
+    let mut seen = BTreeSet::new();
+    for index in indices {
+        if index == 0 {
+            return Err(FrostError::InvalidIndex(index));
+        }
+        if !seen.insert(index) {
+            return Err(FrostError::DuplicateIndex(index));
+        }
+    }
+    Ok(())
+}
+
+// A participant's secret polynomial for key generation. Its constant term is their contribution
+// to the group secret.
+pub struct KeygenSecret {
+    index: u32,
+    coefficients: Vec<ScalarFieldElement>,
+}
+
+impl KeygenSecret {
+    // Round one: a random polynomial of degree threshold - 1, broadcast as commitments to its
+    // coefficients along with a Schnorr proof of knowledge of the constant term. The proof stops
+    // a participant from choosing their commitment as a function of the others' (a rogue-key
+    // attack). `context` names the key-generation session and binds the proof to it, so a proof
+    // from one session cannot be replayed in another; every participant must use the same one.
+    pub fn generate<R: RngCore + CryptoRng>(
+        index: u32,
+        threshold: usize,
+        context: &[u8],
+        rng: &mut R,
+    ) -> Result<(Self, KeygenCommitment), FrostError> {
+        check_distinct([index])?;
+        if threshold == 0 {
+            return Err(FrostError::InvalidThreshold {
+                threshold,
+                participants: 0,
+            });
+        }
+        let coefficients: Vec<ScalarFieldElement> =
+            (0..threshold).map(|_| nonzero_scalar(rng)).collect();
+        let commitments: Vec<S256Point> = coefficients
+            .iter()
+            .map(|a| a * S256Point::generator())
+            .collect();
+
+        let k = nonzero_scalar(rng);
+        let proof_r = &k * S256Point::generator();
+        let c = keygen_challenge(context, index, &commitments[0], &proof_r);
+        let proof_mu = &k + &(&coefficients[0] * &c);
+
+        let commitment = KeygenCommitment {
+            index,
+            commitments,
+            proof_r,
+            proof_mu,
+        };
+        Ok((
+            Self {
+                index,
+                coefficients,
+            },
+            commitment,
+        ))
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    // f(recipient), sent privately to that participant. Participants also keep their own share.
+    // Index 0 is refused: f(0) is this participant's contribution to the group secret.
+    pub fn share_for(&self, recipient: u32) -> Result<ScalarFieldElement, FrostError> {
+        check_distinct([recipient])?;
+        Ok(evaluate(&self.coefficients, recipient))
+    }
+}
+
+impl fmt::Debug for KeygenSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeygenSecret({}, …)", self.index)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeygenSecret {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.coefficients.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+fn keygen_challenge(
+    context: &[u8],
+    index: u32,
+    key: &S256Point,
+    r: &S256Point,
+) -> ScalarFieldElement {
+    hash_to_scalar(
+        "FROST/keygen",
+        &[
+            &(context.len() as u64).to_be_bytes(),
+            context,
+            &index.to_be_bytes(),
+            &sec(key),
+            &sec(r),
+        ],
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeygenCommitment {
+    index: u32,
+    commitments: Vec<S256Point>,
+    proof_r: S256Point,
+    proof_mu: ScalarFieldElement,
+}
+
+impl KeygenCommitment {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    // μ·G = R + c·φ₀, with the challenge taken under the session's `context`.
+    pub fn verify_proof(&self, context: &[u8]) -> bool {
+        let c = keygen_challenge(context, self.index, &self.commitments[0], &self.proof_r);
+        &self.proof_mu * S256Point::generator() == self.proof_r + &c * self.commitments[0]
+    }
+
+    // share·G = Σ φₖ·recipientᵏ, i.e. the share lies on the committed polynomial.
+    pub fn verify_share(&self, recipient: u32, share: &ScalarFieldElement) -> bool {
+        share * S256Point::generator() == self.evaluate(recipient)
+    }
+
+    fn evaluate(&self, index: u32) -> S256Point {
+        let x = ScalarFieldElement::from(index as i64);
+        self.commitments
+            .iter()
+            .rev()
+            .fold(S256Point::infinity(), |acc, phi| &x * acc + *phi)
+    }
+}
+
+// What one participant holds after key generation.
+#[derive(Clone, PartialEq)]
+pub struct KeyPackage {
+    index: u32,
+    signing_share: ScalarFieldElement,
+}
+
+impl KeyPackage {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl fmt::Debug for KeyPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyPackage({}, …)", self.index)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeyPackage {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.signing_share);
+    }
+}
+
+// The public result of key generation, the same for every participant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicKeyPackage {
+    threshold: usize,
+    group_key: S256Point,
+    verifying_shares: Vec<(u32, S256Point)>,
+}
+
+impl PublicKeyPackage {
+    // Y = Σ φᵢ₀ and each participant's verifying share Yⱼ = Σᵢ Cᵢ(j) = sⱼ·G.
+    fn from_commitments(commitments: &[&KeygenCommitment]) -> Result<Self, FrostError> {
+        let group_key = commitments
+            .iter()
+            .fold(S256Point::infinity(), |acc, c| acc + c.commitments[0]);
+        group_key.x().ok_or(FrostError::PointAtInfinity)?;
+        let verifying_shares = commitments
+            .iter()
+            .map(|j| {
+                let share = commitments
+                    .iter()
+                    .fold(S256Point::infinity(), |acc, c| acc + c.evaluate(j.index));
+                (j.index, share)
+            })
+            .collect();
+        Ok(Self {
+            threshold: commitments[0].threshold(),
+            group_key,
+            verifying_shares,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    // The x-only key signatures verify against.
+    pub fn group_key(&self) -> [u8; 32] {
+        self.group_key.x().unwrap().to_bytes_be()
+    }
+
+    fn verifying_share(&self, index: u32) -> Option<&S256Point> {
+        self.verifying_shares
+            .iter()
+            .find(|(i, _)| *i == index)
+            .map(|(_, share)| share)
+    }
+}
+
+// Round two of key generation for participant `index`: `received` pairs every participant's
+// broadcast commitment (including their own) with the share that participant sent to `index`.
+// Checks every proof (under the `context` given to KeygenSecret::generate) and share before
+// summing them into the signing share.
+pub fn finish_keygen(
+    index: u32,
+    context: &[u8],
+    received: &[(KeygenCommitment, ScalarFieldElement)],
+) -> Result<(KeyPackage, PublicKeyPackage), FrostError> {
+    check_distinct(received.iter().map(|(c, _)| c.index))?;
+    if !received.iter().any(|(c, _)| c.index == index) {
+        return Err(FrostError::UnknownParticipant(index));
+    }
+    let threshold = received[0].0.threshold();
+    if threshold > received.len() {
+        return Err(FrostError::InvalidThreshold {
+            threshold,
+            participants: received.len(),
+        });
+    }
+
+    for (commitment, share) in received {
+        if commitment.threshold() != threshold {
+            return Err(FrostError::ThresholdMismatch(commitment.index));
+        }
+        if !commitment.verify_proof(context) {
+            return Err(FrostError::InvalidProof(commitment.index));
+        }
+        if !commitment.verify_share(index, share) {
+            return Err(FrostError::InvalidShare(commitment.index));
+        }
+    }
+
+    let signing_share = received
+        .iter()
+        .fold(ScalarFieldElement::ZERO, |acc, (_, share)| &acc + share);
+    let commitments: Vec<&KeygenCommitment> = received.iter().map(|(c, _)| c).collect();
+    Ok((
+        KeyPackage {
+            index,
+            signing_share,
+        },
+        PublicKeyPackage::from_commitments(&commitments)?,
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SigningCommitments {
+    index: u32,
+    hiding: S256Point,
+    binding: S256Point,
+}
+
+impl SigningCommitments {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+// A hiding and a binding nonce for one signing session. Like MuSig2's SecNonce it is consumed by
+// signing, since reusing it for another message reveals the signing share.
+pub struct SigningNonces {
+    hiding: ScalarFieldElement,
+    binding: ScalarFieldElement,
+    commitments: SigningCommitments,
+}
+
+impl SigningNonces {
+    // Round one of signing: the commitments are sent to every other signer.
+    pub fn generate<R: RngCore + CryptoRng>(index: u32, rng: &mut R) -> Self {
+        let (hiding, binding) = (nonzero_scalar(rng), nonzero_scalar(rng));
+        let commitments = SigningCommitments {
+            index,
+            hiding: &hiding * S256Point::generator(),
+            binding: &binding * S256Point::generator(),
+        };
+        Self {
+            hiding,
+            binding,
+            commitments,
+        }
+    }
+
+    pub fn commitments(&self) -> SigningCommitments {
+        self.commitments
+    }
+}
+
+impl fmt::Debug for SigningNonces {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SigningNonces({}, …)", self.commitments.index)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    commitments: Vec<SigningCommitments>,
+    binding_factors: Vec<ScalarFieldElement>,
+    r: S256Point,
+    c: ScalarFieldElement,
+}
+
+impl Session {
+    // Signers in index order, ρᵢ = hash_FROST/rho(x(Y) || m || i || commitment list) binding each
+    // nonce pair to this message and signer set, R = Σ Dᵢ + ρᵢ·Eᵢ, and c the BIP340 challenge.
+    pub fn new(
+        group: &PublicKeyPackage,
+        commitments: &[SigningCommitments],
+        msg: &[u8],
+    ) -> Result<Self, FrostError> {
+        check_distinct(commitments.iter().map(|c| c.index))?;
+        if let Some(c) = commitments
+            .iter()
+            .find(|c| group.verifying_share(c.index).is_none())
+        {
+            return Err(FrostError::UnknownParticipant(c.index));
+        }
+        if commitments.len() < group.threshold {
+            return Err(FrostError::NotEnoughSigners {
+                signers: commitments.len(),
+                threshold: group.threshold,
+            });
+        }
+
+        let mut commitments = commitments.to_vec();
+        commitments.sort_by_key(|c| c.index);
+        let encoded: Vec<u8> = commitments
+            .iter()
+            .flat_map(|c| {
+                let mut bytes = c.index.to_be_bytes().to_vec();
+                bytes.extend(sec(&c.hiding));
+                bytes.extend(sec(&c.binding));
+                bytes
+            })
+            .collect();
+        let group_key = group.group_key();
+        let binding_factors: Vec<ScalarFieldElement> = commitments
+            .iter()
+            .map(|c| {
+                hash_to_scalar(
+                    "FROST/rho",
+                    &[&group_key, msg, &c.index.to_be_bytes(), &encoded],
+                )
+            })
+            .collect();
+
+        let r = commitments
+            .iter()
+            .zip(&binding_factors)
+            .fold(S256Point::infinity(), |acc, (c, rho)| {
+                acc + c.hiding + rho * c.binding
+            });
+        let rx = r.x().ok_or(FrostError::PointAtInfinity)?.to_bytes_be();
+        let c = challenge(&rx, &group_key, msg);
+        Ok(Self {
+            commitments,
+            binding_factors,
+            r,
+            c,
+        })
+    }
+
+    fn position(&self, index: u32) -> Option<usize> {
+        self.commitments.iter().position(|c| c.index == index)
+    }
+
+    fn lambda(&self, i: usize) -> ScalarFieldElement {
+        let indices: Vec<u32> = self.commitments.iter().map(|c| c.index).collect();
+        lagrange_at_zero(&indices, i).expect("signer indices are distinct and non-zero")
+    }
+
+    // Round two: zᵢ = dᵢ + ρᵢ·eᵢ + λᵢ·c·sᵢ, with the nonces negated when R has an odd y and the
+    // share negated when Y does, so the sum is a BIP340 signature for the even-y lifts of both.
+    pub fn sign(
+        &self,
+        group: &PublicKeyPackage,
+        nonces: SigningNonces,
+        key: &KeyPackage,
+    ) -> Result<ScalarFieldElement, FrostError> {
+        let i = self
+            .position(key.index)
+            .ok_or(FrostError::UnknownParticipant(key.index))?;
+        if nonces.commitments != self.commitments[i] {
+            return Err(FrostError::NonceMismatch(key.index));
+        }
+        let k = &nonces.hiding + &(&self.binding_factors[i] * &nonces.binding);
+        let share = &parity(&group.group_key) * &key.signing_share;
+        Ok(&(&parity(&self.r) * &k) + &(&(&self.lambda(i) * &self.c) * &share))
+    }
+
+    // zᵢ·G = ±(Dᵢ + ρᵢ·Eᵢ) + λᵢ·c·(±Yᵢ), so a bad share can be pinned on its signer.
+    pub fn verify_share(
+        &self,
+        group: &PublicKeyPackage,
+        index: u32,
+        share: &ScalarFieldElement,
+    ) -> bool {
+        let (i, verifying_share) = match (self.position(index), group.verifying_share(index)) {
+            (Some(i), Some(y)) => (i, *y),
+            _ => return false,
+        };
+        let c = &self.commitments[i];
+        let r = c.hiding + &self.binding_factors[i] * c.binding;
+        let weight = &(&self.lambda(i) * &self.c) * &parity(&group.group_key);
+        share * S256Point::generator() == &parity(&self.r) * r + &weight * verifying_share
+    }
+
+    // The BIP340 signature x(R) || Σ zᵢ.
+    pub fn aggregate(&self, shares: &[ScalarFieldElement]) -> [u8; 64] {
+        let s = shares
+            .iter()
+            .fold(ScalarFieldElement::ZERO, |acc, z| &acc + z);
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&self.r.x().unwrap().to_bytes_be());
+        sig[32..].copy_from_slice(&s.to_bytes_be());
+        sig
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrostError {
+    InvalidIndex(u32),
+    DuplicateIndex(u32),
+    UnknownParticipant(u32),
+    InvalidThreshold {
+        threshold: usize,
+        participants: usize,
+    },
+    ThresholdMismatch(u32),
+    InvalidProof(u32),
+    InvalidShare(u32),
+    NotEnoughSigners {
+        signers: usize,
+        threshold: usize,
+    },
+    NonceMismatch(u32),
+    PointAtInfinity,
+}
+
+impl fmt::Display for FrostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrostError::InvalidIndex(i) => write!(f, "participant index {} is not allowed", i),
+            FrostError::DuplicateIndex(i) => write!(f, "participant {} appears twice", i),
+            FrostError::UnknownParticipant(i) => write!(f, "participant {} is not in the group", i),
+            FrostError::InvalidThreshold {
+                threshold,
+                participants,
+            } => write!(
+                f,
+                "threshold {} must be between 1 and the number of participants {}",
+                threshold, participants
+            ),
+            FrostError::ThresholdMismatch(i) => {
+                write!(f, "participant {} committed to a different threshold", i)
+            }
+            FrostError::InvalidProof(i) => {
+                write!(f, "participant {} sent an invalid proof of knowledge", i)
+            }
+            FrostError::InvalidShare(i) => {
+                write!(f, "participant {} sent a share off their commitment", i)
+            }
+            FrostError::NotEnoughSigners { signers, threshold } => write!(
+                f,
+                "{} signers cannot meet the threshold of {}",
+                signers, threshold
+            ),
+            FrostError::NonceMismatch(i) => {
+                write!(
+                    f,
+                    "participant {} signed with nonces from another session",
+                    i
+                )
+            }
+            FrostError::PointAtInfinity => write!(f, "the group key or nonce is infinity"),
+        }
+    }
+}
+
+impl std::error::Error for FrostError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schnorr;
+    use crate::sss::{combine, Share};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Runs key generation among participants 1..=n and returns everyone's key package along with
+    // the (shared) public package.
+    fn keygen(threshold: usize, n: u32, rng: &mut StdRng) -> (Vec<KeyPackage>, PublicKeyPackage) {
+        let (secrets, commitments): (Vec<_>, Vec<_>) = (1..=n)
+            .map(|i| KeygenSecret::generate(i, threshold, b"test", rng).unwrap())
+            .unzip();
+        let mut group = None;
+        let keys = (1..=n)
+            .map(|j| {
+                let received: Vec<_> = secrets
+                    .iter()
+                    .zip(&commitments)
+                    .map(|(s, c)| (c.clone(), s.share_for(j).unwrap()))
+                    .collect();
+                let (key, public) = finish_keygen(j, b"test", &received).unwrap();
+                assert!(group.as_ref().is_none_or(|g| *g == public));
+                group = Some(public);
+                key
+            })
+            .collect();
+        (keys, group.unwrap())
+    }
+
+    fn sign(
+        group: &PublicKeyPackage,
+        signers: &[&KeyPackage],
+        msg: &[u8],
+        rng: &mut StdRng,
+    ) -> [u8; 64] {
+        let nonces: Vec<SigningNonces> = signers
+            .iter()
+            .map(|key| SigningNonces::generate(key.index, rng))
+            .collect();
+        let commitments: Vec<SigningCommitments> =
+            nonces.iter().map(SigningNonces::commitments).collect();
+        let session = Session::new(group, &commitments, msg).unwrap();
+        let shares: Vec<ScalarFieldElement> = nonces
+            .into_iter()
+            .zip(signers)
+            .map(|(nonce, key)| {
+                let share = session.sign(group, nonce, key).unwrap();
+                assert!(session.verify_share(group, key.index, &share));
+                share
+            })
+            .collect();
+        session.aggregate(&shares)
+    }
+
+    #[test]
+    fn two_of_three() {
+        let mut rng = StdRng::seed_from_u64(598);
+        let (keys, group) = keygen(2, 3, &mut rng);
+
+        // Any two signers produce a valid signature.
+        for (a, b, msg) in [(0, 1, &b"one"[..]), (0, 2, b"two"), (2, 1, b"three")] {
+            let sig = sign(&group, &[&keys[a], &keys[b]], msg, &mut rng);
+            assert!(schnorr::verify(&group.group_key(), msg, &sig));
+            assert!(!schnorr::verify(&group.group_key(), b"other", &sig));
+        }
+
+        // The shares interpolate to the secret behind the group key, which nobody ever held.
+        let shares: Vec<Share<_>> = keys
+            .iter()
+            .map(|k| Share::new(k.index, k.signing_share.clone()).unwrap())
+            .collect();
+        let secret = combine(&shares[1..]).unwrap();
+        assert_eq!(&secret * S256Point::generator(), group.group_key);
+        assert_eq!(format!("{:?}", keys[0]), "KeyPackage(1, …)");
+    }
+
+    #[test]
+    fn odd_group_key() {
+        // Seeds until the group key has an odd y, so the share negation is exercised.
+        let mut rng = StdRng::seed_from_u64(0);
+        let (keys, group) = loop {
+            let (keys, group) = keygen(2, 2, &mut rng);
+            if !has_even_y(&group.group_key) {
+                break (keys, group);
+            }
+        };
+        let sig = sign(&group, &[&keys[0], &keys[1]], b"odd", &mut rng);
+        assert!(schnorr::verify(&group.group_key(), b"odd", &sig));
+    }
+
+    #[test]
+    fn rejects_misbehaviour() {
+        let mut rng = StdRng::seed_from_u64(599);
+        let (secrets, commitments): (Vec<_>, Vec<_>) = (1..=3)
+            .map(|i| KeygenSecret::generate(i, 2, b"test", &mut rng).unwrap())
+            .unzip();
+        let received = |j: u32| -> Vec<_> {
+            secrets
+                .iter()
+                .zip(&commitments)
+                .map(|(s, c)| (c.clone(), s.share_for(j).unwrap()))
+                .collect()
+        };
+
+        // Participant 2 sends participant 1 a share off their polynomial.
+        let mut bad = received(1);
+        bad[1].1 = &bad[1].1 + &ScalarFieldElement::from(1);
+        assert_eq!(
+            finish_keygen(1, b"test", &bad),
+            Err(FrostError::InvalidShare(2))
+        );
+
+        // Participant 3 copies participant 1's commitment without knowing its secret.
+        let mut bad = received(1);
+        bad[2].0.commitments[0] = bad[0].0.commitments[0];
+        assert_eq!(
+            finish_keygen(1, b"test", &bad),
+            Err(FrostError::InvalidProof(3))
+        );
+
+        // A proof made for one session does not verify in another.
+        assert!(commitments[0].verify_proof(b"test"));
+        assert!(!commitments[0].verify_proof(b"other"));
+        assert_eq!(
+            finish_keygen(1, b"other", &received(1)),
+            Err(FrostError::InvalidProof(1))
+        );
+        assert_eq!(secrets[0].share_for(0), Err(FrostError::InvalidIndex(0)));
+
+        let mut bad = received(1);
+        bad[2].0.index = 2;
+        assert_eq!(
+            finish_keygen(1, b"test", &bad),
+            Err(FrostError::DuplicateIndex(2))
+        );
+        assert_eq!(
+            finish_keygen(4, b"test", &received(4)),
+            Err(FrostError::UnknownParticipant(4))
+        );
+        assert_eq!(
+            finish_keygen(1, b"test", &received(1)[..1]),
+            Err(FrostError::InvalidThreshold {
+                threshold: 2,
+                participants: 1
+            })
+        );
+
+        let (key1, group) = finish_keygen(1, b"test", &received(1)).unwrap();
+        let (key2, _) = finish_keygen(2, b"test", &received(2)).unwrap();
+        let nonces1 = SigningNonces::generate(1, &mut rng);
+        let nonces2 = SigningNonces::generate(2, &mut rng);
+        let commitments = [nonces1.commitments(), nonces2.commitments()];
+        assert_eq!(
+            Session::new(&group, &commitments[..1], b"msg"),
+            Err(FrostError::NotEnoughSigners {
+                signers: 1,
+                threshold: 2
+            })
+        );
+
+        let session = Session::new(&group, &commitments, b"msg").unwrap();
+        assert_eq!(
+            session.sign(&group, SigningNonces::generate(1, &mut rng), &key1),
+            Err(FrostError::NonceMismatch(1))
+        );
+        let z1 = session.sign(&group, nonces1, &key1).unwrap();
+        let z2 = session.sign(&group, nonces2, &key2).unwrap();
+        assert!(!session.verify_share(&group, 2, &z1));
+        assert!(!session.verify_share(&group, 3, &z1));
+        assert!(schnorr::verify(
+            &group.group_key(),
+            b"msg",
+            &session.aggregate(&[z1, z2])
+        ));
+    }
+}
@@ -0,0 +1,703 @@
+use crate::hex::{self, HexError};
+use crate::sha256d;
+use hmac::{Hmac, Mac};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha512};
+use std::convert::TryInto;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HashError {
+    InvalidLength { expected: usize, got: usize },
+    InvalidHex(HexError),
+    EngineAlreadySet,
+}
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashError::InvalidLength { expected, got } => {
+                write!(f, "expected {} bytes, got {}", expected, got)
+            }
+            HashError::InvalidHex(e) => write!(f, "invalid hex string: {}", e),
+            HashError::EngineAlreadySet => write!(f, "the hash engine is already in use"),
+        }
+    }
+}
+
+impl std::error::Error for HashError {}
+
+fn decode_hex<const N: usize>(s: &str) -> Result<[u8; N], HashError> {
+    let bytes = hex::decode(s).map_err(HashError::InvalidHex)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| HashError::InvalidLength {
+            expected: N,
+            got: bytes.len(),
+        })
+}
+
+// Each hash keeps its bytes in the order the hash function produced them. `reversed`
+// types (txids, block hashes) are displayed and parsed in reversed byte order.
+macro_rules! hash_newtype {
+    ($name:ident, $len:expr, reversed: $reversed:expr) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name([u8; $len]);
+
+        impl $name {
+            pub const LEN: usize = $len;
+            pub const DISPLAY_REVERSED: bool = $reversed;
+
+            pub fn from_byte_array(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            pub fn from_slice(bytes: &[u8]) -> Result<Self, HashError> {
+                let bytes = bytes.try_into().map_err(|_| HashError::InvalidLength {
+                    expected: $len,
+                    got: bytes.len(),
+                })?;
+                Ok(Self(bytes))
+            }
+
+            pub fn as_byte_array(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            pub fn to_byte_array(self) -> [u8; $len] {
+                self.0
+            }
+
+            pub fn to_display_bytes(self) -> [u8; $len] {
+                let mut bytes = self.0;
+                if $reversed {
+                    bytes.reverse();
+                }
+                bytes
+            }
+
+            pub fn from_display_bytes(mut bytes: [u8; $len]) -> Self {
+                if $reversed {
+                    bytes.reverse();
+                }
+                Self(bytes)
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.to_display_bytes()
+                    .iter()
+                    .try_for_each(|b| write!(f, "{:02x}", b))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::LowerHex::fmt(self, f)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({:x})", stringify!($name), self)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = HashError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                decode_hex::<$len>(s).map(Self::from_display_bytes)
+            }
+        }
+    };
+}
+
+hash_newtype!(Sha256, 32, reversed: false);
+hash_newtype!(Sha256d, 32, reversed: true);
+hash_newtype!(Hash160, 20, reversed: false);
+hash_newtype!(TapLeafHash, 32, reversed: false);
+hash_newtype!(TapBranchHash, 32, reversed: false);
+hash_newtype!(TapTweakHash, 32, reversed: false);
+hash_newtype!(Txid, 32, reversed: true);
+hash_newtype!(Wtxid, 32, reversed: true);
+hash_newtype!(BlockHash, 32, reversed: true);
+
+// Txids, wtxids and block hashes are hash256 of the serialized transaction or header; keeping
+// them distinct types stops one being passed, or byte-swapped, where another is expected.
+macro_rules! hash256_newtype {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                pub fn hash(data: &[u8]) -> Self {
+                    Self(hash256(data))
+                }
+
+                // Hashes a serialization as it is written; see HashWriter.
+                pub fn writer() -> HashWriter<Self> {
+                    HashWriter::new(|digest| Self(hash_engine().sha256(&digest)))
+                }
+            }
+        )*
+    };
+}
+
+hash256_newtype!(Txid, Wtxid, BlockHash);
+
+// The primitives behind hash256 and hash160. A backend only has to supply SHA-256 and
+// RIPEMD-160; one with a faster double-SHA256 can override `hash256` as well.
+pub trait HashEngine: Send + Sync {
+    fn sha256(&self, data: &[u8]) -> [u8; 32];
+
+    fn ripemd160(&self, data: &[u8]) -> [u8; 20];
+
+    fn hash256(&self, data: &[u8]) -> [u8; 32] {
+        self.sha256(&self.sha256(data))
+    }
+
+    fn hash160(&self, data: &[u8]) -> [u8; 20] {
+        self.ripemd160(&self.sha256(data))
+    }
+}
+
+// The default backend: the sha2 and ripemd crates, which pick up SHA-NI or the ARMv8 SHA2
+// extensions at runtime where the CPU has them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha2Engine;
+
+impl HashEngine for Sha2Engine {
+    fn sha256(&self, data: &[u8]) -> [u8; 32] {
+        sha2::Sha256::digest(data).into()
+    }
+
+    fn ripemd160(&self, data: &[u8]) -> [u8; 20] {
+        Ripemd160::digest(data).into()
+    }
+
+    fn hash256(&self, data: &[u8]) -> [u8; 32] {
+        sha256d::hash256(data)
+    }
+}
+
+static ENGINE: OnceLock<&'static dyn HashEngine> = OnceLock::new();
+
+// Installs the backend used by hash256 and hash160. It must happen before either is first
+// called, after which the engine is fixed for the life of the process.
+pub fn set_hash_engine(engine: &'static dyn HashEngine) -> Result<(), HashError> {
+    ENGINE.set(engine).map_err(|_| HashError::EngineAlreadySet)
+}
+
+pub fn hash_engine() -> &'static dyn HashEngine {
+    *ENGINE.get_or_init(|| &Sha2Engine)
+}
+
+// SHA-256 applied twice, what the book calls hash256: signature hashes, txids, block hashes and
+// Base58Check checksums.
+pub fn hash256(data: &[u8]) -> [u8; 32] {
+    hash_engine().hash256(data)
+}
+
+// RIPEMD-160 of SHA-256, the 20-byte hash behind P2PKH and P2SH addresses.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    hash_engine().hash160(data)
+}
+
+// An io::Write sink that hashes whatever is serialized into it, so a txid or sighash needs no
+// intermediate buffer. The streamed SHA-256 pass always runs on the sha2 crate, as HashEngine
+// only hashes whole buffers; the fixed-size pass that finishes hash256 and hash160 goes through
+// hash_engine().
+pub struct HashWriter<H> {
+    sha256: sha2::Sha256,
+    finish: fn([u8; 32]) -> H,
+}
+
+impl HashWriter<Sha256> {
+    pub fn sha256() -> Self {
+        HashWriter::new(Sha256::from_byte_array)
+    }
+}
+
+impl HashWriter<Sha256d> {
+    pub fn hash256() -> Self {
+        HashWriter::new(|digest| Sha256d::from_byte_array(hash_engine().sha256(&digest)))
+    }
+}
+
+impl HashWriter<Hash160> {
+    pub fn hash160() -> Self {
+        HashWriter::new(|digest| Hash160::from_byte_array(hash_engine().ripemd160(&digest)))
+    }
+}
+
+impl<H> HashWriter<H> {
+    fn new(finish: fn([u8; 32]) -> H) -> Self {
+        HashWriter {
+            sha256: sha2::Sha256::new(),
+            finish,
+        }
+    }
+
+    pub fn finalize(self) -> H {
+        (self.finish)(self.sha256.finalize().into())
+    }
+}
+
+impl<H> io::Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sha256.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Tags hashed often enough to keep their midstate around: BIP340, BIP341, MuSig2 and FROST.
+const CACHED_TAGS: [&str; 12] = [
+    "BIP0340/aux",
+    "BIP0340/nonce",
+    "BIP0340/challenge",
+    "TapLeaf",
+    "TapBranch",
+    "TapTweak",
+    "TapSighash",
+    "KeyAgg list",
+    "KeyAgg coefficient",
+    "MuSig/noncecoef",
+    "FROST/keygen",
+    "FROST/rho",
+];
+
+// SHA-256 after absorbing sha256(tag) || sha256(tag), exactly one 64-byte block.
+fn tag_midstate(tag: &str) -> sha2::Sha256 {
+    fn absorb(tag: &str) -> sha2::Sha256 {
+        let tag = sha2::Sha256::digest(tag.as_bytes());
+        let mut engine = sha2::Sha256::new();
+        engine.update(tag);
+        engine.update(tag);
+        engine
+    }
+    static MIDSTATES: OnceLock<Vec<sha2::Sha256>> = OnceLock::new();
+    match CACHED_TAGS.iter().position(|&cached| cached == tag) {
+        Some(i) => {
+            MIDSTATES.get_or_init(|| CACHED_TAGS.iter().map(|t| absorb(t)).collect())[i].clone()
+        }
+        None => absorb(tag),
+    }
+}
+
+// BIP340 tagged hash: sha256(sha256(tag) || sha256(tag) || data).
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    tagged_hash_parts(tag, &[data])
+}
+
+pub(crate) fn tagged_hash_parts(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let mut engine = tag_midstate(tag);
+    for part in parts {
+        engine.update(part);
+    }
+    engine.finalize().into()
+}
+
+// HMAC over the concatenation of `parts`, so callers need not copy them into one buffer. RFC 6979
+// nonces use HMAC-SHA256; BIP32 derivation and BIP39 seeds use HMAC-SHA512.
+pub fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+pub fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+// BIP39 stretches mnemonics with this many rounds.
+pub const PBKDF2_ITERATIONS: u32 = 2048;
+
+// PBKDF2 (RFC 8018) with HMAC-SHA512, filling `out` with as many 64-byte blocks as it needs.
+pub fn pbkdf2_sha512(password: &[u8], salt: &[u8], iterations: u32, out: &mut [u8]) {
+    assert!(iterations > 0, "PBKDF2 needs at least one iteration");
+    let prf = Hmac::<Sha512>::new_from_slice(password).expect("HMAC takes keys of any length");
+    for (i, block) in out.chunks_mut(64).enumerate() {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&(i as u32 + 1).to_be_bytes());
+        let mut u: [u8; 64] = mac.finalize().into_bytes().into();
+
+        let mut t = u;
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize().into_bytes().into();
+            for (t, u) in t.iter_mut().zip(u.iter()) {
+                *t ^= u;
+            }
+        }
+        block.copy_from_slice(&t[..block.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const GENESIS: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    #[test]
+    fn sha256d_displays_reversed() {
+        let hash: Sha256d = GENESIS.parse().unwrap();
+
+        assert_eq!(hash.as_byte_array()[0], 0x6f);
+        assert_eq!(hash.as_byte_array()[31], 0x00);
+        assert_eq!(hash.to_string(), GENESIS);
+        assert_eq!(hash.to_display_bytes()[0], 0x00);
+        assert_eq!(Sha256d::from_byte_array(hash.to_byte_array()), hash);
+        assert_eq!(format!("{:?}", hash), format!("Sha256d({})", GENESIS));
+    }
+
+    #[test]
+    fn txids_and_block_hashes() {
+        // The genesis header: its hash is the block hash and bytes 36..68 are the coinbase txid.
+        let header = hex::decode(
+            "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b2\
+             7ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c",
+        )
+        .unwrap();
+        let block_hash = BlockHash::hash(&header);
+        assert_eq!(block_hash.to_string(), GENESIS);
+        assert_eq!(GENESIS.parse(), Ok(block_hash));
+        assert_eq!(
+            format!("{:?}", block_hash),
+            format!("BlockHash({})", GENESIS)
+        );
+
+        let txid = Txid::from_slice(&header[36..68]).unwrap();
+        assert_eq!(
+            txid.to_string(),
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+        );
+        assert_eq!(txid.as_byte_array()[0], 0x3b);
+
+        // Ordering follows the internal bytes, not the displayed ones.
+        let mut first = [0; 32];
+        first[0] = 1;
+        let mut last = [0; 32];
+        last[31] = 1;
+        let (first, last) = (Wtxid::from_byte_array(first), Wtxid::from_byte_array(last));
+        assert!(first > last);
+        assert!(first.to_string() < last.to_string());
+    }
+
+    #[test]
+    fn forward_hashes_display_in_byte_order() {
+        let hash: Sha256 = GENESIS.parse().unwrap();
+        assert_eq!(hash.as_byte_array()[0], 0x00);
+        assert_eq!(hash.to_string(), GENESIS);
+
+        let leaf = TapLeafHash::from_byte_array(*hash.as_byte_array());
+        assert_eq!(leaf.to_string(), GENESIS);
+
+        let h160 = Hash160::from_slice(&[0xab; 20]).unwrap();
+        assert_eq!(h160.to_string(), "ab".repeat(20));
+    }
+
+    #[test]
+    fn hash256_vectors() {
+        let hex = |bytes: [u8; 32]| Sha256::from_byte_array(bytes).to_string();
+        assert_eq!(
+            hex(hash256(b"")),
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+        assert_eq!(
+            hex(hash256(b"hello")),
+            "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50"
+        );
+        // The genesis block header hashes to the (reversed) genesis hash.
+        let header = "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c";
+        let header = hex::decode(header).unwrap();
+        assert_eq!(
+            Sha256d::from_byte_array(hash256(&header)).to_string(),
+            GENESIS
+        );
+    }
+
+    // SEC encodings from the book's chapter 4 address exercises.
+    #[test]
+    fn hash160_vectors() {
+        let hex = |bytes: [u8; 20]| Hash160::from_byte_array(bytes).to_string();
+        assert_eq!(
+            hex(hash160(b"")),
+            "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb"
+        );
+        // 5002, uncompressed.
+        assert_eq!(
+            hex(hash160(&hex::decode("040f85cb0c917647fadfd31e641231d3a01ac9d3d8a680aab2457e0036bf34d37e6949b302843a33cdf068589c8330bb5c09c1739420d65b7b33cfab6d10118d97").unwrap())),
+            "41243614aecd13819d7a7f348a4a07fbcb29d8e5"
+        );
+        // 0x12345deadbeef, compressed.
+        assert_eq!(
+            hex(hash160(
+                &hex::decode("030564faa58724c9326b297b3c0b35fb37a0ed7e3e13d1f1ba63fc762928f2b85b")
+                    .unwrap()
+            )),
+            "99a4c61750789253f69fd750ac0d021263373305"
+        );
+    }
+
+    // RFC 4231 test cases 1, 2, 3 and 6.
+    #[test]
+    fn hmac_vectors() {
+        let cases: [(&[u8], &[u8], &str, &str); 4] = [
+            (
+                &[0x0b; 20],
+                b"Hi There",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+            ),
+            (
+                b"Jefe",
+                b"what do ya want for nothing?",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+                 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            ),
+            (
+                &[0xaa; 20],
+                &[0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39\
+                 bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+            ),
+            // A key longer than the block size is hashed first.
+            (
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            ),
+        ];
+        for (key, data, sha256, sha512) in cases.iter() {
+            assert_eq!(hex::encode(&hmac_sha256(key, &[data])), *sha256);
+            assert_eq!(hex::encode(&hmac_sha512(key, &[data])), *sha512);
+            // Splitting the message does not change the MAC.
+            let (a, b) = data.split_at(data.len() / 2);
+            assert_eq!(hex::encode(&hmac_sha256(key, &[a, b])), *sha256);
+        }
+    }
+
+    // Counts calls so the provided methods can be seen going through the backend.
+    #[derive(Default)]
+    struct CountingEngine {
+        sha256_calls: AtomicUsize,
+    }
+
+    impl HashEngine for CountingEngine {
+        fn sha256(&self, data: &[u8]) -> [u8; 32] {
+            self.sha256_calls.fetch_add(1, Ordering::Relaxed);
+            Sha2Engine.sha256(data)
+        }
+
+        fn ripemd160(&self, data: &[u8]) -> [u8; 20] {
+            Sha2Engine.ripemd160(data)
+        }
+    }
+
+    #[test]
+    fn hash_engine_backends() {
+        let engine = CountingEngine::default();
+        assert_eq!(engine.hash256(b"hello"), hash256(b"hello"));
+        assert_eq!(engine.hash160(b"hello"), hash160(b"hello"));
+        assert_eq!(engine.sha256_calls.load(Ordering::Relaxed), 3);
+        assert_eq!(
+            Sha256::from_byte_array(Sha2Engine.sha256(b"")).to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        // hash256 above has already fixed the engine.
+        static LATE: CountingEngine = CountingEngine {
+            sha256_calls: AtomicUsize::new(0),
+        };
+        assert_eq!(set_hash_engine(&LATE), Err(HashError::EngineAlreadySet));
+    }
+
+    #[test]
+    fn hash_writer() {
+        use crate::encode::write_u32_le;
+        use std::io::Write;
+
+        // The genesis header, written field by field.
+        let header = hex::decode(
+            "0100000000000000000000000000000000000000000000000000000000000000000000003ba3edfd7a7b12b2\
+             7ac72c3e67768f617fc81bc3888a51323a9fb8aa4b1e5e4a29ab5f49ffff001d1dac2b7c",
+        )
+        .unwrap();
+        let mut writer = HashWriter::hash256();
+        write_u32_le(&mut writer, 1).unwrap();
+        writer.write_all(&header[4..68]).unwrap();
+        for field in [0x495fab29, 0x1d00ffff, 0x7c2bac1d] {
+            write_u32_le(&mut writer, field).unwrap();
+        }
+        let hash = writer.finalize();
+        assert_eq!(hash.to_string(), GENESIS);
+        assert_eq!(hash.to_byte_array(), hash256(&header));
+        assert_eq!(
+            BlockHash::from_byte_array(hash.to_byte_array()),
+            BlockHash::hash(&header)
+        );
+
+        let mut writer = BlockHash::writer();
+        writer.write_all(&header).unwrap();
+        assert_eq!(writer.finalize(), BlockHash::hash(&header));
+        let mut writer = Txid::writer();
+        writer.write_all(&header).unwrap();
+        assert_eq!(writer.finalize(), Txid::hash(&header));
+
+        let mut writer = HashWriter::sha256();
+        io::copy(&mut &header[..], &mut writer).unwrap();
+        assert_eq!(
+            writer.finalize().to_byte_array(),
+            Sha2Engine.sha256(&header)
+        );
+
+        let mut writer = HashWriter::hash160();
+        writer.write_all(b"hel").unwrap();
+        writer.write_all(b"lo").unwrap();
+        assert_eq!(writer.finalize().to_byte_array(), hash160(b"hello"));
+    }
+
+    #[test]
+    fn pbkdf2_vectors() {
+        let cases: [(&[u8], &[u8], u32, &str); 5] = [
+            (
+                b"password",
+                b"salt",
+                1,
+                "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252\
+                 c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce",
+            ),
+            (
+                b"password",
+                b"salt",
+                2,
+                "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53c\
+                 f76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e",
+            ),
+            (
+                b"password",
+                b"salt",
+                4096,
+                "d197b1b33db0143e018b12f3d1d1479e6cdebdcc97c5c0f87f6902e072f457b5\
+                 143f30602641b3d55cd335988cb36b84376060ecd532e039b742a239434af2d5",
+            ),
+            // Spans two blocks, the second truncated.
+            (
+                b"passwordPASSWORDpassword",
+                b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                4096,
+                "8c0511f4c6e597c6ac6315d8f0362e225f3c501495ba23b868c005174dc4ee71\
+                 115b59f9e60cd9532fa33e0f75aefe30225c583a186cd82bd4daea9724a3d3b8\
+                 04f75bdd41494fa324cab24bcc680fb3",
+            ),
+            (
+                b"pass\0word",
+                b"sa\0lt",
+                PBKDF2_ITERATIONS,
+                "83441528ad720fce1ae2ee3c14020312",
+            ),
+        ];
+        for (password, salt, iterations, expected) in cases.iter() {
+            let mut out = vec![0u8; expected.len() / 2];
+            pbkdf2_sha512(password, salt, *iterations, &mut out);
+            assert_eq!(hex::encode(&out), *expected);
+        }
+    }
+
+    #[test]
+    fn tagged_hash_vectors() {
+        let hex = |bytes: [u8; 32]| Sha256::from_byte_array(bytes).to_string();
+
+        // The challenge for BIP340 test vector 0: r || P || m with secret key 3 and m = 0.
+        let r = hex::decode("e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215")
+            .unwrap();
+        let p = hex::decode("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9")
+            .unwrap();
+        let challenge = "6bb6b93a91f2ecc0cd924f4f9baabb5e6eb21745bb00f2cebdaac908bb5d86ce";
+        let data = [&r[..], &p, &[0; 32]].concat();
+        assert_eq!(hex(tagged_hash("BIP0340/challenge", &data)), challenge);
+        assert_eq!(
+            hex(tagged_hash_parts("BIP0340/challenge", &[&r, &p, &[0; 32]])),
+            challenge
+        );
+        assert_eq!(
+            hex(tagged_hash("BIP0340/aux", &[0; 32])),
+            "54f169cfc9e2e5727480441f90ba25c488f461c70b5ea5dcaaf7af69270aa514"
+        );
+
+        // Tags outside the cache take the same path from scratch.
+        assert_eq!(
+            hex(tagged_hash("example/uncached", b"abc")),
+            "7ecaaa0dbf852bd7c31e7804dc0979c7d7b9031ae4eb2fc9a723dbda354a45d5"
+        );
+        for tag in CACHED_TAGS.iter() {
+            let tag_hash = sha2::Sha256::digest(tag.as_bytes());
+            let expected = sha2::Sha256::digest([&tag_hash[..], &tag_hash, b"abc"].concat());
+            assert_eq!(tagged_hash(tag, b"abc"), <[u8; 32]>::from(expected));
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            Hash160::from_slice(&[0; 32]),
+            Err(HashError::InvalidLength {
+                expected: 20,
+                got: 32
+            })
+        );
+        assert_eq!(
+            "00".parse::<Sha256d>(),
+            Err(HashError::InvalidLength {
+                expected: 32,
+                got: 1
+            })
+        );
+        assert_eq!(
+            "zz".repeat(20).parse::<Hash160>(),
+            Err(HashError::InvalidHex(HexError::InvalidCharacter {
+                character: 'z',
+                position: 0
+            }))
+        );
+        assert_eq!(
+            format!("+1{}", "00".repeat(19)).parse::<Hash160>(),
+            Err(HashError::InvalidHex(HexError::InvalidCharacter {
+                character: '+',
+                position: 0
+            }))
+        );
+        assert_eq!(
+            "000".parse::<Sha256d>(),
+            Err(HashError::InvalidHex(HexError::OddLength(3)))
+        );
+    }
+}
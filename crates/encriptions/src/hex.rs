@@ -0,0 +1,108 @@
+use std::fmt;
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+// Lowercase, two digits per byte.
+pub fn encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+// Accepts either case. Unlike u8::from_str_radix, a sign is not a digit.
+pub fn decode(s: &str) -> Result<Vec<u8>, HexError> {
+    if !s.len().is_multiple_of(2) {
+        return Err(HexError::OddLength(s.len()));
+    }
+    let mut out = Vec::with_capacity(s.len() / 2);
+    let mut high = 0;
+    for (position, character) in s.char_indices() {
+        let nibble = character.to_digit(16).ok_or(HexError::InvalidCharacter {
+            character,
+            position,
+        })? as u8;
+        if position.is_multiple_of(2) {
+            high = nibble << 4;
+        } else {
+            out.push(high | nibble);
+        }
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexError {
+    OddLength(usize),
+    // `position` is a byte offset into the input.
+    InvalidCharacter { character: char, position: usize },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexError::OddLength(len) => write!(f, "hex string has odd length {}", len),
+            HexError::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid hex character {:?} at position {}",
+                character, position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn encode_and_decode() {
+        assert_eq!(encode(&[]), "");
+        assert_eq!(encode(&[0x00, 0x0f, 0xa5, 0xff]), "000fa5ff");
+        assert_eq!(decode("000fA5Ff"), Ok(vec![0x00, 0x0f, 0xa5, 0xff]));
+        assert_eq!(decode(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert_eq!(decode("abc"), Err(HexError::OddLength(3)));
+        assert_eq!(
+            decode("00zz"),
+            Err(HexError::InvalidCharacter {
+                character: 'z',
+                position: 2
+            })
+        );
+        assert_eq!(
+            decode("+f"),
+            Err(HexError::InvalidCharacter {
+                character: '+',
+                position: 0
+            })
+        );
+        // Multi-byte characters are reported rather than split.
+        assert_eq!(
+            decode("0é0"),
+            Err(HexError::InvalidCharacter {
+                character: 'é',
+                position: 1
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(bytes in proptest::collection::vec(any::<u8>(), 0..64)) {
+            prop_assert_eq!(decode(&encode(&bytes)).unwrap(), bytes.clone());
+            prop_assert_eq!(decode(&encode(&bytes).to_uppercase()).unwrap(), bytes);
+        }
+    }
+}
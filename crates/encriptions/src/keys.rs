@@ -0,0 +1,824 @@
+use crate::address::Address;
+use crate::base58::{decode_base58check, encode_base58check, Base58Error};
+use crate::ecdsa::{deterministic_k, deterministic_k_with_extra, Message, Signature};
+use crate::fe256::Fe256;
+use crate::field::{Choice, ConstantTimeEq, Inverse, ScalarFieldElement};
+use crate::hashes::{hash160, Hash160};
+use crate::msm::strauss;
+use crate::network::Network;
+use crate::point::{GeneralPoint, S256Point};
+use crate::taproot::tweak_public_key;
+use num::{BigInt, BigUint, Zero};
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::convert::TryInto;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PublicKey(S256Point);
+
+impl PublicKey {
+    // The point at infinity is not a valid key.
+    pub fn from_point(point: S256Point) -> Option<Self> {
+        point.x().map(|_| Self(point))
+    }
+
+    pub fn point(&self) -> &S256Point {
+        &self.0
+    }
+
+    // 02/03 || x for compressed keys (the prefix carries y's parity), 04 || x || y otherwise.
+    pub fn to_sec(&self, compressed: bool) -> Vec<u8> {
+        let (x, y) = (self.0.x().unwrap(), self.0.y().unwrap());
+        let mut out = if compressed {
+            vec![if y.is_odd() { 3 } else { 2 }]
+        } else {
+            vec![4]
+        };
+        out.extend(x.to_bytes_be());
+        if !compressed {
+            out.extend(y.to_bytes_be());
+        }
+        out
+    }
+
+    pub fn from_sec(bytes: &[u8]) -> Result<Self, SecError> {
+        let coordinate = |b: &[u8]| Fe256::from_bytes_be(b.try_into().unwrap());
+        let point = match (bytes.first(), bytes.len()) {
+            (Some(4), 65) => coordinate(&bytes[1..33])
+                .zip(coordinate(&bytes[33..]))
+                .and_then(|(x, y)| S256Point::new(GeneralPoint::finite(x, y))),
+            (Some(prefix @ (2 | 3)), 33) => {
+                coordinate(&bytes[1..]).and_then(|x| S256Point::lift_x(x, *prefix == 3))
+            }
+            (Some(prefix @ 2..=4), len) => {
+                return Err(SecError::InvalidLength {
+                    prefix: *prefix,
+                    len,
+                })
+            }
+            (Some(prefix), _) => return Err(SecError::InvalidPrefix(*prefix)),
+            (None, _) => return Err(SecError::Empty),
+        };
+        point.map(Self).ok_or(SecError::NotOnCurve)
+    }
+
+    // ripemd160(sha256(sec)) of the compressed encoding.
+    pub fn hash160(&self) -> Hash160 {
+        Hash160::from_byte_array(hash160(&self.to_sec(true)))
+    }
+
+    pub fn address(&self, network: Network, kind: AddressKind) -> Address {
+        match kind {
+            AddressKind::P2pkh => Address::P2pkh {
+                network,
+                hash: self.hash160(),
+            },
+            AddressKind::P2pkhUncompressed => Address::P2pkh {
+                network,
+                hash: Hash160::from_byte_array(hash160(&self.to_sec(false))),
+            },
+            AddressKind::P2shP2wpkh => {
+                let mut redeem_script = vec![0x00, 0x14];
+                redeem_script.extend(self.hash160().as_byte_array());
+                Address::P2sh {
+                    network,
+                    hash: Hash160::from_byte_array(hash160(&redeem_script)),
+                }
+            }
+            AddressKind::P2wpkh => Address::P2wpkh {
+                network,
+                hash: self.hash160(),
+            },
+            AddressKind::P2tr => {
+                let internal_key = self.0.x().unwrap().to_bytes_be();
+                let (output_key, _) = tweak_public_key(&internal_key, None).expect(
+                    "a tweak of n or more, or one cancelling the key, is negligibly unlikely",
+                );
+                Address::P2tr {
+                    network,
+                    output_key,
+                }
+            }
+        }
+    }
+
+    pub fn to_address(&self, network: Network, kind: AddressKind) -> String {
+        self.address(network, kind).to_string()
+    }
+
+    // Accepts when x(u·G + v·P) ≡ r (mod n), with u = z/s and v = r/s.
+    pub fn verify<M: Message + ?Sized>(&self, msg: &M, sig: &Signature) -> bool {
+        if sig.r.is_zero() || sig.s.is_zero() {
+            return false;
+        }
+
+        let z = ScalarFieldElement::from_biguint_reduced(&msg.z());
+        let s_inv = sig.s.inverse().unwrap();
+        let u = &z * &s_inv;
+        let v = &sig.r * &s_inv;
+        let total = strauss(&[
+            (BigInt::from(u.value().clone()), S256Point::generator()),
+            (BigInt::from(v.value().clone()), self.0),
+        ]);
+
+        match total.x() {
+            Some(x) => ScalarFieldElement::from_biguint_reduced(&x.to_biguint()) == sig.r,
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    // Legacy pay-to-pubkey-hash over the compressed key.
+    P2pkh,
+    // The same for wallets that still use uncompressed keys.
+    P2pkhUncompressed,
+    // BIP49 P2WPKH nested in P2SH.
+    P2shP2wpkh,
+    // BIP84 native segwit v0.
+    P2wpkh,
+    // BIP86 key-path-only taproot: the x-only key tweaked with no script tree.
+    P2tr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecError {
+    Empty,
+    InvalidPrefix(u8),
+    InvalidLength { prefix: u8, len: usize },
+    NotOnCurve,
+}
+
+impl fmt::Display for SecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecError::Empty => write!(f, "empty SEC encoding"),
+            SecError::InvalidPrefix(prefix) => write!(f, "unknown SEC prefix 0x{:02x}", prefix),
+            SecError::InvalidLength { prefix, len } => {
+                write!(
+                    f,
+                    "SEC encoding with prefix 0x{:02x} cannot be {} bytes",
+                    prefix, len
+                )
+            }
+            SecError::NotOnCurve => write!(f, "SEC encoding is not a point on secp256k1"),
+        }
+    }
+}
+
+impl std::error::Error for SecError {}
+
+#[derive(Clone)]
+pub struct PrivateKey {
+    secret: ScalarFieldElement,
+    public_key: PublicKey,
+}
+
+impl PrivateKey {
+    // A zero secret would give the point at infinity, so it is rejected along with it.
+    pub fn new(secret: ScalarFieldElement) -> Option<Self> {
+        let point = &secret * S256Point::generator();
+        PublicKey::from_point(point).map(|public_key| Self { secret, public_key })
+    }
+
+    // Base58Check of the network prefix, the 32-byte secret and, for keys whose addresses use the
+    // compressed SEC form, a trailing 0x01.
+    pub fn to_wif(&self, network: Network, compressed: bool) -> String {
+        let mut payload = self.secret.to_bytes_be().to_vec();
+        if compressed {
+            payload.push(1);
+        }
+        encode_base58check(network.wif_prefix(), &payload)
+    }
+
+    pub fn from_wif(s: &str) -> Result<(Self, Network, bool), WifError> {
+        let (prefix, payload) = decode_base58check(s).map_err(WifError::InvalidBase58Check)?;
+        let network = Network::from_wif_prefix(prefix).ok_or(WifError::InvalidPrefix(prefix))?;
+        let compressed = match payload.len() {
+            32 => false,
+            33 if payload[32] == 1 => true,
+            33 => return Err(WifError::InvalidCompressionFlag(payload[32])),
+            len => return Err(WifError::InvalidLength(len)),
+        };
+        let secret = ScalarFieldElement::from_bytes_be(payload[..32].try_into().unwrap())
+            .ok_or(WifError::InvalidSecret)?;
+        let key = Self::new(secret).ok_or(WifError::InvalidSecret)?;
+        Ok((key, network, compressed))
+    }
+
+    // Uniform in [1, n - 1]: the scalar sampler already rejects values ≥ n, and zero is rejected
+    // here.
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            if let Some(key) = Self::new(ScalarFieldElement::random(rng)) {
+                return key;
+            }
+        }
+    }
+
+    // sha256 of the seed, rehashed in the (astronomically unlikely) case it is zero or ≥ n.
+    // Deterministic, so only as secret as the seed itself.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut hash: [u8; 32] = Sha256::digest(seed).into();
+        loop {
+            if let Some(key) = ScalarFieldElement::from_bytes_be(&hash).and_then(Self::new) {
+                return key;
+            }
+            hash = Sha256::digest(hash).into();
+        }
+    }
+
+    pub fn secret(&self) -> &ScalarFieldElement {
+        &self.secret
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    // s = (z + r·e)/k with k from RFC 6979, so the same key and z always give the same signature.
+    // s is normalized to the low half of the order.
+    pub fn sign<M: Message + ?Sized>(&self, msg: &M) -> Signature {
+        self.sign_recoverable(msg).0
+    }
+
+    // Also returns the recovery id: bit 0 is the parity of R's y, bit 1 is set when R's x
+    // overflowed the order. Flipping s to n - s negates R, so it flips the parity bit.
+    pub fn sign_recoverable<M: Message + ?Sized>(&self, msg: &M) -> (Signature, u8) {
+        let z = msg.z();
+        self.sign_with_k(&z, deterministic_k(&self.secret, &z))
+    }
+
+    // Opt-in grinding for a low r, as Bitcoin Core signs: retry with the RFC 6979 extra data set
+    // to a little-endian counter 1, 2, ... (zero-padded to 32 bytes) until r < 2^255. The DER
+    // encoding is then at most 70 bytes, 71 with the sighash byte. Takes two tries on average.
+    pub fn sign_low_r<M: Message + ?Sized>(&self, msg: &M) -> Signature {
+        let z = msg.z();
+        let mut sig = self.sign(&z);
+        let mut extra = [0u8; 32];
+        let mut counter = 0u32;
+        while !sig.is_low_r() {
+            counter += 1;
+            extra[..4].copy_from_slice(&counter.to_le_bytes());
+            sig = self
+                .sign_with_k(&z, deterministic_k_with_extra(&self.secret, &z, &extra))
+                .0;
+        }
+        sig
+    }
+
+    fn sign_with_k(&self, z: &BigUint, k: ScalarFieldElement) -> (Signature, u8) {
+        let z = ScalarFieldElement::from_biguint_reduced(z);
+        let point = &k * S256Point::generator();
+        let (x, y) = (point.x().unwrap().to_biguint(), point.y().unwrap());
+
+        let r = ScalarFieldElement::from_biguint_reduced(&x);
+        let mut recovery_id = y.is_odd() as u8;
+        if &x != r.value() {
+            recovery_id |= 2;
+        }
+
+        let s = (&z + &(&r * &self.secret)) / k;
+        let mut sig = Signature::new(r, s);
+        if !sig.is_low_s() {
+            sig.normalize_s();
+            recovery_id ^= 1;
+        }
+        (sig, recovery_id)
+    }
+}
+
+// The secret is compared in constant time, with the public key folded into the same choice.
+impl ConstantTimeEq for PrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.secret.ct_eq(&other.secret) & Choice::from((self.public_key == other.public_key) as u8)
+    }
+}
+
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+// The secret never appears in logs.
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PrivateKey(…)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for PrivateKey {}
+
+// A private key with its public key, and every standard single-key address for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keypair {
+    privkey: PrivateKey,
+    pubkey: PublicKey,
+}
+
+impl Keypair {
+    pub fn new(privkey: PrivateKey) -> Self {
+        let pubkey = *privkey.public_key();
+        Self { privkey, pubkey }
+    }
+
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self::new(PrivateKey::generate(rng))
+    }
+
+    pub fn privkey(&self) -> &PrivateKey {
+        &self.privkey
+    }
+
+    pub fn pubkey(&self) -> &PublicKey {
+        &self.pubkey
+    }
+
+    pub fn p2pkh(&self, network: Network) -> String {
+        self.pubkey.to_address(network, AddressKind::P2pkh)
+    }
+
+    pub fn p2sh_p2wpkh(&self, network: Network) -> String {
+        self.pubkey.to_address(network, AddressKind::P2shP2wpkh)
+    }
+
+    pub fn p2wpkh(&self, network: Network) -> String {
+        self.pubkey.to_address(network, AddressKind::P2wpkh)
+    }
+
+    pub fn p2tr(&self, network: Network) -> String {
+        self.pubkey.to_address(network, AddressKind::P2tr)
+    }
+}
+
+impl From<PrivateKey> for Keypair {
+    fn from(privkey: PrivateKey) -> Self {
+        Self::new(privkey)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WifError {
+    InvalidBase58Check(Base58Error),
+    InvalidPrefix(u8),
+    InvalidLength(usize),
+    InvalidCompressionFlag(u8),
+    InvalidSecret,
+}
+
+impl fmt::Display for WifError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WifError::InvalidBase58Check(e) => write!(f, "invalid WIF: {}", e),
+            WifError::InvalidPrefix(prefix) => write!(f, "unknown WIF prefix 0x{:02x}", prefix),
+            WifError::InvalidLength(len) => {
+                write!(f, "WIF payload must be 32 or 33 bytes, got {}", len)
+            }
+            WifError::InvalidCompressionFlag(flag) => {
+                write!(f, "WIF compression flag must be 0x01, got 0x{:02x}", flag)
+            }
+            WifError::InvalidSecret => write!(f, "secret is zero or not below the group order"),
+        }
+    }
+}
+
+impl std::error::Error for WifError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecdsa::Raw;
+    use crate::hashes::Sha256;
+    use crate::hex;
+    use num_traits::Pow;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    fn scalar(s: &str) -> ScalarFieldElement {
+        ScalarFieldElement::new(hex(s)).unwrap()
+    }
+
+    fn book_key() -> PublicKey {
+        let coordinate = |s| Fe256::from_biguint(&hex(s)).unwrap();
+        PublicKey::from_point(
+            S256Point::new(GeneralPoint::finite(
+                coordinate("887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c"),
+                coordinate("61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34"),
+            ))
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_book_signatures() {
+        let key = book_key();
+
+        let z = hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = Signature::new(
+            scalar("ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395"),
+            scalar("68342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4"),
+        );
+        assert!(key.verify(&z, &sig));
+
+        let z = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let sig = Signature::new(
+            scalar("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c"),
+            scalar("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6"),
+        );
+        assert!(key.verify(&z, &sig));
+
+        assert!(!key.verify(&(z + 1u64), &sig));
+        assert!(!key.verify(
+            &hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60"),
+            &sig
+        ));
+    }
+
+    #[test]
+    fn private_keys_compare_in_constant_time() {
+        let low = scalar("0000000000000000a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718");
+        let high = scalar("0000000000000001a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718");
+        let (a, b) = (
+            PrivateKey::new(low).unwrap(),
+            PrivateKey::new(high).unwrap(),
+        );
+
+        // Only the most significant limb differs.
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_ne!(a, b);
+        assert_eq!(a, a.clone());
+    }
+
+    #[test]
+    fn rejects_degenerate_signatures() {
+        let key = book_key();
+        let z = BigUint::from(1u64);
+        let one = ScalarFieldElement::from(1);
+        assert!(!key.verify(&z, &Signature::new(ScalarFieldElement::zero(), one.clone())));
+        assert!(!key.verify(&z, &Signature::new(one, ScalarFieldElement::zero())));
+        assert_eq!(PublicKey::from_point(S256Point::infinity()), None);
+        assert_eq!(PrivateKey::new(ScalarFieldElement::zero()), None);
+    }
+
+    #[test]
+    fn recover_signer() {
+        for secret in [1, 0x1234_5678_9abc] {
+            let key = PrivateKey::new(ScalarFieldElement::from(secret)).unwrap();
+            for z in [1u64, 0xdead_beef] {
+                let z = BigUint::from(z) * hex("ffffffffffffffffffffffffff");
+                let (sig, id) = key.sign_recoverable(&z);
+                assert_eq!(sig, key.sign(&z));
+                assert_eq!(sig.recover(&z, id).as_ref(), Some(key.public_key()));
+                assert_ne!(sig.recover(&z, id ^ 1).as_ref(), Some(key.public_key()));
+            }
+        }
+
+        let key = PrivateKey::new(ScalarFieldElement::from(7)).unwrap();
+        let z = BigUint::from(99u64);
+        let (sig, _) = key.sign_recoverable(&z);
+        assert_eq!(sig.recover(&z, 4), None);
+        // r + n is above p for all but a tiny range of r, so the overflow ids fail.
+        assert_eq!(sig.recover(&z, 2), None);
+    }
+
+    fn key(secret: ScalarFieldElement) -> PublicKey {
+        *PrivateKey::new(secret).unwrap().public_key()
+    }
+
+    // Chapter 4, exercises 2 and 3.
+    #[test]
+    fn sec_book_exercises() {
+        let cases = [
+            (
+                ScalarFieldElement::from(5000),
+                false,
+                "04ffe558e388852f0120e46af2d1b370f85854a8eb0841811ece0e3e03d282d57c315dc72890a4f10a\
+                 1481c031b03b351b0dc79901ca18a00cf009dbdb157a1d10",
+            ),
+            (
+                ScalarFieldElement::from(2018).pow(5u64),
+                false,
+                "04027f3da1918455e03c46f659266a1bb5204e959db7364d2f473bdf8f0a13cc9dff87647fd023c13b\
+                 4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06",
+            ),
+            (
+                scalar("deadbeef12345"),
+                false,
+                "04d90cd625ee87dd38656dd95cf79f65f60f7273b67d3096e68bd81e4f5342691f842efa762fd59961\
+                 d0e99803c61edba8b3e3f7dc3a341836f97733aebf987121",
+            ),
+            (
+                ScalarFieldElement::from(5001),
+                true,
+                "0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1",
+            ),
+            (
+                ScalarFieldElement::from(2019).pow(5u64),
+                true,
+                "02933ec2d2b111b92737ec12f1c5d20f3233a0ad21cd8b36d0bca7a0cfa5cb8701",
+            ),
+            (
+                scalar("deadbeef54321"),
+                true,
+                "0296be5b1292f6c856b3c5654e886fc13511462059089cdf9c479623bfcbe77690",
+            ),
+        ];
+        for (secret, compressed, sec) in cases {
+            let public = key(secret);
+            assert_eq!(public.to_sec(compressed), hex::decode(sec).unwrap());
+            assert_eq!(PublicKey::from_sec(&hex::decode(sec).unwrap()), Ok(public));
+        }
+    }
+
+    #[test]
+    fn sec_rejects_malformed() {
+        let g = key(ScalarFieldElement::from(1));
+        let mut sec = g.to_sec(false);
+        assert_eq!(PublicKey::from_sec(&[]), Err(SecError::Empty));
+        assert_eq!(
+            PublicKey::from_sec(&sec[..33]),
+            Err(SecError::InvalidLength { prefix: 4, len: 33 })
+        );
+        sec[64] ^= 1;
+        assert_eq!(PublicKey::from_sec(&sec), Err(SecError::NotOnCurve));
+        sec[0] = 5;
+        assert_eq!(PublicKey::from_sec(&sec), Err(SecError::InvalidPrefix(5)));
+        // No point has x = 0.
+        assert_eq!(
+            PublicKey::from_sec(&[2; 1].iter().chain(&[0; 32]).copied().collect::<Vec<_>>()),
+            Err(SecError::NotOnCurve)
+        );
+    }
+
+    // Chapter 4, exercise 5, plus a BIP49 nested segwit address for key 1.
+    #[test]
+    fn addresses() {
+        assert_eq!(
+            key(ScalarFieldElement::from(5002))
+                .to_address(Network::Testnet, AddressKind::P2pkhUncompressed),
+            "mmTPbXQFxboEtNRkwfh6K51jvdtHLxGeMA"
+        );
+        assert_eq!(
+            key(ScalarFieldElement::from(2020).pow(5u64))
+                .to_address(Network::Testnet, AddressKind::P2pkh),
+            "mopVkxp8UhXqRYbCYJsbeE1h1fiF64jcoH"
+        );
+        assert_eq!(
+            key(scalar("12345deadbeef")).to_address(Network::Mainnet, AddressKind::P2pkh),
+            "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1"
+        );
+
+        let one = key(ScalarFieldElement::from(1));
+        assert_eq!(
+            one.hash160().to_string(),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+        assert_eq!(
+            one.to_address(Network::Mainnet, AddressKind::P2shP2wpkh),
+            "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"
+        );
+    }
+
+    #[test]
+    fn keypair_addresses() {
+        let pair = Keypair::from(PrivateKey::new(ScalarFieldElement::from(1)).unwrap());
+        assert_eq!(
+            pair.p2pkh(Network::Mainnet),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+        assert_eq!(
+            pair.p2sh_p2wpkh(Network::Mainnet),
+            "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"
+        );
+        assert_eq!(
+            pair.p2wpkh(Network::Mainnet),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            pair.p2wpkh(Network::Testnet),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+        assert_eq!(
+            pair.p2tr(Network::Mainnet),
+            "bc1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5sspknck9"
+        );
+        assert_eq!(
+            pair.p2tr(Network::Testnet),
+            "tb1pmfr3p9j00pfxjh0zmgp99y8zftmd3s5pmedqhyptwy6lm87hf5ssk79hv2"
+        );
+        assert_eq!(pair.pubkey(), pair.privkey().public_key());
+
+        // BIP341 wallet vector: key-path-only output for this internal key.
+        let internal = PublicKey::from_sec(
+            &hex::decode("02d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            internal.to_address(Network::Mainnet, AddressKind::P2tr),
+            "bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5"
+        );
+    }
+
+    // Chapter 4, exercise 6.
+    #[test]
+    fn wif_book_exercises() {
+        let cases = [
+            (
+                ScalarFieldElement::from(5003),
+                Network::Testnet,
+                true,
+                "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN8rFTv2sfUK",
+            ),
+            (
+                ScalarFieldElement::from(2021).pow(5u64),
+                Network::Testnet,
+                false,
+                "91avARGdfge8E4tZfYLoxeJ5sGBdNJQH4kvjpWAxgzczjbCwxic",
+            ),
+            (
+                scalar("54321deadbeef"),
+                Network::Mainnet,
+                true,
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgiuQJv1h8Ytr2S53a",
+            ),
+        ];
+        for (secret, network, compressed, wif) in cases {
+            let key = PrivateKey::new(secret).unwrap();
+            assert_eq!(key.to_wif(network, compressed), wif);
+            assert_eq!(PrivateKey::from_wif(wif), Ok((key, network, compressed)));
+        }
+
+        // Signet and regtest keys are indistinguishable from testnet ones.
+        let key = PrivateKey::new(ScalarFieldElement::from(5003)).unwrap();
+        for network in [Network::Signet, Network::Regtest] {
+            let wif = key.to_wif(network, true);
+            assert_eq!(wif, "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN8rFTv2sfUK");
+            assert_eq!(
+                PrivateKey::from_wif(&wif),
+                Ok((key.clone(), Network::Testnet, true))
+            );
+        }
+    }
+
+    #[test]
+    fn wif_rejects_malformed() {
+        let key = PrivateKey::new(ScalarFieldElement::from(1)).unwrap();
+        let wif =
+            |prefix, payload: &[u8]| PrivateKey::from_wif(&encode_base58check(prefix, payload));
+
+        assert_eq!(
+            wif(0x00, &key.secret().to_bytes_be()),
+            Err(WifError::InvalidPrefix(0x00))
+        );
+        assert_eq!(wif(0x80, &[1; 31]), Err(WifError::InvalidLength(31)));
+        let mut flagged = key.secret().to_bytes_be().to_vec();
+        flagged.push(2);
+        assert_eq!(
+            wif(0x80, &flagged),
+            Err(WifError::InvalidCompressionFlag(2))
+        );
+        assert_eq!(wif(0x80, &[0; 32]), Err(WifError::InvalidSecret));
+        assert_eq!(wif(0x80, &[0xff; 32]), Err(WifError::InvalidSecret));
+        assert!(matches!(
+            PrivateKey::from_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgiuQJv1h8Ytr2S53b"),
+            Err(WifError::InvalidBase58Check(
+                Base58Error::ChecksumMismatch { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn generate_and_from_seed() {
+        let mut rng = StdRng::seed_from_u64(594);
+        let a = PrivateKey::generate(&mut rng);
+        let b = PrivateKey::generate(&mut rng);
+        assert_ne!(a, b);
+        assert!(!a.secret().is_zero());
+
+        let seeded = PrivateKey::from_seed(b"correct horse battery staple");
+        assert_eq!(
+            seeded,
+            PrivateKey::from_seed(b"correct horse battery staple")
+        );
+        assert_ne!(
+            seeded,
+            PrivateKey::from_seed(b"correct horse battery stapler")
+        );
+        assert_eq!(
+            seeded.secret(),
+            &scalar("c4bbcb1fbec99d65bf59d85c8cb62ee2db963f0fe106f483d9afa73bd4e39a8a")
+        );
+    }
+
+    #[test]
+    fn debug_redacts_secret() {
+        let key = PrivateKey::new(ScalarFieldElement::from(0x5ec2e7)).unwrap();
+        assert_eq!(format!("{:?}", key), "PrivateKey(…)");
+        assert_eq!(format!("{:?}", Some(key)), "Some(PrivateKey(…))");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        use zeroize::Zeroize;
+
+        let mut key = PrivateKey::new(ScalarFieldElement::from(0x5ec2e7)).unwrap();
+        key.zeroize();
+        assert!(key.secret().is_zero());
+    }
+
+    #[test]
+    fn sign_is_deterministic_and_verifies() {
+        let key = PrivateKey::new(ScalarFieldElement::from(12345)).unwrap();
+        let z = hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48");
+        let sig = key.sign(&z);
+        assert_eq!(key.sign(&z), sig);
+        assert!(key.public_key().verify(&z, &sig));
+        assert!(!key.public_key().verify(&(z + 1u64), &sig));
+
+        // The well-known secp256k1 RFC 6979 vector: e = 1, z = sha256("Satoshi Nakamoto").
+        let key = PrivateKey::new(ScalarFieldElement::from(1)).unwrap();
+        let z = hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        let sig = key.sign(&z);
+        assert_eq!(
+            sig.r,
+            scalar("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8")
+        );
+        assert_eq!(
+            sig.s,
+            scalar("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5")
+        );
+        assert!(sig.is_low_s());
+
+        // Verification itself accepts either s.
+        let high = Signature::new(sig.r.clone(), -sig.s.clone());
+        assert!(key.public_key().verify(&z, &high));
+
+        // Chapter 3 signs z = hash256(b"Programming Bitcoin!") with e = 12345.
+        let key = PrivateKey::new(ScalarFieldElement::from(12345)).unwrap();
+        let msg = Raw::new(b"Programming Bitcoin!");
+        let z = hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48");
+        assert_eq!(msg.z(), z);
+        assert_eq!(key.sign(&msg), key.sign(&z));
+        assert!(key.public_key().verify(&msg, &key.sign(&z)));
+        assert!(!key.public_key().verify(
+            &Raw::<Sha256>::with_hash(b"Programming Bitcoin!"),
+            &key.sign(&z)
+        ));
+    }
+
+    #[test]
+    fn sign_low_r() {
+        // r above 2^255 without grinding; the first counter fixes it.
+        let key = PrivateKey::new(ScalarFieldElement::from(1)).unwrap();
+        let z = hex("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        assert!(!key.sign(&z).is_low_r());
+        let sig = key.sign_low_r(&z);
+        assert_eq!(
+            sig.r,
+            scalar("3311d51d1326e30774b2fb1fbfd5e199ebccb43be1db2ce41051eb2d75e4b68f")
+        );
+        assert_eq!(
+            sig.s,
+            scalar("44d2ea67486df31a242363de1f835d583620fea148ee422c8c80b904b53f5ac3")
+        );
+        assert!(key.public_key().verify(&z, &sig));
+
+        // Already low: grinding changes nothing.
+        let key = PrivateKey::new(ScalarFieldElement::from(4)).unwrap();
+        assert_eq!(key.sign_low_r(&z), key.sign(&z));
+
+        // Secrets 2, 3 and 5 need three or four tries.
+        for secret in 2..8 {
+            let key = PrivateKey::new(ScalarFieldElement::from(secret)).unwrap();
+            let sig = key.sign_low_r(&z);
+            assert!(sig.is_low_r() && sig.is_low_s());
+            assert!(sig.der().len() <= 70);
+            assert!(key.public_key().verify(&z, &sig));
+        }
+    }
+}
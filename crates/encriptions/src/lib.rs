@@ -1,6 +1,45 @@
+pub mod address;
+#[cfg(feature = "alloc-audit")]
+pub mod alloc_audit;
+pub mod amount;
+pub mod base58;
+pub mod bech32;
+pub mod bip21;
+pub mod bip32;
+pub mod bip39;
+pub mod bip85;
+pub mod block;
 pub mod curve;
-mod field;
+pub mod curve_models;
+pub mod ecdsa;
+pub mod ecies;
+pub mod encode;
+pub mod extension;
+#[cfg(feature = "inexact-f64-field")]
+pub mod f64_field;
+pub mod fe256;
+pub mod field;
+#[cfg(test)]
+mod field_axioms;
+pub mod frost;
+pub mod hashes;
+pub mod hex;
+pub mod keys;
+pub mod message;
+#[cfg(feature = "montgomery")]
+pub mod montgomery;
+pub mod msm;
+pub mod musig2;
+pub mod network;
 pub mod point;
+pub mod rational;
+pub mod schnorr;
+pub mod sha256d;
+pub mod small_field;
+pub mod sss;
+pub mod taproot;
+pub mod twist;
+pub mod varint;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,81 @@
+use crate::ecdsa::{CompactError, RecoverableSignature};
+use crate::hashes::hash256;
+use crate::keys::{AddressKind, PrivateKey};
+use crate::network::Network;
+use crate::varint::encode_varint;
+use num::BigUint;
+
+const MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+// hash256(magic || varint(len) || text), read as a big-endian z.
+pub fn message_hash(text: &str) -> BigUint {
+    let mut data = MAGIC.to_vec();
+    data.extend(encode_varint(text.len() as u64));
+    data.extend(text.as_bytes());
+    BigUint::from_bytes_be(&hash256(&data))
+}
+
+// Signs for the compressed-key address, which is what current wallets use.
+pub fn sign_message(key: &PrivateKey, text: &str) -> String {
+    let (signature, recovery_id) = key.sign_recoverable(&message_hash(text));
+    RecoverableSignature {
+        signature,
+        recovery_id,
+        compressed: true,
+    }
+    .to_base64()
+}
+
+// Recovers the signer and compares its P2PKH address, mainnet or testnet, with `address`.
+pub fn verify_message(address: &str, signature: &str, text: &str) -> Result<bool, CompactError> {
+    let sig = RecoverableSignature::from_base64(signature)?;
+    let key = match sig.recover(&message_hash(text)) {
+        Some(key) => key,
+        None => return Ok(false),
+    };
+    let kind = if sig.compressed {
+        AddressKind::P2pkh
+    } else {
+        AddressKind::P2pkhUncompressed
+    };
+    Ok([Network::Mainnet, Network::Testnet]
+        .iter()
+        .any(|&network| key.to_address(network, kind) == address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::ScalarFieldElement;
+
+    #[test]
+    fn sign_and_verify() {
+        let key = PrivateKey::new(ScalarFieldElement::from(1)).unwrap();
+        let sig = sign_message(&key, "hello");
+        assert_eq!(
+            sig,
+            "IH33YF6i6ooDT6dbZ8TA11L/ks3+zSV0Zxt0DCGNTadBdRSl78b8/9wME9jh+BBVhb5a9EjQ4hTu7nW3/Oa9bm8="
+        );
+        assert_eq!(
+            verify_message("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH", &sig, "hello"),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_message("mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r", &sig, "hello"),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_message("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH", &sig, "hello!"),
+            Ok(false)
+        );
+        // The header says compressed, so the uncompressed-key address does not match.
+        assert_eq!(
+            verify_message("1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm", &sig, "hello"),
+            Ok(false)
+        );
+        assert_eq!(
+            verify_message("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH", "AAAA", "hello"),
+            Err(CompactError::InvalidLength(3))
+        );
+    }
+}
@@ -0,0 +1,428 @@
+use crate::fe256::{adc, mac, sub_limbs};
+use crate::field::{
+    forward_ref_binop, impl_assign_ops, impl_pow_variants, impl_sum_product, reduce_exponent,
+    signed_exponent, Field, FieldError, FiniteFieldElement, Inverse, Prime, PrimeField,
+};
+use num::{BigInt, BigUint, One, Zero};
+use num_traits::Pow;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// Little-endian 64-bit limbs. R = 2^256, so any odd prime of up to 256 bits fits.
+type Limbs = [u64; 4];
+
+pub struct MontgomeryParams {
+    prime: BigUint,
+    modulus: Limbs,
+    // -p^-1 mod 2^64
+    inv: u64,
+    // R mod p, which is one in Montgomery form.
+    r: Limbs,
+    // R^2 mod p, for moving values into Montgomery form.
+    r2: Limbs,
+}
+
+impl MontgomeryParams {
+    // The prime must be odd and at most 256 bits.
+    pub fn new(prime: BigUint) -> Self {
+        assert!(
+            prime.bit(0) && prime.bits() <= 256,
+            "Montgomery form needs an odd prime of at most 256 bits, got {}",
+            prime
+        );
+
+        let modulus = to_limbs(&prime);
+        let r = to_limbs(&((BigUint::one() << 256u32) % &prime));
+        let r2 = to_limbs(&((BigUint::one() << 512u32) % &prime));
+
+        Self {
+            inv: inverse_mod_word(modulus[0]).wrapping_neg(),
+            prime,
+            modulus,
+            r,
+            r2,
+        }
+    }
+
+    fn to_montgomery(&self, value: &BigUint) -> Limbs {
+        self.mul(&to_limbs(value), &self.r2)
+    }
+
+    fn to_canonical(&self, a: &Limbs) -> BigUint {
+        from_limbs(&self.mul(a, &[1, 0, 0, 0]))
+    }
+
+    // CIOS Montgomery multiplication: a·b·R^-1 mod p, interleaving the product with the
+    // reduction so the intermediate never exceeds six limbs.
+    fn mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut t = [0u64; 6];
+        for &b in b {
+            let mut carry = 0;
+            for j in 0..4 {
+                let (v, c) = mac(t[j], a[j], b, carry);
+                t[j] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[4], carry, 0);
+            t[4] = v;
+            t[5] = c;
+
+            // Adding m·p clears the low limb, which is then shifted out.
+            let m = t[0].wrapping_mul(self.inv);
+            let (_, mut carry) = mac(t[0], m, self.modulus[0], 0);
+            for j in 1..4 {
+                let (v, c) = mac(t[j], m, self.modulus[j], carry);
+                t[j - 1] = v;
+                carry = c;
+            }
+            let (v, c) = adc(t[4], carry, 0);
+            t[3] = v;
+            t[4] = t[5] + c;
+        }
+        self.reduce([t[0], t[1], t[2], t[3]], t[4])
+    }
+
+    // Takes a value below 2p, with `high` as its 257th bit, into [0, p).
+    fn reduce(&self, a: Limbs, high: u64) -> Limbs {
+        let (r, borrow) = sub_limbs(a, self.modulus);
+        if high != 0 || borrow == 0 {
+            r
+        } else {
+            a
+        }
+    }
+
+    fn add(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (r, carry) = add_limbs(a, b);
+        self.reduce(r, carry)
+    }
+
+    fn sub(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (r, borrow) = sub_limbs(*a, *b);
+        if borrow != 0 {
+            add_limbs(&r, &self.modulus).0
+        } else {
+            r
+        }
+    }
+}
+
+fn add_limbs(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut r = [0u64; 4];
+    let mut carry = 0;
+    for ((r, a), b) in r.iter_mut().zip(a).zip(b) {
+        let (v, c) = adc(*a, *b, carry);
+        *r = v;
+        carry = c;
+    }
+    (r, carry)
+}
+
+fn to_limbs(value: &BigUint) -> Limbs {
+    let mut limbs = [0u64; 4];
+    for (limb, digit) in limbs.iter_mut().zip(value.iter_u64_digits()) {
+        *limb = digit;
+    }
+    limbs
+}
+
+fn from_limbs(limbs: &Limbs) -> BigUint {
+    let digits: Vec<u32> = limbs
+        .iter()
+        .flat_map(|l| [*l as u32, (*l >> 32) as u32])
+        .collect();
+    BigUint::new(digits)
+}
+
+// Newton iteration for p^-1 mod 2^64, valid because p is odd. Each step doubles the number
+// of correct low bits.
+fn inverse_mod_word(p: u64) -> u64 {
+    let mut inv = 1u64;
+    for _ in 0..6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+    }
+    inv
+}
+
+// A prime with its Montgomery constants computed once. `def_prime_struct!` implements this with
+// a static OnceLock per prime; hand-written primes can do the same.
+pub trait MontgomeryPrime: Prime {
+    fn params() -> &'static MontgomeryParams;
+}
+
+// x·R mod p in fixed-width limbs, so multiplication needs neither a division nor an allocation.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MontgomeryFieldElement<P: Prime>(Limbs, PhantomData<P>);
+
+impl<P: Prime> Clone for MontgomeryFieldElement<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Prime> Copy for MontgomeryFieldElement<P> {}
+
+impl<P: MontgomeryPrime + 'static> MontgomeryFieldElement<P> {
+    pub fn to_field_element(&self) -> FiniteFieldElement<P> {
+        FiniteFieldElement::new(P::params().to_canonical(&self.0)).unwrap()
+    }
+
+    fn square(self) -> Self {
+        self * self
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> From<FiniteFieldElement<P>> for MontgomeryFieldElement<P> {
+    fn from(v: FiniteFieldElement<P>) -> Self {
+        Self(P::params().to_montgomery(v.value()), PhantomData)
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> From<i64> for MontgomeryFieldElement<P> {
+    fn from(v: i64) -> Self {
+        Self::from(FiniteFieldElement::<P>::from(v))
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> FromStr for MontgomeryFieldElement<P> {
+    type Err = FieldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<FiniteFieldElement<P>>().map(Self::from)
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> Add for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(P::params().add(&self.0, &rhs.0), PhantomData)
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> Sub for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(P::params().sub(&self.0, &rhs.0), PhantomData)
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> Mul for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(P::params().mul(&self.0, &rhs.0), PhantomData)
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> Div for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse().expect("division by zero")
+    }
+}
+
+forward_ref_binop!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>, Add, add);
+forward_ref_binop!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>, Sub, sub);
+forward_ref_binop!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>, Mul, mul);
+forward_ref_binop!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>, Div, div);
+impl_assign_ops!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>);
+impl_sum_product!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>);
+impl_pow_variants!([P: MontgomeryPrime + 'static] MontgomeryFieldElement<P>);
+
+impl<P: MontgomeryPrime + 'static> Neg for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> Inverse for MontgomeryFieldElement<P> {
+    fn inverse(&self) -> Result<Self, FieldError> {
+        self.to_field_element().inverse().map(Self::from)
+    }
+}
+
+impl<'a, P: MontgomeryPrime + 'static> Pow<&'a BigInt> for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        self.pow(&signed_exponent(rhs, &P::params().prime))
+    }
+}
+
+impl<'a, P: MontgomeryPrime + 'static> Pow<&'a BigUint> for MontgomeryFieldElement<P> {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        let exponent = reduce_exponent(rhs, &P::params().prime);
+
+        let mut result = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            result = result.square();
+            if exponent.bit(i) {
+                result *= self;
+            }
+        }
+        result
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> Zero for MontgomeryFieldElement<P> {
+    fn zero() -> Self {
+        Self([0; 4], PhantomData)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+}
+
+impl<P: MontgomeryPrime + 'static> One for MontgomeryFieldElement<P> {
+    fn one() -> Self {
+        Self(P::params().r, PhantomData)
+    }
+}
+
+impl<P: MontgomeryPrime + Clone + PartialEq + 'static> Field for MontgomeryFieldElement<P> {}
+
+impl<P: MontgomeryPrime + Clone + PartialEq + 'static> PrimeField for MontgomeryFieldElement<P> {
+    fn modulus() -> BigUint {
+        P::params().prime.clone()
+    }
+
+    fn num_bytes() -> usize {
+        P::num_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::MontgomerySecp256k1;
+    use crate::fe256::Fe256;
+    use crate::field::{Prime13, Prime223, Secp256k1Prime};
+    use crate::field_axioms::{any_element, check_fermat_identity, check_field_axioms};
+    use crate::point::{GeneralPoint, PointOnCurve, S256Point};
+    use proptest::prelude::*;
+    use std::sync::OnceLock;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Prime2p61;
+
+    impl Prime for Prime2p61 {
+        fn get_prime() -> BigUint {
+            BigUint::from(2305843009213693951u64)
+        }
+    }
+
+    impl MontgomeryPrime for Prime2p61 {
+        fn params() -> &'static MontgomeryParams {
+            static PARAMS: OnceLock<MontgomeryParams> = OnceLock::new();
+            PARAMS.get_or_init(|| MontgomeryParams::new(Self::get_prime()))
+        }
+    }
+
+    fn element<P: Prime + 'static>(v: u64) -> FiniteFieldElement<P> {
+        FiniteFieldElement::new(BigUint::from(v) % P::get_prime()).unwrap()
+    }
+
+    fn wide<P: Prime + 'static>(limbs: [u64; 4]) -> FiniteFieldElement<P> {
+        FiniteFieldElement::new(from_limbs(&limbs) % P::get_prime()).unwrap()
+    }
+
+    fn assert_equivalent<P: MontgomeryPrime + Clone + PartialEq + std::fmt::Debug + 'static>(
+        fa: FiniteFieldElement<P>,
+        fb: FiniteFieldElement<P>,
+        e: i64,
+    ) {
+        let (ma, mb) = (
+            MontgomeryFieldElement::from(fa.clone()),
+            MontgomeryFieldElement::from(fb.clone()),
+        );
+
+        assert_eq!(ma.to_field_element(), fa);
+        assert_eq!((ma + mb).to_field_element(), fa.clone() + fb.clone());
+        assert_eq!((ma - mb).to_field_element(), fa.clone() - fb.clone());
+        assert_eq!((-ma).to_field_element(), -fa.clone());
+        assert_eq!((ma * mb).to_field_element(), fa.clone() * fb.clone());
+        assert_eq!(
+            ma.pow(BigInt::from(e)).to_field_element(),
+            fa.clone().pow(BigInt::from(e))
+        );
+        if !fb.is_zero() {
+            assert_eq!((ma / mb).to_field_element(), fa / fb);
+        }
+    }
+
+    #[test]
+    fn scalar_multiplication_matches_fe256() {
+        let lift = |v: Fe256| {
+            MontgomeryFieldElement::<Secp256k1Prime>::from(
+                FiniteFieldElement::new(v.to_biguint()).unwrap(),
+            )
+        };
+        let g = S256Point::generator();
+        let mont_g = PointOnCurve::<_, MontgomerySecp256k1>::new(GeneralPoint::finite(
+            lift(g.x().unwrap()),
+            lift(g.y().unwrap()),
+        ))
+        .unwrap();
+
+        let k = BigInt::from(2).pow(200u32) - 12345;
+        let expected = g.mul_wnaf(&k);
+        let actual = mont_g.mul_wnaf(&k);
+        assert_eq!(actual.x(), expected.x().map(lift));
+        assert_eq!(actual.y(), expected.y().map(lift));
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms_prime13(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<MontgomeryFieldElement<Prime13>>(a, b, c)?;
+        }
+
+        #[test]
+        fn field_axioms_prime223(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<MontgomeryFieldElement<Prime223>>(a, b, c)?;
+        }
+
+        #[test]
+        fn field_axioms_prime2p61(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<MontgomeryFieldElement<Prime2p61>>(a, b, c)?;
+            check_fermat_identity::<MontgomeryFieldElement<Prime2p61>>(a, &Prime2p61::get_prime())?;
+        }
+
+        #[test]
+        fn field_axioms_secp256k1(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<MontgomeryFieldElement<Secp256k1Prime>>(a, b, c)?;
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn montgomery_matches_prime13(a: u64, b: u64, e: i64) {
+            assert_equivalent(element::<Prime13>(a), element(b), e);
+        }
+
+        #[test]
+        fn montgomery_matches_prime223(a: u64, b: u64, e: i64) {
+            assert_equivalent(element::<Prime223>(a), element(b), e);
+        }
+
+        #[test]
+        fn montgomery_matches_prime2p61(a: u64, b: u64, e: i64) {
+            assert_equivalent(element::<Prime2p61>(a), element(b), e);
+        }
+
+        #[test]
+        fn montgomery_matches_secp256k1(a: [u64; 4], b: [u64; 4], e: i64) {
+            assert_equivalent(wide::<Secp256k1Prime>(a), wide(b), e);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use crate::curve::EllipticCurve;
+use crate::field::{Field, FieldRef};
+use crate::point::PointOnCurve;
+use num::{BigInt, BigUint, Signed};
+
+const STRAUSS_WINDOW: u64 = 4;
+pub const PIPPENGER_THRESHOLD: usize = 128;
+
+pub fn multi_scalar_mul<T: Field, C: EllipticCurve<CurveField = T>>(
+    terms: &[(BigInt, PointOnCurve<T, C>)],
+) -> PointOnCurve<T, C>
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
+    if terms.len() < PIPPENGER_THRESHOLD {
+        strauss(terms)
+    } else {
+        pippenger(terms)
+    }
+}
+
+fn normalize<T: Field, C: EllipticCurve<CurveField = T>>(
+    terms: &[(BigInt, PointOnCurve<T, C>)],
+) -> Vec<(BigUint, PointOnCurve<T, C>)> {
+    terms
+        .iter()
+        .map(|(k, p)| {
+            if k.is_negative() {
+                ((-k).to_biguint().unwrap(), -(p.clone()))
+            } else {
+                (k.to_biguint().unwrap(), p.clone())
+            }
+        })
+        .collect()
+}
+
+fn window(k: &BigUint, start: u64, width: u64) -> usize {
+    (0..width)
+        .filter(|i| k.bit(start + i))
+        .map(|i| 1 << i)
+        .sum()
+}
+
+fn double_times<T: Field, C: EllipticCurve<CurveField = T>>(
+    mut p: PointOnCurve<T, C>,
+    times: u64,
+) -> PointOnCurve<T, C>
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
+    for _ in 0..times {
+        p = p.clone() + p;
+    }
+    p
+}
+
+pub fn strauss<T: Field, C: EllipticCurve<CurveField = T>>(
+    terms: &[(BigInt, PointOnCurve<T, C>)],
+) -> PointOnCurve<T, C>
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
+    let terms = normalize(terms);
+    let max_bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+
+    let tables: Vec<Vec<PointOnCurve<T, C>>> = terms
+        .iter()
+        .map(|(_, p)| {
+            let mut table = vec![PointOnCurve::infinity()];
+            for i in 1..(1 << STRAUSS_WINDOW) {
+                let next = table[i - 1].clone() + p.clone();
+                table.push(next);
+            }
+            table
+        })
+        .collect();
+
+    let mut result = PointOnCurve::infinity();
+    for w in (0..max_bits.div_ceil(STRAUSS_WINDOW)).rev() {
+        result = double_times(result, STRAUSS_WINDOW);
+        for ((k, _), table) in terms.iter().zip(&tables) {
+            let digit = window(k, w * STRAUSS_WINDOW, STRAUSS_WINDOW);
+            if digit != 0 {
+                result = result + table[digit].clone();
+            }
+        }
+    }
+    result
+}
+
+// Roughly log2(n) - 2, which balances bucket accumulation against the bucket sums.
+fn pippenger_window(n: usize) -> u64 {
+    ((usize::BITS - n.leading_zeros()) as u64)
+        .saturating_sub(3)
+        .max(1)
+}
+
+pub fn pippenger<T: Field, C: EllipticCurve<CurveField = T>>(
+    terms: &[(BigInt, PointOnCurve<T, C>)],
+) -> PointOnCurve<T, C>
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
+    let terms = normalize(terms);
+    let max_bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+    let c = pippenger_window(terms.len());
+
+    let mut result = PointOnCurve::infinity();
+    for w in (0..max_bits.div_ceil(c)).rev() {
+        result = double_times(result, c);
+
+        let mut buckets = vec![PointOnCurve::infinity(); (1 << c) - 1];
+        for (k, p) in &terms {
+            let digit = window(k, w * c, c);
+            if digit != 0 {
+                buckets[digit - 1] = buckets[digit - 1].clone() + p.clone();
+            }
+        }
+
+        // sum_i i * B_i computed as a suffix sum of suffix sums.
+        let mut running = PointOnCurve::infinity();
+        let mut sum = PointOnCurve::infinity();
+        for bucket in buckets.into_iter().rev() {
+            running = running + bucket;
+            sum = sum + running.clone();
+        }
+        result = result + sum;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{OverField, Secp256k1};
+    use crate::field::Prime223;
+    use crate::point::GeneralPoint;
+    use crate::small_field::SmallPrimeField;
+    use proptest::prelude::*;
+
+    type Point223 =
+        PointOnCurve<SmallPrimeField<Prime223>, OverField<Secp256k1, SmallPrimeField<Prime223>>>;
+
+    fn point(x: i64, y: i64) -> Point223 {
+        Point223::new(GeneralPoint::finite(
+            SmallPrimeField::from(x),
+            SmallPrimeField::from(y),
+        ))
+        .unwrap()
+    }
+
+    fn naive(terms: &[(BigInt, Point223)]) -> Point223 {
+        terms
+            .iter()
+            .fold(Point223::infinity(), |acc, (k, p)| acc + p.mul_naive(k))
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(
+            multi_scalar_mul::<_, _>(&[] as &[(BigInt, Point223)]),
+            Point223::infinity()
+        );
+        assert_eq!(
+            pippenger(&[] as &[(BigInt, Point223)]),
+            Point223::infinity()
+        );
+    }
+
+    #[test]
+    fn large_input_uses_pippenger() {
+        let terms: Vec<(BigInt, Point223)> = (0..PIPPENGER_THRESHOLD as i64 + 2)
+            .map(|i| (BigInt::from(i * 7 - 300), point(47, 71)))
+            .collect();
+        let expected = point(47, 71).mul_naive(&terms.iter().map(|(k, _)| k).sum());
+
+        assert_eq!(multi_scalar_mul(&terms), expected);
+        assert_eq!(strauss(&terms), expected);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn strauss_and_pippenger_match_naive(scalars in prop::collection::vec(any::<i64>(), 1..48)) {
+            let points = [point(47, 71), point(143, 98), point(192, 105), point(17, 56)];
+            let terms: Vec<(BigInt, Point223)> = scalars
+                .iter()
+                .zip(points.iter().cycle())
+                .map(|(k, p)| (BigInt::from(*k), *p))
+                .collect();
+
+            let expected = naive(&terms);
+            prop_assert_eq!(strauss(&terms), expected);
+            prop_assert_eq!(pippenger(&terms), expected);
+            prop_assert_eq!(multi_scalar_mul(&terms), expected);
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use crate::field::ScalarFieldElement;
+use crate::hashes::tagged_hash_parts;
+use crate::keys::{PrivateKey, PublicKey};
+use crate::point::S256Point;
+use crate::schnorr::challenge;
+use crate::taproot::hash_to_scalar;
+use num::{BigInt, Zero};
+use rand_core::{CryptoRng, RngCore};
+use std::fmt;
+
+// MuSig2 as specified in BIP327, without tweaking: n signers agree on an aggregate x-only key,
+// exchange two nonces each, and their partial signatures add up to one BIP340 signature.
+
+fn times(k: &ScalarFieldElement, point: S256Point) -> S256Point {
+    BigInt::from(k.value().clone()) * point
+}
+
+// cbytes_ext: the compressed encoding, with 33 zero bytes standing in for infinity.
+fn cbytes_ext(point: &S256Point) -> Vec<u8> {
+    PublicKey::from_point(*point)
+        .map(|key| key.to_sec(true))
+        .unwrap_or_else(|| vec![0; 33])
+}
+
+pub(crate) fn nonzero_scalar(rng: &mut impl RngCore) -> ScalarFieldElement {
+    loop {
+        let k = ScalarFieldElement::random(rng);
+        if !k.is_zero() {
+            return k;
+        }
+    }
+}
+
+fn has_even_y(point: &S256Point) -> bool {
+    point.y().is_some_and(|y| !y.is_odd())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyAggContext {
+    keys: Vec<PublicKey>,
+    coefficients: Vec<ScalarFieldElement>,
+    q: S256Point,
+}
+
+impl KeyAggContext {
+    // Q = Σ a_i·P_i with a_i = hash_KeyAgg coefficient(L || P_i), except that the first key
+    // differing from keys[0] gets a_i = 1. Keys are used in the given order; sort them first for
+    // an order-independent aggregate. None if Q is the point at infinity.
+    pub fn new(keys: &[PublicKey]) -> Option<Self> {
+        let encoded: Vec<Vec<u8>> = keys.iter().map(|key| key.to_sec(true)).collect();
+        let parts: Vec<&[u8]> = encoded.iter().map(Vec::as_slice).collect();
+        let list_hash = tagged_hash_parts("KeyAgg list", &parts);
+        let second = encoded.iter().find(|key| *key != encoded.first().unwrap());
+
+        let coefficients: Vec<ScalarFieldElement> = encoded
+            .iter()
+            .map(|key| {
+                if Some(key) == second {
+                    ScalarFieldElement::from(1)
+                } else {
+                    hash_to_scalar("KeyAgg coefficient", &[&list_hash, key])
+                }
+            })
+            .collect();
+        let q = keys
+            .iter()
+            .zip(&coefficients)
+            .map(|(key, a)| times(a, *key.point()))
+            .fold(S256Point::infinity(), |acc, p| acc + p);
+        q.x()?;
+
+        Some(Self {
+            keys: keys.to_vec(),
+            coefficients,
+            q,
+        })
+    }
+
+    pub fn aggregate_key(&self) -> [u8; 32] {
+        self.q.x().unwrap().to_bytes_be()
+    }
+
+    fn coefficient(&self, key: &PublicKey) -> Option<&ScalarFieldElement> {
+        self.keys
+            .iter()
+            .position(|k| k == key)
+            .map(|i| &self.coefficients[i])
+    }
+
+    // g = -1 when Q has an odd y, since BIP340 signs for the even-y lift of x(Q).
+    fn g(&self) -> ScalarFieldElement {
+        if has_even_y(&self.q) {
+            ScalarFieldElement::from(1)
+        } else {
+            ScalarFieldElement::from(-1)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PubNonce {
+    r1: S256Point,
+    r2: S256Point,
+}
+
+// Deliberately neither Clone nor Copy: signing consumes it, because using the same nonce for two
+// different sessions reveals the secret key.
+pub struct SecNonce {
+    k1: ScalarFieldElement,
+    k2: ScalarFieldElement,
+    public: PubNonce,
+}
+
+// BIP327 NonceGen. The random bytes are masked with the secret key and hashed together with the
+// signer's key, the aggregate key and the message, so a weak RNG on its own does not repeat a
+// nonce. `generate` passes no extra input.
+fn nonce_gen(
+    rand: &[u8; 32],
+    secret: &[u8; 32],
+    public: &[u8],
+    aggregate: &[u8; 32],
+    msg: &[u8],
+    extra_in: &[u8],
+) -> (ScalarFieldElement, ScalarFieldElement) {
+    let aux = tagged_hash_parts("MuSig/aux", &[rand]);
+    let mut masked = *secret;
+    for (m, a) in masked.iter_mut().zip(aux) {
+        *m ^= a;
+    }
+    let msg_len = (msg.len() as u64).to_be_bytes();
+    let extra_len = (extra_in.len() as u32).to_be_bytes();
+    let k = |i: u8| {
+        hash_to_scalar(
+            "MuSig/nonce",
+            &[
+                &masked,
+                &[public.len() as u8],
+                public,
+                &[aggregate.len() as u8],
+                aggregate,
+                &[1],
+                &msg_len,
+                msg,
+                &extra_len,
+                extra_in,
+                &[i],
+            ],
+        )
+    };
+    (k(0), k(1))
+}
+
+impl SecNonce {
+    pub fn generate(
+        rng: &mut (impl RngCore + CryptoRng),
+        key: &PrivateKey,
+        ctx: &KeyAggContext,
+        msg: &[u8],
+    ) -> Self {
+        let (secret, public) = (key.secret().to_bytes_be(), key.public_key().to_sec(true));
+        let (k1, k2) = loop {
+            let mut rand = [0u8; 32];
+            rng.fill_bytes(&mut rand);
+            let (k1, k2) = nonce_gen(&rand, &secret, &public, &ctx.aggregate_key(), msg, &[]);
+            if !k1.is_zero() && !k2.is_zero() {
+                break (k1, k2);
+            }
+        };
+        let public = PubNonce {
+            r1: &k1 * S256Point::generator(),
+            r2: &k2 * S256Point::generator(),
+        };
+        Self { k1, k2, public }
+    }
+
+    pub fn public(&self) -> PubNonce {
+        self.public
+    }
+}
+
+impl fmt::Debug for SecNonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecNonce(…)")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecNonce {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.k1.zeroize();
+        self.k2.zeroize();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AggNonce {
+    r1: S256Point,
+    r2: S256Point,
+}
+
+pub fn aggregate_nonces(nonces: &[PubNonce]) -> AggNonce {
+    nonces.iter().fold(
+        AggNonce {
+            r1: S256Point::infinity(),
+            r2: S256Point::infinity(),
+        },
+        |acc, n| AggNonce {
+            r1: acc.r1 + n.r1,
+            r2: acc.r2 + n.r2,
+        },
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    b: ScalarFieldElement,
+    r: S256Point,
+    e: ScalarFieldElement,
+}
+
+impl Session {
+    // b = hash_MuSig/noncecoef(aggnonce || x(Q) || m), R = R1 + b·R2 (G if that is infinity), and
+    // e is the BIP340 challenge for R, Q and m.
+    pub fn new(ctx: &KeyAggContext, nonce: &AggNonce, msg: &[u8]) -> Self {
+        let q = ctx.aggregate_key();
+        let b = hash_to_scalar(
+            "MuSig/noncecoef",
+            &[&cbytes_ext(&nonce.r1), &cbytes_ext(&nonce.r2), &q, msg],
+        );
+        let r = nonce.r1 + times(&b, nonce.r2);
+        let r = if r.x().is_some() {
+            r
+        } else {
+            S256Point::generator()
+        };
+        let e = challenge(&r.x().unwrap().to_bytes_be(), &q, msg);
+        Self { b, r, e }
+    }
+
+    // s_i = k1 + b·k2 + e·a_i·g·d_i, with both nonces negated when R has an odd y. None if the
+    // key is not part of the aggregate.
+    pub fn partial_sign(
+        &self,
+        ctx: &KeyAggContext,
+        nonce: SecNonce,
+        key: &PrivateKey,
+    ) -> Option<ScalarFieldElement> {
+        let a = ctx.coefficient(key.public_key())?;
+        let (k1, k2) = if has_even_y(&self.r) {
+            (nonce.k1.clone(), nonce.k2.clone())
+        } else {
+            (-nonce.k1.clone(), -nonce.k2.clone())
+        };
+        Some(&(&k1 + &(&self.b * &k2)) + &(&(&self.e * a) * &(&ctx.g() * key.secret())))
+    }
+
+    // s_i·G = R1_i + b·R2_i (negated with R) + e·a_i·g·P_i.
+    pub fn partial_verify(
+        &self,
+        ctx: &KeyAggContext,
+        nonce: &PubNonce,
+        key: &PublicKey,
+        partial: &ScalarFieldElement,
+    ) -> bool {
+        let a = match ctx.coefficient(key) {
+            Some(a) => a,
+            None => return false,
+        };
+        let r = nonce.r1 + times(&self.b, nonce.r2);
+        let r = if has_even_y(&self.r) { r } else { -r };
+        let expected = r + times(&(&(&self.e * a) * &ctx.g()), *key.point());
+        partial * S256Point::generator() == expected
+    }
+
+    // The BIP340 signature x(R) || Σ s_i.
+    pub fn aggregate(&self, partials: &[ScalarFieldElement]) -> [u8; 64] {
+        let s = partials
+            .iter()
+            .fold(ScalarFieldElement::zero(), |acc, s| &acc + s);
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&self.r.x().unwrap().to_bytes_be());
+        sig[32..].copy_from_slice(&s.to_bytes_be());
+        sig
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fe256::Fe256;
+    use crate::hex;
+    use crate::schnorr;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::convert::TryInto;
+
+    #[test]
+    fn three_signers() {
+        let mut rng = StdRng::seed_from_u64(585);
+        let keys: Vec<PrivateKey> = (0..3)
+            .map(|_| PrivateKey::new(ScalarFieldElement::random(&mut rng)).unwrap())
+            .collect();
+        let public: Vec<PublicKey> = keys.iter().map(|k| *k.public_key()).collect();
+        let ctx = KeyAggContext::new(&public).unwrap();
+        let msg = b"three parties, one signature";
+
+        // Round one: everyone publishes a nonce pair.
+        let secnonces: Vec<SecNonce> = keys
+            .iter()
+            .map(|key| SecNonce::generate(&mut rng, key, &ctx, msg))
+            .collect();
+        let pubnonces: Vec<PubNonce> = secnonces.iter().map(SecNonce::public).collect();
+        let session = Session::new(&ctx, &aggregate_nonces(&pubnonces), msg);
+
+        // Round two: partial signatures, each checked before aggregation.
+        let partials: Vec<ScalarFieldElement> = secnonces
+            .into_iter()
+            .zip(&keys)
+            .map(|(nonce, key)| session.partial_sign(&ctx, nonce, key).unwrap())
+            .collect();
+        for ((nonce, key), partial) in pubnonces.iter().zip(&public).zip(&partials) {
+            assert!(session.partial_verify(&ctx, nonce, key, partial));
+        }
+        assert!(!session.partial_verify(&ctx, &pubnonces[0], &public[1], &partials[0]));
+
+        let sig = session.aggregate(&partials);
+        assert!(schnorr::verify(&ctx.aggregate_key(), msg, &sig));
+        assert!(!schnorr::verify(
+            &ctx.aggregate_key(),
+            b"another message",
+            &sig
+        ));
+        assert!(!schnorr::verify(
+            &ctx.aggregate_key(),
+            msg,
+            &session.aggregate(&partials[..2])
+        ));
+    }
+
+    // key_agg_vectors.json from BIP327, first valid case.
+    #[test]
+    fn bip327_key_agg_vector() {
+        let key = |sec: &str| {
+            let bytes = hex::decode(sec).unwrap();
+            let x = Fe256::from_bytes_be(bytes[1..].try_into().unwrap()).unwrap();
+            PublicKey::from_point(S256Point::lift_x(x, bytes[0] == 3).unwrap()).unwrap()
+        };
+        let keys = [
+            key("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            key("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+            key("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"),
+        ];
+        let q = KeyAggContext::new(&keys).unwrap().aggregate_key();
+        assert_eq!(
+            hex::encode(&q).to_uppercase(),
+            "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C"
+        );
+    }
+
+    // nonce_gen_vectors.json from BIP327, first case.
+    #[test]
+    fn bip327_nonce_gen_vector() {
+        let bytes = |s: &str| hex::decode(s).unwrap();
+        let fixed = |s: &str| -> [u8; 32] { bytes(s).try_into().unwrap() };
+        let (k1, k2) = nonce_gen(
+            &fixed("0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F0F"),
+            &fixed("0202020202020202020202020202020202020202020202020202020202020202"),
+            &bytes("024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766"),
+            &fixed("0707070707070707070707070707070707070707070707070707070707070707"),
+            &bytes("0101010101010101010101010101010101010101010101010101010101010101"),
+            &bytes("0808080808080808080808080808080808080808080808080808080808080808"),
+        );
+        assert_eq!(
+            hex::encode(&k1.to_bytes_be()).to_uppercase(),
+            "B114E502BEAA4E301DD08A50264172C84E41650E6CB726B410C0694D59EFFB64"
+        );
+        assert_eq!(
+            hex::encode(&k2.to_bytes_be()).to_uppercase(),
+            "95B5CAF28D045B973D63E3C99A44B807BDE375FD6CB39E46DC4A511708D0E9D2"
+        );
+    }
+
+    #[test]
+    fn key_aggregation() {
+        let key = |secret| {
+            *PrivateKey::new(ScalarFieldElement::from(secret))
+                .unwrap()
+                .public_key()
+        };
+        let (a, b) = (key(1), key(2));
+
+        // The second distinct key has coefficient 1, and order matters.
+        let ctx = KeyAggContext::new(&[a, b, a]).unwrap();
+        assert_eq!(ctx.coefficient(&b), Some(&ScalarFieldElement::from(1)));
+        assert_ne!(
+            KeyAggContext::new(&[a, b]).unwrap().aggregate_key(),
+            KeyAggContext::new(&[b, a]).unwrap().aggregate_key()
+        );
+
+        let outsider = PrivateKey::new(ScalarFieldElement::from(3)).unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+        let nonce = SecNonce::generate(&mut rng, &outsider, &ctx, b"m");
+        let session = Session::new(&ctx, &aggregate_nonces(&[nonce.public()]), b"m");
+        assert_eq!(session.partial_sign(&ctx, nonce, &outsider), None);
+    }
+}
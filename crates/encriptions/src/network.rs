@@ -0,0 +1,77 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl Network {
+    pub const ALL: [Network; 4] = [
+        Network::Mainnet,
+        Network::Testnet,
+        Network::Signet,
+        Network::Regtest,
+    ];
+
+    // Signet and regtest reuse testnet's WIF, address and extended key prefixes, so anything
+    // decoded from Base58 can only tell mainnet from testnet.
+    pub fn base58_network(self) -> Network {
+        match self {
+            Network::Mainnet => Network::Mainnet,
+            _ => Network::Testnet,
+        }
+    }
+
+    pub fn wif_prefix(self) -> u8 {
+        match self.base58_network() {
+            Network::Mainnet => 0x80,
+            _ => 0xef,
+        }
+    }
+
+    pub fn p2pkh_prefix(self) -> u8 {
+        match self.base58_network() {
+            Network::Mainnet => 0x00,
+            _ => 0x6f,
+        }
+    }
+
+    pub fn p2sh_prefix(self) -> u8 {
+        match self.base58_network() {
+            Network::Mainnet => 0x05,
+            _ => 0xc4,
+        }
+    }
+
+    // The human-readable part of segwit addresses. Signet shares testnet's.
+    pub fn bech32_hrp(self) -> &'static str {
+        match self {
+            Network::Mainnet => "bc",
+            Network::Testnet | Network::Signet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+
+    // The first four bytes of every p2p message; signet's is for the default signet.
+    pub fn magic(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0xf9, 0xbe, 0xb4, 0xd9],
+            Network::Testnet => [0x0b, 0x11, 0x09, 0x07],
+            Network::Signet => [0x0a, 0x03, 0xcf, 0x40],
+            Network::Regtest => [0xfa, 0xbf, 0xb5, 0xda],
+        }
+    }
+
+    pub fn from_wif_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            0x80 => Some(Network::Mainnet),
+            0xef => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
+    pub fn from_magic(magic: [u8; 4]) -> Option<Self> {
+        Network::ALL.iter().copied().find(|n| n.magic() == magic)
+    }
+}
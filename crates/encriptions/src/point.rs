@@ -1,8 +1,10 @@
-use crate::curve::EllipticCurve;
-use crate::field::Field;
-use num::{BigInt, Float};
+use crate::curve::{CurveError, CurveGroup, EllipticCurve, Secp256k1};
+use crate::fe256::Fe256;
+use crate::field::{Field, FieldRef, ScalarFieldElement};
+use num::{BigInt, Integer, Signed, ToPrimitive};
+use std::cmp::Ordering;
 use std::marker::PhantomData;
-use std::ops::{Add, Mul};
+use std::ops::{Add, Mul, Neg};
 
 pub trait Point<T> {
     fn x(&self) -> Option<T>;
@@ -18,13 +20,13 @@ pub enum GeneralPoint<T> {
     Infinite,
 }
 
-impl<'a, T: Field> GeneralPoint<T> {
+impl<T: Field> GeneralPoint<T> {
     pub fn finite(x: T, y: T) -> Self {
         Self::Finite { x, y }
     }
 }
 
-impl<'a, T: Field + Clone> Point<T> for GeneralPoint<T> {
+impl<T: Field> Point<T> for GeneralPoint<T> {
     fn x(&self) -> Option<T> {
         match self {
             Self::Finite { x, .. } => Some((*x).clone()),
@@ -40,12 +42,41 @@ impl<'a, T: Field + Clone> Point<T> for GeneralPoint<T> {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct PointOnCurve<T, C: EllipticCurve<T>>(GeneralPoint<T>, PhantomData<fn() -> C>);
+#[derive(Debug)]
+pub struct PointOnCurve<T, C: EllipticCurve<CurveField = T>>(
+    GeneralPoint<T>,
+    PhantomData<fn() -> C>,
+);
+
+impl<T: Clone, C: EllipticCurve<CurveField = T>> Clone for PointOnCurve<T, C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: Copy, C: EllipticCurve<CurveField = T>> Copy for PointOnCurve<T, C> {}
+
+impl<T: PartialEq, C: EllipticCurve<CurveField = T>> PartialEq for PointOnCurve<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+pub type S256Point = PointOnCurve<Fe256, Secp256k1>;
 
-impl<'a, T: Field + Clone, C: EllipticCurve<T>> PointOnCurve<T, C> {
+impl<T: Field, C: EllipticCurve<CurveField = T>> PointOnCurve<T, C> {
     pub fn new(point: GeneralPoint<T>) -> Option<Self> {
-        C::on(&point).then(|| Self(point, PhantomData))
+        Self::try_new(point).ok()
+    }
+
+    pub fn try_new(point: GeneralPoint<T>) -> Result<Self, CurveError> {
+        if !C::is_valid() {
+            return Err(CurveError::Singular);
+        }
+        if !C::on(&point) {
+            return Err(CurveError::NotOnCurve);
+        }
+        Ok(Self(point, PhantomData))
     }
 
     pub fn x(&self) -> Option<T> {
@@ -57,7 +88,22 @@ impl<'a, T: Field + Clone, C: EllipticCurve<T>> PointOnCurve<T, C> {
     }
 }
 
-impl<T: Field + Clone, C: EllipticCurve<T>> Point<T> for PointOnCurve<T, C> {
+impl S256Point {
+    // The point with this x and the requested y parity, if x³ + 7 is a square.
+    pub fn lift_x(x: Fe256, odd: bool) -> Option<Self> {
+        let y = (x * x * x + Secp256k1::b()).sqrt()?;
+        let y = if y.is_odd() == odd { y } else { -y };
+        Self::new(GeneralPoint::finite(x, y))
+    }
+}
+
+impl<T: Field, C: CurveGroup<CurveField = T>> PointOnCurve<T, C> {
+    pub fn generator() -> Self {
+        Self::new(C::generator()).expect("generator is on the curve")
+    }
+}
+
+impl<T: Field, C: EllipticCurve<CurveField = T>> Point<T> for PointOnCurve<T, C> {
     fn x(&self) -> Option<T> {
         self.0.x()
     }
@@ -67,15 +113,132 @@ impl<T: Field + Clone, C: EllipticCurve<T>> Point<T> for PointOnCurve<T, C> {
     }
 }
 
-impl<T: Field<Output = T> + Clone, C: EllipticCurve<T>> Mul<PointOnCurve<T, C>> for BigInt {
+const WNAF_WIDTH: u32 = 4;
+
+fn wnaf_digits(k: &BigInt, width: u32) -> Vec<i64> {
+    let modulus = 1i64 << width;
+    let half = modulus >> 1;
+
+    let mut k = k.clone();
+    let mut digits = vec![];
+    while k.is_positive() {
+        let digit = if k.is_odd() {
+            let m = (&k % modulus).to_i64().unwrap();
+            let d = if m >= half { m - modulus } else { m };
+            k -= d;
+            d
+        } else {
+            0
+        };
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+impl<T: Field, C: EllipticCurve<CurveField = T>> PointOnCurve<T, C>
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
+    pub fn infinity() -> Self {
+        Self(GeneralPoint::Infinite, PhantomData)
+    }
+
+    fn normalize_scalar(&self, k: &BigInt) -> (BigInt, Self) {
+        if k.is_negative() {
+            (-k, -(self.clone()))
+        } else {
+            (k.clone(), self.clone())
+        }
+    }
+
+    pub fn mul_naive(&self, k: &BigInt) -> Self {
+        let (mut k, mut current) = self.normalize_scalar(k);
+
+        let mut result = Self::infinity();
+        while k.is_positive() {
+            if k.is_odd() {
+                result = result + current.clone();
+            }
+            current = current.clone() + current;
+            k >>= 1;
+        }
+        result
+    }
+
+    pub fn mul_wnaf(&self, k: &BigInt) -> Self {
+        let (k, point) = self.normalize_scalar(k);
+
+        let double = point.clone() + point.clone();
+        let mut odd_multiples = vec![point];
+        for _ in 1..(1 << (WNAF_WIDTH - 2)) {
+            let next = odd_multiples.last().unwrap().clone() + double.clone();
+            odd_multiples.push(next);
+        }
+
+        let mut result = Self::infinity();
+        for digit in wnaf_digits(&k, WNAF_WIDTH).into_iter().rev() {
+            result = result.clone() + result;
+            match digit.cmp(&0) {
+                Ordering::Greater => result = result + odd_multiples[(digit / 2) as usize].clone(),
+                Ordering::Less => result = result + -odd_multiples[(-digit / 2) as usize].clone(),
+                Ordering::Equal => {}
+            }
+        }
+        result
+    }
+}
+
+impl<T: Field, C: EllipticCurve<CurveField = T>> Neg for PointOnCurve<T, C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self.0 {
+            GeneralPoint::Finite { x, y } => Self(GeneralPoint::Finite { x, y: -y }, PhantomData),
+            GeneralPoint::Infinite => self,
+        }
+    }
+}
+
+impl<T: Field, C: EllipticCurve<CurveField = T>> Mul<PointOnCurve<T, C>> for BigInt
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
     type Output = PointOnCurve<T, C>;
 
     fn mul(self, rhs: PointOnCurve<T, C>) -> Self::Output {
-        unimplemented!()
+        let result = rhs.mul_wnaf(&self);
+
+        #[cfg(feature = "verify-opt")]
+        assert!(
+            result == rhs.mul_naive(&self),
+            "wNAF scalar multiplication disagrees with double-and-add"
+        );
+
+        result
     }
 }
 
-impl<T: Field<Output = T> + Clone, C: EllipticCurve<T>> Add for PointOnCurve<T, C> {
+impl Mul<S256Point> for &ScalarFieldElement {
+    type Output = S256Point;
+
+    fn mul(self, rhs: S256Point) -> Self::Output {
+        BigInt::from(self.value().clone()) * rhs
+    }
+}
+
+impl Mul<S256Point> for ScalarFieldElement {
+    type Output = S256Point;
+
+    fn mul(self, rhs: S256Point) -> Self::Output {
+        &self * rhs
+    }
+}
+
+impl<T: Field, C: EllipticCurve<CurveField = T>> Add for PointOnCurve<T, C>
+where
+    for<'a> &'a T: FieldRef<'a, T>,
+{
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -83,32 +246,20 @@ impl<T: Field<Output = T> + Clone, C: EllipticCurve<T>> Add for PointOnCurve<T,
             (GeneralPoint::Infinite, r) => Self(r, PhantomData),
             (l, GeneralPoint::Infinite) => Self(l, PhantomData),
             (GeneralPoint::Finite { x: x1, y: y1 }, GeneralPoint::Finite { x: x2, y: y2 }) => {
-                if x1.eq(&x2) {
-                    if y1.ne(&y2) {
-                        Self::new(GeneralPoint::Infinite).unwrap()
-                    } else {
-                        let s = (x1.clone().pow(BigInt::from(2)) * T::from(3) + C::a())
-                            / (y1.clone() * T::from(2));
-
-                        let x3 = s.clone().pow(BigInt::from(2)) - x1.clone() - x2.clone();
-                        Self::new(GeneralPoint::Finite {
-                            x: x3.clone(),
-                            y: s.mul(x1.clone() - x3) - y1.clone(),
-                        })
-                        .unwrap()
+                let s = if x1 == x2 {
+                    if y1 != y2 || y1 == T::from(0) {
+                        return Self::infinity();
                     }
+                    let numerator = &(&(&x1 * &x1) * &T::from(3)) + &C::a();
+                    &numerator / &(&y1 * &T::from(2))
                 } else {
-                    let s = T::from(
-                        T::from((y2.clone() - y1.clone())) / T::from((x2.clone() - x1.clone())),
-                    );
-
-                    let x3 = s.clone().pow(BigInt::from(2)) - x1.clone() - x2.clone();
-                    Self::new(GeneralPoint::Finite {
-                        x: x3.clone(),
-                        y: s.mul(x1.clone() - x3) - y1.clone(),
-                    })
-                    .unwrap()
-                }
+                    &(&y2 - &y1) / &(&x2 - &x1)
+                };
+
+                let x3 = &(&s * &s) - &(&x1 + &x2);
+                let y3 = &(&s * &(&x1 - &x3)) - &y1;
+                // The sum of two points on the curve is on it, so no check is needed.
+                Self(GeneralPoint::finite(x3, y3), PhantomData)
             }
         }
     }
@@ -117,25 +268,30 @@ impl<T: Field<Output = T> + Clone, C: EllipticCurve<T>> Add for PointOnCurve<T,
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::curve::{Secp256k1, TestEllipticCurve};
-    use crate::field::{f64FieldElement, FiniteFieldElement, Prime223};
+    use crate::curve::{OverField, Secp256k1, TestEllipticCurve};
+    use crate::field::Prime223;
+    use crate::rational::RationalElement;
+    use crate::small_field::SmallPrimeField;
+    use proptest::prelude::*;
+
+    type Secp256k1Over223 = OverField<Secp256k1, SmallPrimeField<Prime223>>;
 
     #[test]
     fn create_point_on_curve() {
         assert_eq!(
-            PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-                f64FieldElement::from(-1.0),
-                f64FieldElement::from(-1.0),
+            PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+                RationalElement::from(-1),
+                RationalElement::from(-1),
             )),
             Some(PointOnCurve::<_, TestEllipticCurve>(
-                GeneralPoint::finite(f64FieldElement::from(-1.0), f64FieldElement::from(-1.0)),
+                GeneralPoint::finite(RationalElement::from(-1), RationalElement::from(-1)),
                 PhantomData
             ))
         );
         assert_eq!(
             PointOnCurve::<_, TestEllipticCurve>::new(GeneralPoint::finite(
-                f64FieldElement::from(-1.0),
-                f64FieldElement::from(-2.0)
+                RationalElement::from(-1),
+                RationalElement::from(-2)
             )),
             None
         );
@@ -143,59 +299,59 @@ mod tests {
 
     #[test]
     fn point_on_curve_add() {
-        let p1 = PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-            f64FieldElement::from(2.0),
-            f64FieldElement::from(5.0),
+        let p1 = PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+            RationalElement::from(2),
+            RationalElement::from(5),
         ))
         .unwrap();
-        let p2 = PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-            f64FieldElement::from(-1.0),
-            f64FieldElement::from(-1.0),
+        let p2 = PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+            RationalElement::from(-1),
+            RationalElement::from(-1),
         ))
         .unwrap();
 
         assert_eq!(
             p1 + p2,
-            PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-                f64FieldElement::from(3.0),
-                f64FieldElement::from(-7.0),
+            PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+                RationalElement::from(3),
+                RationalElement::from(-7),
             ))
             .unwrap()
         );
 
-        let p1 = PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-            f64FieldElement::from(2.0),
-            f64FieldElement::from(5.0),
+        let p1 = PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+            RationalElement::from(2),
+            RationalElement::from(5),
         ))
         .unwrap();
-        let p2 = PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-            f64FieldElement::from(2.0),
-            f64FieldElement::from(-5.0),
+        let p2 = PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+            RationalElement::from(2),
+            RationalElement::from(-5),
         ))
         .unwrap();
 
         assert_eq!(
             p1 + p2,
-            PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::Infinite)
+            PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::Infinite)
                 .unwrap()
         );
 
-        let p1 = PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-            f64FieldElement::from(-1.0),
-            f64FieldElement::from(-1.0),
+        let p1 = PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+            RationalElement::from(-1),
+            RationalElement::from(-1),
         ))
         .unwrap();
-        let p2 = PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-            f64FieldElement::from(-1.0),
-            f64FieldElement::from(-1.0),
+        let p2 = PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+            RationalElement::from(-1),
+            RationalElement::from(-1),
         ))
         .unwrap();
 
         assert_eq!(
             p1 + p2,
-            PointOnCurve::<f64FieldElement, TestEllipticCurve>::new(GeneralPoint::finite(
-                f64FieldElement::from(18.0),
-                f64FieldElement::from(77.0),
+            PointOnCurve::<RationalElement, TestEllipticCurve>::new(GeneralPoint::finite(
+                RationalElement::from(18),
+                RationalElement::from(77),
             ))
             .unwrap()
         );
@@ -204,49 +360,114 @@ mod tests {
     fn secp256k1_point(
         x: i64,
         y: i64,
-    ) -> Option<PointOnCurve<FiniteFieldElement<Prime223>, Secp256k1>> {
-        PointOnCurve::<FiniteFieldElement<Prime223>, Secp256k1>::new(GeneralPoint::finite(
-            FiniteFieldElement::from(x),
-            FiniteFieldElement::from(y),
+    ) -> Option<PointOnCurve<SmallPrimeField<Prime223>, Secp256k1Over223>> {
+        PointOnCurve::<SmallPrimeField<Prime223>, Secp256k1Over223>::new(GeneralPoint::finite(
+            SmallPrimeField::from(x),
+            SmallPrimeField::from(y),
         ))
     }
 
+    // y² = x³ - 3x + 2 = (x - 1)²(x + 2) has a node at (1, 0).
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct NodalCurve;
+
+    impl EllipticCurve for NodalCurve {
+        type CurveField = RationalElement;
+
+        fn a() -> RationalElement {
+            RationalElement::from(-3)
+        }
+
+        fn b() -> RationalElement {
+            RationalElement::from(2)
+        }
+    }
+
+    #[test]
+    fn singular_curve_detected() {
+        assert!(Secp256k1::is_valid());
+        assert!(TestEllipticCurve::is_valid());
+        assert!(Secp256k1Over223::is_valid());
+        assert!(!NodalCurve::is_valid());
+
+        assert_eq!(
+            PointOnCurve::<_, NodalCurve>::try_new(GeneralPoint::finite(
+                RationalElement::from(1),
+                RationalElement::from(0)
+            )),
+            Err(CurveError::Singular)
+        );
+        assert_eq!(
+            PointOnCurve::<_, TestEllipticCurve>::try_new(GeneralPoint::finite(
+                RationalElement::from(-1),
+                RationalElement::from(-2)
+            )),
+            Err(CurveError::NotOnCurve)
+        );
+    }
+
+    #[test]
+    fn check_reports_both_sides() {
+        use crate::curve::OffCurve;
+
+        let off = GeneralPoint::finite(RationalElement::from(-1), RationalElement::from(-2));
+        let err = TestEllipticCurve::check(&off).unwrap_err();
+        assert_eq!(
+            err,
+            OffCurve {
+                lhs: RationalElement::from(4),
+                rhs: RationalElement::from(1),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "point is not on the curve: y² = 4 but x³ + ax + b = 1"
+        );
+
+        let on = GeneralPoint::finite(RationalElement::from(-1), RationalElement::from(-1));
+        assert_eq!(TestEllipticCurve::check(&on), Ok(()));
+        assert_eq!(
+            TestEllipticCurve::check(&GeneralPoint::<RationalElement>::Infinite),
+            Ok(())
+        );
+    }
+
     #[test]
     fn curve_on_finite_field() {
         assert!(
-            PointOnCurve::<FiniteFieldElement<Prime223>, Secp256k1>::new(GeneralPoint::finite(
-                FiniteFieldElement::from(192),
-                FiniteFieldElement::from(105)
+            PointOnCurve::<SmallPrimeField<Prime223>, Secp256k1Over223>::new(GeneralPoint::finite(
+                SmallPrimeField::from(192),
+                SmallPrimeField::from(105)
             ),)
             .is_some()
         );
         assert!(
-            PointOnCurve::<FiniteFieldElement<Prime223>, Secp256k1>::new(GeneralPoint::finite(
-                FiniteFieldElement::from(17),
-                FiniteFieldElement::from(56)
+            PointOnCurve::<SmallPrimeField<Prime223>, Secp256k1Over223>::new(GeneralPoint::finite(
+                SmallPrimeField::from(17),
+                SmallPrimeField::from(56)
             ),)
             .is_some()
         );
         assert!(
-            !(PointOnCurve::<FiniteFieldElement<Prime223>, Secp256k1>::new(GeneralPoint::finite(
-                FiniteFieldElement::from(200),
-                FiniteFieldElement::from(119)
+            PointOnCurve::<SmallPrimeField<Prime223>, Secp256k1Over223>::new(GeneralPoint::finite(
+                SmallPrimeField::from(200),
+                SmallPrimeField::from(119)
             ),)
-            .is_some())
+            .is_none()
         );
         assert!(
-            PointOnCurve::<FiniteFieldElement<Prime223>, Secp256k1>::new(GeneralPoint::finite(
-                FiniteFieldElement::from(1),
-                FiniteFieldElement::from(193)
+            PointOnCurve::<SmallPrimeField<Prime223>, Secp256k1Over223>::new(GeneralPoint::finite(
+                SmallPrimeField::from(1),
+                SmallPrimeField::from(193)
             ),)
             .is_some()
         );
         assert!(
-            !(PointOnCurve::<FiniteFieldElement<Prime223>, Secp256k1>::new(GeneralPoint::finite(
-                FiniteFieldElement::from(42),
-                FiniteFieldElement::from(99)
+            PointOnCurve::<SmallPrimeField<Prime223>, Secp256k1Over223>::new(GeneralPoint::finite(
+                SmallPrimeField::from(42),
+                SmallPrimeField::from(99)
             ),)
-            .is_some())
+            .is_none()
         );
     }
 
@@ -264,4 +485,167 @@ mod tests {
         let p2 = secp256k1_point(76, 66).unwrap();
         assert_eq!(p1 + p2, secp256k1_point(47, 71).unwrap());
     }
+
+    #[test]
+    fn scalar_mul_finite_field() {
+        let cases = [
+            (2, (192, 105), Some((49, 71))),
+            (2, (143, 98), Some((64, 168))),
+            (2, (47, 71), Some((36, 111))),
+            (4, (47, 71), Some((194, 51))),
+            (8, (47, 71), Some((116, 55))),
+            (21, (47, 71), None),
+        ];
+
+        for (k, (x, y), expected) in cases {
+            let p = secp256k1_point(x, y).unwrap();
+            let expected = match expected {
+                Some((x, y)) => secp256k1_point(x, y).unwrap(),
+                None => PointOnCurve::infinity(),
+            };
+            assert_eq!(BigInt::from(k) * p, expected);
+            assert_eq!(p.mul_naive(&BigInt::from(k)), expected);
+        }
+
+        let p = secp256k1_point(47, 71).unwrap();
+        assert_eq!(BigInt::from(-1) * p, -p);
+        assert_eq!(BigInt::from(0) * p, PointOnCurve::infinity());
+    }
+
+    fn s256_point(x: &str, y: &str) -> Option<S256Point> {
+        let parse = |v: &str| {
+            Fe256::from_biguint(&num::BigUint::parse_bytes(v.as_bytes(), 16).unwrap()).unwrap()
+        };
+        S256Point::new(GeneralPoint::finite(parse(x), parse(y)))
+    }
+
+    #[test]
+    fn s256_point_add() {
+        let g = s256_point(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        )
+        .unwrap();
+        let g2 = s256_point(
+            "c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+            "1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+        )
+        .unwrap();
+        let g3 = s256_point(
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+        )
+        .unwrap();
+
+        assert_eq!(g + g, g2);
+        assert_eq!(g2 + g, g3);
+        assert!(s256_point(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b9",
+        )
+        .is_none());
+    }
+
+    fn s256_generator() -> S256Point {
+        S256Point::generator()
+    }
+
+    #[test]
+    fn s256_generator_and_order() {
+        assert_eq!(
+            Some(S256Point::generator()),
+            s256_point(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            )
+        );
+
+        let n = BigInt::from(Secp256k1::order());
+        assert_eq!(
+            n,
+            BigInt::parse_bytes(
+                b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                16,
+            )
+            .unwrap()
+        );
+        assert_eq!(n.clone() * s256_generator(), S256Point::infinity());
+        assert_ne!((n - 1) * s256_generator(), S256Point::infinity());
+    }
+
+    #[test]
+    fn lift_x() {
+        let g = s256_generator();
+        assert_eq!(S256Point::lift_x(g.x().unwrap(), false), Some(g));
+        assert_eq!(S256Point::lift_x(g.x().unwrap(), true), Some(-g));
+        // 7 is not a square mod p, so no point has x = 0.
+        assert_eq!(S256Point::lift_x(Fe256::ZERO, false), None);
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let g = s256_generator();
+        assert_eq!(ScalarFieldElement::from(2) * g, g + g);
+        assert_eq!(ScalarFieldElement::from(-1) * g, -g);
+        assert_eq!(ScalarFieldElement::from(0) * g, S256Point::infinity());
+
+        // Scalars wrap at N, so k and k + N name the same point.
+        let n_plus_3 = ScalarFieldElement::from(-1) + ScalarFieldElement::from(4);
+        assert_eq!(n_plus_3, ScalarFieldElement::from(3));
+        assert_eq!(&n_plus_3 * g, BigInt::from(3) * g);
+    }
+
+    fn assert_generator_order<C: CurveGroup + std::fmt::Debug>()
+    where
+        C::CurveField: Field + std::fmt::Debug,
+        for<'a> &'a C::CurveField: FieldRef<'a, C::CurveField>,
+    {
+        assert!(C::is_valid());
+        let g = PointOnCurve::<_, C>::generator();
+        let n = BigInt::from(C::order());
+        assert_eq!(g.mul_wnaf(&n), PointOnCurve::infinity());
+        assert_eq!(g.mul_wnaf(&(n - 1)), -g);
+    }
+
+    #[test]
+    fn named_curves() {
+        use crate::curve::P256;
+
+        assert_generator_order::<Secp256k1>();
+
+        let g = PointOnCurve::<_, P256>::generator();
+        let hex = |s: &str| num::BigUint::parse_bytes(s.as_bytes(), 16).unwrap();
+        let doubled = g.clone() + g;
+        assert_eq!(
+            doubled.x().unwrap().value(),
+            &hex("7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978")
+        );
+        assert_eq!(
+            doubled.y().unwrap().value(),
+            &hex("07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1")
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn wnaf_matches_naive(k: [u8; 32], negative: bool) {
+            let k = BigInt::from_bytes_be(
+                if negative { num_bigint::Sign::Minus } else { num_bigint::Sign::Plus },
+                &k,
+            );
+            let g = s256_generator();
+            prop_assert_eq!(g.mul_wnaf(&k), g.mul_naive(&k));
+        }
+
+        #[test]
+        fn wnaf_matches_naive_f223(k: i64) {
+            let p = secp256k1_point(47, 71).unwrap();
+            prop_assert_eq!(
+                p.mul_wnaf(&BigInt::from(k)),
+                p.mul_naive(&BigInt::from(k))
+            );
+        }
+    }
 }
@@ -0,0 +1,251 @@
+use crate::field::{
+    impl_assign_ops, impl_pow_variants, impl_sum_product, Field, FieldError, Inverse,
+};
+use num::{BigInt, BigRational, BigUint, One, Signed, Zero};
+use num_traits::Pow;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// Exact real-number arithmetic for the curves drawn over ℚ in the early chapters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalElement(BigRational);
+
+impl RationalElement {
+    // None for a zero denominator.
+    pub fn new(numer: impl Into<BigInt>, denom: impl Into<BigInt>) -> Option<Self> {
+        let denom = denom.into();
+        if denom.is_zero() {
+            return None;
+        }
+        Some(Self(BigRational::new(numer.into(), denom)))
+    }
+
+    pub fn value(&self) -> &BigRational {
+        &self.0
+    }
+}
+
+impl<'a> Add<&'a RationalElement> for &'a RationalElement {
+    type Output = RationalElement;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        RationalElement(&self.0 + &rhs.0)
+    }
+}
+
+impl<'a> Sub<&'a RationalElement> for &'a RationalElement {
+    type Output = RationalElement;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        RationalElement(&self.0 - &rhs.0)
+    }
+}
+
+impl<'a> Mul<&'a RationalElement> for &'a RationalElement {
+    type Output = RationalElement;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        RationalElement(&self.0 * &rhs.0)
+    }
+}
+
+impl<'a> Div<&'a RationalElement> for &'a RationalElement {
+    type Output = RationalElement;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * &rhs.inverse().expect("division by zero")
+    }
+}
+
+impl Add for RationalElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl Sub for RationalElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl Mul for RationalElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl Div for RationalElement {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        &self / &rhs
+    }
+}
+
+impl_assign_ops!([] RationalElement);
+impl_sum_product!([] RationalElement);
+impl_pow_variants!([] RationalElement);
+
+impl Neg for RationalElement {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl Inverse for RationalElement {
+    fn inverse(&self) -> Result<Self, FieldError> {
+        if self.0.is_zero() {
+            Err(FieldError::NotInvertible)
+        } else {
+            Ok(Self(self.0.recip()))
+        }
+    }
+}
+
+impl<'a> Pow<&'a BigUint> for RationalElement {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        let mut result = Self::one();
+        for i in (0..rhs.bits()).rev() {
+            result = &result * &result;
+            if rhs.bit(i) {
+                result = &result * &self;
+            }
+        }
+        result
+    }
+}
+
+impl<'a> Pow<&'a BigInt> for RationalElement {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        let base = if rhs.is_negative() {
+            self.inverse().expect("division by zero")
+        } else {
+            self
+        };
+        base.pow(&rhs.magnitude().clone())
+    }
+}
+
+impl From<i64> for RationalElement {
+    fn from(v: i64) -> Self {
+        Self(BigRational::from_integer(BigInt::from(v)))
+    }
+}
+
+impl From<BigRational> for RationalElement {
+    fn from(v: BigRational) -> Self {
+        Self(v)
+    }
+}
+
+impl Zero for RationalElement {
+    fn zero() -> Self {
+        Self(BigRational::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl One for RationalElement {
+    fn one() -> Self {
+        Self(BigRational::one())
+    }
+}
+
+impl fmt::Display for RationalElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+// Accepts integers and fractions such as "-3/4".
+impl FromStr for RationalElement {
+    type Err = FieldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BigRational::from_str(s)
+            .map(Self)
+            .map_err(|_| FieldError::InvalidNumber(s.to_string()))
+    }
+}
+
+impl Field for RationalElement {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_axioms::{any_element, check_field_axioms};
+    use proptest::prelude::*;
+
+    #[test]
+    fn exact_arithmetic() {
+        let third = RationalElement::new(1, 3).unwrap();
+        assert_eq!(
+            third.clone() + third.clone() + third.clone(),
+            RationalElement::one()
+        );
+        assert_eq!(
+            RationalElement::from(1) / RationalElement::from(10) * RationalElement::from(10),
+            RationalElement::one()
+        );
+        assert_eq!(-third.clone(), RationalElement::new(-1, 3).unwrap());
+        assert_eq!(third.inverse(), Ok(RationalElement::from(3)));
+        assert_eq!(
+            RationalElement::zero().inverse(),
+            Err(FieldError::NotInvertible)
+        );
+        assert_eq!(
+            RationalElement::new(2, 3).unwrap().pow(BigInt::from(-3)),
+            RationalElement::new(27, 8).unwrap()
+        );
+        assert_eq!(
+            RationalElement::from(2).pow(100u64).value().numer(),
+            &BigInt::from(2).pow(100u32)
+        );
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(
+            "-3/4".parse::<RationalElement>(),
+            Ok(RationalElement::new(-3, 4).unwrap())
+        );
+        assert_eq!(RationalElement::new(6, -8).unwrap().to_string(), "-3/4");
+        assert_eq!(RationalElement::new(1, 0), None);
+        assert_eq!(RationalElement::from(5).to_string(), "5");
+        assert_eq!(
+            "1/x".parse::<RationalElement>(),
+            Err(FieldError::InvalidNumber("1/x".to_string()))
+        );
+    }
+
+    // Fractions of two full-width integers, so reduction by the gcd is exercised too.
+    fn any_rational() -> impl Strategy<Value = RationalElement> {
+        let denominator = any_element::<RationalElement>().prop_filter("zero", |d| !d.is_zero());
+        (any_element::<RationalElement>(), denominator).prop_map(|(n, d)| n / d)
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms(a in any_rational(), b in any_rational(), c in any_rational()) {
+            check_field_axioms(a, b, c)?;
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::fe256::Fe256;
+use crate::field::ScalarFieldElement;
+use crate::hashes::tagged_hash_parts;
+use crate::msm::strauss;
+use crate::point::S256Point;
+use num::{BigInt, BigUint};
+use std::convert::TryInto;
+
+// BIP340 challenge e = hash_BIP0340/challenge(x(R) || x(P) || m) mod n.
+pub(crate) fn challenge(r: &[u8; 32], pubkey: &[u8; 32], msg: &[u8]) -> ScalarFieldElement {
+    let hash = tagged_hash_parts("BIP0340/challenge", &[r, pubkey, msg]);
+    ScalarFieldElement::from_biguint_reduced(&BigUint::from_bytes_be(&hash))
+}
+
+// BIP340 verification against an x-only key: R = s·G - e·P must have an even y and x(R) = r.
+pub fn verify(pubkey: &[u8; 32], msg: &[u8], sig: &[u8; 64]) -> bool {
+    let p = match Fe256::from_bytes_be(pubkey).and_then(|x| S256Point::lift_x(x, false)) {
+        Some(p) => p,
+        None => return false,
+    };
+    let r: [u8; 32] = sig[..32].try_into().unwrap();
+    if Fe256::from_bytes_be(&r).is_none() {
+        return false;
+    }
+    let s = match ScalarFieldElement::from_bytes_be(sig[32..].try_into().unwrap()) {
+        Some(s) => s,
+        None => return false,
+    };
+
+    let e = challenge(&r, pubkey, msg);
+    let point = strauss(&[
+        (BigInt::from(s.value().clone()), S256Point::generator()),
+        (BigInt::from((-e).value().clone()), p),
+    ]);
+    match (point.x(), point.y()) {
+        (Some(x), Some(y)) => !y.is_odd() && x.to_bytes_be() == r,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    // Vectors 0, 1 and 5 of BIP340's test-vectors.csv.
+    #[test]
+    fn bip340_vectors() {
+        let check = |pubkey: &str, msg: &str, sig: &str| {
+            verify(
+                &hex::decode(pubkey).unwrap().try_into().unwrap(),
+                &hex::decode(msg).unwrap(),
+                &hex::decode(sig).unwrap().try_into().unwrap(),
+            )
+        };
+        assert!(check(
+            "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+             25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+        ));
+        assert!(check(
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+             8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+        ));
+        // Public key not on the curve.
+        assert!(!check(
+            "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769\
+             69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        ));
+        assert!(!check(
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C88",
+            "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+             8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use sha2::{Digest, Sha256};
+
+// Double SHA-256 for txids, merkle nodes and block headers. The hardware path is the sha2
+// crate's own: it detects SHA-NI on x86-64 and the SHA2 extensions on AArch64 at runtime and
+// falls back to its portable code otherwise. Building with the `portable-sha2` feature forces
+// the portable code, which is what benches/sha256d.rs compares against.
+pub fn hash256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn known_vectors() {
+        for (data, digest) in [
+            (
+                &b""[..],
+                "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456",
+            ),
+            (
+                b"hello",
+                "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50",
+            ),
+        ] {
+            assert_eq!(hex::encode(&hash256(data)), digest);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::field::{
+    forward_ref_binop, impl_assign_ops, impl_pow_variants, impl_sum_product, pow_mod,
+    reduce_exponent, signed_exponent, Field, FieldError, FiniteFieldElement, Inverse, Prime,
+    PrimeField,
+};
+use num::{BigInt, BigUint, One, ToPrimitive, Zero};
+use num_traits::Pow;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// Primes given to `def_prime_struct!` as integer literals fit in a u64.
+pub trait SmallPrime: Prime {
+    const MODULUS: u64;
+    // The smallest quadratic non-residue, computed at compile time; None for p = 2.
+    const NON_RESIDUE: Option<u64> = smallest_non_residue(Self::MODULUS);
+}
+
+const fn smallest_non_residue(p: u64) -> Option<u64> {
+    if p == 2 {
+        return None;
+    }
+    let mut v = 2;
+    while pow_mod(v, (p - 1) / 2, p) != p - 1 {
+        v += 1;
+    }
+    Some(v)
+}
+
+// Elements of a prime field whose modulus fits in a u64; products are taken in u128, so
+// nothing allocates.
+pub struct SmallPrimeField<P: SmallPrime>(u64, PhantomData<P>);
+
+impl<P: SmallPrime> SmallPrimeField<P> {
+    pub fn new(value: u64) -> Option<Self> {
+        if value < P::MODULUS {
+            Some(Self(value, PhantomData))
+        } else {
+            None
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    fn reduce(value: u128) -> Self {
+        Self((value % P::MODULUS as u128) as u64, PhantomData)
+    }
+}
+
+impl<P: SmallPrime> Clone for SmallPrimeField<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: SmallPrime> Copy for SmallPrimeField<P> {}
+
+impl<P: SmallPrime> PartialEq for SmallPrimeField<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<P: SmallPrime> Eq for SmallPrimeField<P> {}
+
+impl<P: SmallPrime> fmt::Debug for SmallPrimeField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SmallPrimeField_{}({})", P::MODULUS, self.0)
+    }
+}
+
+impl<P: SmallPrime> fmt::Display for SmallPrimeField<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FieldElement_{}({})", P::MODULUS, self.0)
+    }
+}
+
+impl<P: SmallPrime> Add for SmallPrimeField<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::reduce(self.0 as u128 + rhs.0 as u128)
+    }
+}
+
+impl<P: SmallPrime> Sub for SmallPrimeField<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::reduce(self.0 as u128 + P::MODULUS as u128 - rhs.0 as u128)
+    }
+}
+
+impl<P: SmallPrime> Mul for SmallPrimeField<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::reduce(self.0 as u128 * rhs.0 as u128)
+    }
+}
+
+impl<P: SmallPrime> Div for SmallPrimeField<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<P: SmallPrime> Neg for SmallPrimeField<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::zero() - self
+    }
+}
+
+forward_ref_binop!([P: SmallPrime] SmallPrimeField<P>, Add, add);
+forward_ref_binop!([P: SmallPrime] SmallPrimeField<P>, Sub, sub);
+forward_ref_binop!([P: SmallPrime] SmallPrimeField<P>, Mul, mul);
+forward_ref_binop!([P: SmallPrime] SmallPrimeField<P>, Div, div);
+impl_assign_ops!([P: SmallPrime] SmallPrimeField<P>);
+impl_sum_product!([P: SmallPrime] SmallPrimeField<P>);
+impl_pow_variants!([P: SmallPrime] SmallPrimeField<P>);
+
+impl<P: SmallPrime> Inverse for SmallPrimeField<P> {
+    fn inverse(&self) -> Result<Self, FieldError> {
+        if self.is_zero() {
+            Err(FieldError::NotInvertible)
+        } else {
+            Ok(Self(
+                pow_mod(self.0, P::MODULUS - 2, P::MODULUS),
+                PhantomData,
+            ))
+        }
+    }
+}
+
+impl<'a, P: SmallPrime> Pow<&'a BigUint> for SmallPrimeField<P> {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigUint) -> Self::Output {
+        let exponent = reduce_exponent(rhs, &BigUint::from(P::MODULUS))
+            .to_u64()
+            .unwrap();
+        Self(pow_mod(self.0, exponent, P::MODULUS), PhantomData)
+    }
+}
+
+impl<'a, P: SmallPrime> Pow<&'a BigInt> for SmallPrimeField<P> {
+    type Output = Self;
+
+    fn pow(self, rhs: &'a BigInt) -> Self::Output {
+        self.pow(&signed_exponent(rhs, &BigUint::from(P::MODULUS)))
+    }
+}
+
+impl<P: SmallPrime> From<i64> for SmallPrimeField<P> {
+    fn from(v: i64) -> Self {
+        let magnitude = Self::reduce(v.unsigned_abs() as u128);
+        if v < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl<P: SmallPrime> From<SmallPrimeField<P>> for FiniteFieldElement<P> {
+    fn from(v: SmallPrimeField<P>) -> Self {
+        FiniteFieldElement::new_from_u64(v.0).unwrap()
+    }
+}
+
+impl<P: SmallPrime> From<&FiniteFieldElement<P>> for SmallPrimeField<P> {
+    fn from(v: &FiniteFieldElement<P>) -> Self {
+        Self(v.value().to_u64().unwrap(), PhantomData)
+    }
+}
+
+impl<P: SmallPrime> Zero for SmallPrimeField<P> {
+    fn zero() -> Self {
+        Self(0, PhantomData)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<P: SmallPrime> One for SmallPrimeField<P> {
+    fn one() -> Self {
+        Self(1, PhantomData)
+    }
+}
+
+impl<P: SmallPrime> Field for SmallPrimeField<P> {}
+
+impl<P: SmallPrime> PrimeField for SmallPrimeField<P> {
+    fn modulus() -> BigUint {
+        BigUint::from(P::MODULUS)
+    }
+
+    fn num_bytes() -> usize {
+        P::num_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Prime13, Prime19, Prime223, Prime29};
+    use crate::field_axioms::{any_element, check_fermat_identity, check_field_axioms};
+    use proptest::prelude::*;
+
+    #[test]
+    fn book_exercises() {
+        let e = SmallPrimeField::<Prime13>::from;
+        assert_eq!(e(7) + e(12), e(6));
+        assert_eq!(e(3) * e(12), e(10));
+        assert_eq!(e(3).pow(3u64), e(1));
+        assert_eq!(e(7).pow(BigInt::from(-3)), e(8));
+
+        let e = SmallPrimeField::<Prime19>::from;
+        assert_eq!(e(2) / e(7), e(3));
+        assert_eq!(e(7) / e(5), e(9));
+
+        assert_eq!(SmallPrimeField::<Prime29>::from(-1).value(), 28);
+        assert_eq!(SmallPrimeField::<Prime13>::new(13), None);
+        assert_eq!(
+            SmallPrimeField::<Prime13>::zero().inverse(),
+            Err(FieldError::NotInvertible)
+        );
+        assert_eq!(e(5).to_string(), "FieldElement_19(5)");
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms_prime13(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<SmallPrimeField<Prime13>>(a, b, c)?;
+        }
+
+        #[test]
+        fn field_axioms_prime223(a in any_element(), b in any_element(), c in any_element()) {
+            check_field_axioms::<SmallPrimeField<Prime223>>(a, b, c)?;
+            check_fermat_identity::<SmallPrimeField<Prime223>>(a, &BigUint::from(223u64))?;
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matches_finite_field_element(a: i64, b: i64, e: i64) {
+            type Big = FiniteFieldElement<Prime223>;
+            type Small = SmallPrimeField<Prime223>;
+
+            let (ba, bb) = (Big::from(a), Big::from(b));
+            let (sa, sb) = (Small::from(a), Small::from(b));
+            prop_assert_eq!(Small::from(&ba), sa);
+            prop_assert_eq!(Small::from(&(&ba + &bb)), sa + sb);
+            prop_assert_eq!(Small::from(&(&ba - &bb)), sa - sb);
+            prop_assert_eq!(Small::from(&(&ba * &bb)), sa * sb);
+            prop_assert_eq!(Big::from(sa.pow(BigInt::from(e))), ba.clone().pow(BigInt::from(e)));
+            if !bb.is_zero() {
+                prop_assert_eq!(Small::from(&(&ba / &bb)), sa / sb);
+            }
+        }
+    }
+}
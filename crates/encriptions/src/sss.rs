@@ -0,0 +1,228 @@
+use crate::field::{Choice, ConstantTimeEq, FiniteFieldElement, Inverse, Prime};
+use num::BigUint;
+use num_traits::Zero;
+use rand_core::{CryptoRng, RngCore};
+use std::fmt;
+
+// One point (x, f(x)) on the sharing polynomial. x is never zero, since f(0) is the secret.
+#[derive(Clone)]
+pub struct Share<P: Prime> {
+    index: u32,
+    value: FiniteFieldElement<P>,
+}
+
+impl<P: Prime> Share<P> {
+    // Rejects indices that are zero in the field.
+    pub fn new(index: u32, value: FiniteFieldElement<P>) -> Option<Self> {
+        if x::<P>(index).is_zero() {
+            return None;
+        }
+        Some(Self { index, value })
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn value(&self) -> &FiniteFieldElement<P> {
+        &self.value
+    }
+}
+
+// The value is compared in constant time, with the public index folded into the same choice.
+impl<P: Prime> PartialEq for Share<P> {
+    fn eq(&self, other: &Self) -> bool {
+        (Choice::from((self.index == other.index) as u8) & self.value.ct_eq(&other.value)).into()
+    }
+}
+
+// Each share is a piece of the secret, so keep its value out of logs.
+impl<P: Prime> fmt::Debug for Share<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Share({}, …)", self.index)
+    }
+}
+
+fn x<P: Prime>(index: u32) -> FiniteFieldElement<P> {
+    FiniteFieldElement::from_biguint_reduced(&BigUint::from(index))
+}
+
+// f(index) by Horner's rule.
+pub(crate) fn evaluate<P: Prime>(
+    coefficients: &[FiniteFieldElement<P>],
+    index: u32,
+) -> FiniteFieldElement<P> {
+    let x = x::<P>(index);
+    coefficients
+        .iter()
+        .rev()
+        .fold(FiniteFieldElement::ZERO, |acc, c| &(&acc * &x) + c)
+}
+
+// λᵢ = Π_{j≠i} xⱼ/(xⱼ - xᵢ), the weight of the i-th index when interpolating at zero. None if
+// another index is the same field element.
+pub(crate) fn lagrange_at_zero<P: Prime>(
+    indices: &[u32],
+    i: usize,
+) -> Option<FiniteFieldElement<P>> {
+    let xi = x::<P>(indices[i]);
+    let mut numerator = FiniteFieldElement::from(1);
+    let mut denominator = FiniteFieldElement::from(1);
+    for (j, &index) in indices.iter().enumerate() {
+        if i != j {
+            let xj = x::<P>(index);
+            denominator = &denominator * &(&xj - &xi);
+            numerator = &numerator * &xj;
+        }
+    }
+    Some(&numerator * &denominator.inverse().ok()?)
+}
+
+// Shares f(1), ..., f(n) of a random polynomial of degree threshold - 1 with f(0) = secret. Any
+// threshold of them determine f; fewer reveal nothing about f(0).
+pub fn split<P: Prime + Clone, R: RngCore + CryptoRng>(
+    secret: &FiniteFieldElement<P>,
+    threshold: usize,
+    shares: usize,
+    rng: &mut R,
+) -> Result<Vec<Share<P>>, SssError> {
+    if threshold == 0 || threshold > shares {
+        return Err(SssError::InvalidThreshold { threshold, shares });
+    }
+    // Indices 1..=n must stay distinct and non-zero in the field.
+    if shares > u32::MAX as usize || BigUint::from(shares) >= P::get_prime() {
+        return Err(SssError::TooManyShares(shares));
+    }
+
+    let coefficients: Vec<FiniteFieldElement<P>> = std::iter::once(secret.clone())
+        .chain((1..threshold).map(|_| FiniteFieldElement::random(rng)))
+        .collect();
+    Ok((1..=shares as u32)
+        .map(|index| Share {
+            index,
+            value: evaluate(&coefficients, index),
+        })
+        .collect())
+}
+
+// Lagrange interpolation at zero: f(0) = Σ yᵢ · Π_{j≠i} xⱼ/(xⱼ - xᵢ). Given fewer shares than
+// the threshold this still returns a value, just not the secret.
+pub fn combine<P: Prime>(shares: &[Share<P>]) -> Result<FiniteFieldElement<P>, SssError> {
+    if shares.is_empty() {
+        return Err(SssError::NoShares);
+    }
+    let indices: Vec<u32> = shares.iter().map(Share::index).collect();
+    let mut secret = FiniteFieldElement::ZERO;
+    for (i, share) in shares.iter().enumerate() {
+        let lambda = lagrange_at_zero(&indices, i).ok_or(SssError::DuplicateIndex(share.index))?;
+        secret = &secret + &(&share.value * &lambda);
+    }
+    Ok(secret)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SssError {
+    InvalidThreshold { threshold: usize, shares: usize },
+    TooManyShares(usize),
+    NoShares,
+    DuplicateIndex(u32),
+}
+
+impl fmt::Display for SssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SssError::InvalidThreshold { threshold, shares } => write!(
+                f,
+                "threshold {} must be between 1 and the number of shares {}",
+                threshold, shares
+            ),
+            SssError::TooManyShares(n) => {
+                write!(f, "{} shares do not have distinct non-zero indices", n)
+            }
+            SssError::NoShares => write!(f, "no shares to combine"),
+            SssError::DuplicateIndex(index) => write!(f, "share index {} appears twice", index),
+        }
+    }
+}
+
+impl std::error::Error for SssError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{Prime13, ScalarFieldElement};
+    use crate::keys::PrivateKey;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn three_of_five() {
+        let mut rng = StdRng::seed_from_u64(597);
+        let key = PrivateKey::generate(&mut rng);
+        let shares = split(key.secret(), 3, 5, &mut rng).unwrap();
+        assert_eq!(
+            shares.iter().map(Share::index).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                    let secret = combine(&subset).unwrap();
+                    assert_eq!(PrivateKey::new(secret).as_ref(), Some(&key));
+                }
+            }
+        }
+        assert_eq!(combine(&shares).as_ref(), Ok(key.secret()));
+        assert_ne!(combine(&shares[..2]).as_ref(), Ok(key.secret()));
+        assert_eq!(format!("{:?}", shares[0]), "Share(1, …)");
+    }
+
+    #[test]
+    fn one_of_n_is_the_secret() {
+        let mut rng = StdRng::seed_from_u64(598);
+        let secret = ScalarFieldElement::from(42);
+        for share in split(&secret, 1, 3, &mut rng).unwrap() {
+            assert_eq!(share.value(), &secret);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        type F13 = FiniteFieldElement<Prime13>;
+        let mut rng = StdRng::seed_from_u64(599);
+        let secret = F13::from(5);
+
+        assert_eq!(
+            split(&secret, 0, 3, &mut rng),
+            Err(SssError::InvalidThreshold {
+                threshold: 0,
+                shares: 3
+            })
+        );
+        assert_eq!(
+            split(&secret, 4, 3, &mut rng),
+            Err(SssError::InvalidThreshold {
+                threshold: 4,
+                shares: 3
+            })
+        );
+        // Index 13 would be zero mod 13.
+        assert_eq!(
+            split(&secret, 2, 13, &mut rng),
+            Err(SssError::TooManyShares(13))
+        );
+        let shares = split(&secret, 12, 12, &mut rng).unwrap();
+        assert_eq!(combine(&shares), Ok(secret.clone()));
+
+        assert_eq!(combine::<Prime13>(&[]), Err(SssError::NoShares));
+        assert_eq!(Share::new(26, F13::from(1)), None);
+        // 14 ≡ 1 mod 13, so it collides with share 1.
+        let duplicate = Share::new(14, F13::from(1)).unwrap();
+        assert_eq!(
+            combine(&[shares[0].clone(), duplicate]),
+            Err(SssError::DuplicateIndex(1))
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crate::fe256::Fe256;
+use crate::field::ScalarFieldElement;
+use crate::hashes::{tagged_hash_parts, TapBranchHash, TapTweakHash};
+use crate::keys::PrivateKey;
+use crate::point::S256Point;
+use num::BigUint;
+
+pub(crate) fn hash_to_scalar(tag: &str, data: &[&[u8]]) -> ScalarFieldElement {
+    ScalarFieldElement::from_biguint_reduced(&BigUint::from_bytes_be(&tagged_hash_parts(tag, data)))
+}
+
+// t = hash_TapTweak(P || merkle_root); a key-path-only output commits to P alone.
+pub fn tap_tweak_hash(
+    internal_key: &[u8; 32],
+    merkle_root: Option<&TapBranchHash>,
+) -> TapTweakHash {
+    let root = merkle_root
+        .map(|root| root.as_byte_array().as_slice())
+        .unwrap_or(&[]);
+    TapTweakHash::from_byte_array(tagged_hash_parts("TapTweak", &[internal_key, root]))
+}
+
+// BIP341 taproot_tweak_pubkey: Q = lift_x(P) + t·G. Returns x(Q) and whether Q's y is odd,
+// which the control block needs for script-path spends.
+pub fn tweak_public_key(
+    internal_key: &[u8; 32],
+    merkle_root: Option<&TapBranchHash>,
+) -> Option<([u8; 32], bool)> {
+    let p = S256Point::lift_x(Fe256::from_bytes_be(internal_key)?, false)?;
+    let t = ScalarFieldElement::from_bytes_be(
+        tap_tweak_hash(internal_key, merkle_root).as_byte_array(),
+    )?;
+    let q = &t * S256Point::generator() + p;
+    Some((q.x()?.to_bytes_be(), q.y()?.is_odd()))
+}
+
+// BIP341 taproot_tweak_seckey: the secret is negated first when its point has an odd y, so the
+// result matches the tweak of the x-only internal key.
+pub fn tweak_private_key(
+    key: &PrivateKey,
+    merkle_root: Option<&TapBranchHash>,
+) -> Option<PrivateKey> {
+    let point = key.public_key().point();
+    let secret = if point.y()?.is_odd() {
+        -key.secret().clone()
+    } else {
+        key.secret().clone()
+    };
+    let internal_key = point.x()?.to_bytes_be();
+    let t = ScalarFieldElement::from_bytes_be(
+        tap_tweak_hash(&internal_key, merkle_root).as_byte_array(),
+    )?;
+    PrivateKey::new(secret + t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+    use std::convert::TryInto;
+
+    fn bytes32(s: &str) -> [u8; 32] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    // From BIP341's wallet-test-vectors.json, scriptPubKey section.
+    #[test]
+    fn bip341_output_keys() {
+        let internal = bytes32("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d");
+        assert_eq!(
+            tap_tweak_hash(&internal, None).to_string(),
+            "b86e7be8f39bab32a6f2c0443abbc210f0edac0e2c53d501b36b64437d9c6c70"
+        );
+        let (output, _) = tweak_public_key(&internal, None).unwrap();
+        assert_eq!(
+            hex::encode(&output),
+            "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"
+        );
+
+        let internal = bytes32("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27");
+        let root: TapBranchHash =
+            "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            tap_tweak_hash(&internal, Some(&root)).to_string(),
+            "cbd8679ba636c1110ea247542cfbd964131a6be84f873f7f3b62a777528ed001"
+        );
+        let (output, _) = tweak_public_key(&internal, Some(&root)).unwrap();
+        assert_eq!(
+            hex::encode(&output),
+            "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3"
+        );
+    }
+
+    #[test]
+    fn private_tweak_matches_public_tweak() {
+        let root = TapBranchHash::from_byte_array([7; 32]);
+        let mut parities = vec![];
+        // -1 gives -G, whose y is odd.
+        for secret in [1, -1] {
+            let key = PrivateKey::new(ScalarFieldElement::from(secret)).unwrap();
+            parities.push(key.public_key().point().y().unwrap().is_odd());
+            let internal = key.public_key().point().x().unwrap().to_bytes_be();
+
+            for merkle_root in [None, Some(&root)] {
+                let tweaked = tweak_private_key(&key, merkle_root).unwrap();
+                let point = tweaked.public_key().point();
+                assert_eq!(
+                    tweak_public_key(&internal, merkle_root),
+                    Some((
+                        point.x().unwrap().to_bytes_be(),
+                        point.y().unwrap().is_odd()
+                    ))
+                );
+            }
+        }
+        assert!(parities.contains(&true) && parities.contains(&false));
+    }
+}
@@ -0,0 +1,183 @@
+use crate::curve::EllipticCurve;
+use crate::extension::QuadraticNonResidue;
+use crate::field::PrimeField;
+use num::{BigInt, ToPrimitive};
+use num_traits::Pow;
+use std::fmt;
+use std::marker::PhantomData;
+
+// The quadratic twist of `C` by a non-residue d: d·y² = x³ + ax + b, written in short
+// Weierstrass form as y² = x³ + ad²x + bd³. Every x has x³ + ax + b a square on exactly one of
+// the two curves (or zero on both), so #E + #E' = 2p + 2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadraticTwist<C>(PhantomData<fn() -> C>);
+
+impl<C: EllipticCurve> EllipticCurve for QuadraticTwist<C>
+where
+    C::CurveField: QuadraticNonResidue,
+{
+    type CurveField = C::CurveField;
+
+    fn a() -> C::CurveField {
+        let d = C::CurveField::non_residue().expect("the twist needs an odd characteristic");
+        C::a() * d.clone() * d
+    }
+
+    fn b() -> C::CurveField {
+        let d = C::CurveField::non_residue().expect("the twist needs an odd characteristic");
+        C::b() * d.clone() * d.clone() * d
+    }
+}
+
+fn small_modulus<F: PrimeField>() -> u64 {
+    F::modulus()
+        .to_u64()
+        .expect("twist orders are only computed for small primes")
+}
+
+// The twist's order from the trace: #E' = 2p + 2 - #E.
+pub fn twist_order<C: EllipticCurve>() -> u64
+where
+    C::CurveField: PrimeField,
+{
+    2 * small_modulus::<C::CurveField>() + 2 - C::count_points()
+}
+
+fn prime_factors_up_to(mut n: u64, bound: u64) -> Vec<u64> {
+    let mut factors = vec![];
+    let mut f = 2;
+    while f <= bound && f <= n {
+        if n.is_multiple_of(f) {
+            factors.push(f);
+            while n.is_multiple_of(f) {
+                n /= f;
+            }
+        }
+        f += 1;
+    }
+    factors
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwistWarning {
+    pub twist_order: u64,
+    pub small_factors: Vec<u64>,
+}
+
+impl fmt::Display for TwistWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x lies on the quadratic twist, whose order {} has small prime factors {:?}",
+            self.twist_order, self.small_factors
+        )
+    }
+}
+
+impl std::error::Error for TwistWarning {}
+
+// An x-only ladder never looks at y, so it accepts an x whose point lies on the twist instead.
+// If the twist order has prime factors up to `bound`, such an x can sit in a small subgroup and
+// leak the scalar modulo those factors.
+pub fn check_x_only<C: EllipticCurve>(x: &C::CurveField, bound: u64) -> Result<(), TwistWarning>
+where
+    C::CurveField: PrimeField,
+{
+    let p = small_modulus::<C::CurveField>();
+    let rhs = x.clone() * x.clone() * x.clone() + C::a() * x.clone() + C::b();
+    let on_twist = rhs != C::CurveField::from(0)
+        && rhs.pow(BigInt::from((p - 1) / 2)) != C::CurveField::from(1);
+    if !on_twist {
+        return Ok(());
+    }
+
+    let twist_order = twist_order::<C>();
+    let small_factors = prime_factors_up_to(twist_order, bound);
+    if small_factors.is_empty() {
+        Ok(())
+    } else {
+        Err(TwistWarning {
+            twist_order,
+            small_factors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{OverField, Secp256k1, ToyCurve223};
+    use crate::field::{Prime13, Prime223, Prime29};
+    use crate::point::{GeneralPoint, PointOnCurve};
+    use crate::small_field::SmallPrimeField;
+
+    type F223 = SmallPrimeField<Prime223>;
+    type Curve223 = OverField<Secp256k1, F223>;
+
+    #[test]
+    fn twist_order_matches_count() {
+        fn check<C: EllipticCurve>(p: u64)
+        where
+            C::CurveField: PrimeField + QuadraticNonResidue,
+        {
+            let twisted = QuadraticTwist::<C>::count_points();
+            assert_eq!(twisted, twist_order::<C>());
+            assert_eq!(C::count_points() + twisted, 2 * p + 2);
+        }
+
+        check::<Curve223>(223);
+        check::<ToyCurve223>(223);
+        check::<OverField<Secp256k1, SmallPrimeField<Prime13>>>(13);
+        check::<OverField<Secp256k1, SmallPrimeField<Prime29>>>(29);
+    }
+
+    #[test]
+    fn twist_points_map_back_to_curve() {
+        // (x, y) on the twist corresponds to (x/d, y/d²) satisfying d·y² = x³ + ax + b.
+        let d = F223::non_residue().unwrap();
+        for x in 0..223 {
+            for y in 0..223 {
+                let (x, y) = (F223::from(x), F223::from(y));
+                if QuadraticTwist::<Curve223>::on(&GeneralPoint::finite(x, y)) {
+                    let (u, v) = (x / d, y / (d * d));
+                    assert_eq!(d * v * v, u * u * u + F223::from(7));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn x_only_warning() {
+        let twist = twist_order::<Curve223>();
+        let small = prime_factors_up_to(twist, 100);
+        assert!(!small.is_empty(), "twist order {}", twist);
+
+        // Any x of a point on the curve itself is fine.
+        let g = PointOnCurve::<_, ToyCurve223>::generator();
+        assert_eq!(check_x_only::<ToyCurve223>(&g.x().unwrap(), 100), Ok(()));
+
+        // x³ + 7 is a non-residue for this x, so it only exists on the twist.
+        let x = (0..223)
+            .map(F223::from)
+            .find(|x| {
+                let rhs = *x * *x * *x + F223::from(7);
+                rhs.pow(BigInt::from(111)) == -F223::from(1)
+            })
+            .unwrap();
+        assert_eq!(
+            check_x_only::<Curve223>(&x, 100),
+            Err(TwistWarning {
+                twist_order: twist,
+                small_factors: small,
+            })
+        );
+        assert_eq!(check_x_only::<Curve223>(&x, 1), Ok(()));
+    }
+
+    #[test]
+    fn factors() {
+        assert_eq!(prime_factors_up_to(2 * 2 * 3 * 101, 50), vec![2, 3]);
+        assert_eq!(prime_factors_up_to(97, 100), vec![97]);
+        assert_eq!(prime_factors_up_to(1, 100), Vec::<u64>::new());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::encode::{read_u16_le, read_u32_le, read_u64_le};
+use std::io::{self, Read};
+
+// Bitcoin's CompactSize: values below 0xfd are one byte; larger ones are 0xfd, 0xfe or 0xff
+// followed by a little-endian u16, u32 or u64.
+pub fn encode_varint(n: u64) -> Vec<u8> {
+    if n < 0xfd {
+        vec![n as u8]
+    } else if n <= 0xffff {
+        let mut out = vec![0xfd];
+        out.extend((n as u16).to_le_bytes());
+        out
+    } else if n <= 0xffff_ffff {
+        let mut out = vec![0xfe];
+        out.extend((n as u32).to_le_bytes());
+        out
+    } else {
+        let mut out = vec![0xff];
+        out.extend(n.to_le_bytes());
+        out
+    }
+}
+
+// Rejects encodings longer than needed, as Bitcoin Core does, so every value has exactly one
+// serialization.
+pub fn read_varint(reader: &mut impl Read) -> io::Result<u64> {
+    let mut prefix = [0u8; 1];
+    reader.read_exact(&mut prefix)?;
+    let (n, min) = match prefix[0] {
+        0xfd => (read_u16_le(reader)? as u64, 0xfd),
+        0xfe => (read_u32_le(reader)? as u64, 0x10000),
+        0xff => (read_u64_le(reader)?, 0x1_0000_0000),
+        n => return Ok(n as u64),
+    };
+    if n < min {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("non-canonical varint for {}", n),
+        ));
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boundaries() {
+        let cases: [(u64, &[u8]); 9] = [
+            (0, &[0x00]),
+            (0xfc, &[0xfc]),
+            (0xfd, &[0xfd, 0xfd, 0x00]),
+            (0xffff, &[0xfd, 0xff, 0xff]),
+            (0x10000, &[0xfe, 0x00, 0x00, 0x01, 0x00]),
+            (0xffff_ffff, &[0xfe, 0xff, 0xff, 0xff, 0xff]),
+            (
+                0x1_0000_0000,
+                &[0xff, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00],
+            ),
+            (
+                0x0102_0304_0506_0708,
+                &[0xff, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01],
+            ),
+            (u64::MAX, &[0xff; 9]),
+        ];
+        for (n, encoded) in cases.iter() {
+            assert_eq!(encode_varint(*n), *encoded);
+            let mut reader = &encoded[..];
+            assert_eq!(read_varint(&mut reader).unwrap(), *n);
+            assert!(reader.is_empty());
+        }
+
+        // Only the varint is consumed.
+        let mut reader = &[0xfd, 0x00, 0x01, 0xaa][..];
+        assert_eq!(read_varint(&mut reader).unwrap(), 0x100);
+        assert_eq!(reader, [0xaa]);
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        for encoded in [
+            &[][..],
+            &[0xfd, 0x00],
+            &[0xfe, 0x00, 0x00, 0x01],
+            &[0xff, 0, 0, 0, 0, 0, 0, 0],
+        ] {
+            let err = read_varint(&mut &encoded[..]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+        for encoded in [
+            &[0xfd, 0xfc, 0x00][..],
+            &[0xfe, 0xff, 0xff, 0x00, 0x00],
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00],
+        ] {
+            let err = read_varint(&mut &encoded[..]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}